@@ -1,6 +1,17 @@
+use crate::cli::plan::PlanFormat;
+use crate::schema::doc::DocFormat;
+use crate::schema::export::ExportFormat;
+use crate::schema::export::sql::Dialect;
+use crate::schema::format::Order;
+use crate::schema::lint::Rule;
+use crate::tools::package::PackageMetadata;
 use crate::wrappers::flatc::Language;
 
-use clap::{Parser, Subcommand};
+use baad::helpers::ServerRegion;
+use clap::{Parser, Subcommand, ValueEnum};
+use eyre::{eyre, Result};
+use std::fmt;
+use std::net::IpAddr;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -10,10 +21,18 @@ pub struct Args {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Configuration file path (defaults to ./config.toml)
+    /// Project configuration file path (defaults to ./config.toml and ./bafb.toml)
     #[arg(long, global = true)]
     pub config: Option<PathBuf>,
 
+    /// Override a configuration value, e.g. flatc.scoped_enums=false
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+
+    /// Configuration profile to apply on top of the base configuration
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Force update
     #[arg(short, long)]
     pub update: bool,
@@ -24,7 +43,18 @@ pub struct Args {
 
     /// Enable verbose output
     #[arg(short, long)]
-    pub verbose: bool
+    pub verbose: bool,
+
+    /// Print the resolved commands without downloading or running anything
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text"
+    )]
+    pub dry_run: Option<PlanFormat>,
 }
 
 #[derive(Subcommand)]
@@ -35,17 +65,291 @@ pub enum Commands {
     },
     Generate {
         /// FlatBuffers schema file
+        #[arg(short, long, required_unless_present = "region", conflicts_with = "region")]
+        fbs: Option<PathBuf>,
+
+        /// Dump the schema of a region first, reusing the cached dump of the current game build
+        #[arg(long)]
+        region: Option<Region>,
+
+        /// Programming languages to generate, repeatable or comma separated (defaults to flatc.languages)
+        #[arg(short, long, value_delimiter = ',')]
+        language: Vec<Language>,
+
+        /// Output path (defaults to flatc.output_path)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+
+        #[command(flatten)]
+        codegen: CodegenArgs,
+
+        #[command(flatten)]
+        package: PackageArgs,
+    },
+    /// Split, merge and inspect FlatBuffers schemas with the native parser
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+    /// Load decoded Excel tables into a SQLite database
+    Import {
+        /// FlatBuffers schema file the tables were decoded with
         #[arg(short, long)]
         fbs: PathBuf,
 
-        /// Programming language to generate
+        /// Directory of decoded JSON tables, a file per table named after its row type
         #[arg(short, long)]
-        language: Language,
+        data: PathBuf,
 
-        /// Output path
+        /// SQLite database to create, or script to write with --script
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Write a SQL script for DIALECT creating the tables and inserting the rows instead
+        #[arg(long, value_name = "DIALECT")]
+        script: Option<Dialect>,
+    },
+    /// Serve decoded Excel tables over GraphQL and REST, read-only
+    Serve {
+        /// FlatBuffers schema file the tables were decoded with
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Directory of decoded JSON tables, a file per table named after its row type
+        #[arg(short, long)]
+        data: PathBuf,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: IpAddr,
+
+        /// Port to listen on, 0 picks a free one
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Write the Rust module root from postprocess.merge without generating, e.g. to combine the
+    /// roots of regions generated separately
+    Postprocess {
+        /// Directory the module root is written to
+        #[arg(short, long, default_value = ".")]
+        output: PathBuf,
+    },
+    /// Keep the schema of every game version and trace fields through them
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Inspect the extracted il2cpp files of a region
+    Inspect {
+        /// Server region
+        region: Region,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SchemaCommand {
+    /// Split a schema into a file per table and union, a shared file and an index including them
+    Split {
+        /// FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Output directory, the index is named after the schema
         #[arg(short, long)]
         output: PathBuf,
     },
+    /// Merge a schema and everything it includes into a single file
+    Merge {
+        /// Index or any other FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Rewrite schemas in a canonical layout and declaration order, includes are not followed
+    Fmt {
+        /// FlatBuffers schema files
+        #[arg(required = true)]
+        fbs: Vec<PathBuf>,
+
+        /// Declaration order, fields always keep theirs
+        #[arg(long, default_value_t)]
+        order: Order,
+
+        /// Only report the files that are not formatted
+        #[arg(long)]
+        check: bool,
+    },
+    /// Export a schema and everything it includes to another schema language
+    Export {
+        /// FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Format to export to
+        #[arg(long)]
+        format: ExportFormat,
+
+        /// Output file (defaults to the schema name with the extension of the format)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Render a schema as a static site with a page per declaration
+    Doc {
+        /// FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Output directory
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Page format
+        #[arg(long, default_value_t)]
+        format: DocFormat,
+
+        /// Schema of a region, pages list the regions that have each field (repeatable)
+        #[arg(long, value_name = "NAME=FBS")]
+        region: Vec<String>,
+
+        /// History store, or directory of schemas named after their game version, pages list
+        /// the version each field first appeared in
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Write a markdown changelog of the tables, fields and enum values added since a previous
+    /// schema
+    Changelog {
+        /// FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Previous schema file, output directory of an earlier dump or history store
+        #[arg(short, long)]
+        previous: PathBuf,
+
+        /// Output file (defaults to CHANGELOG.md next to the schema)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Game version the changelog is headed with
+        #[arg(long)]
+        game_version: String,
+    },
+    /// Check a schema and everything it includes for suspicious declarations
+    Lint {
+        /// FlatBuffers schema file
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Skip rules, repeatable or comma separated (added to lint.allow)
+        #[arg(long, value_delimiter = ',', value_name = "RULE")]
+        allow: Vec<Rule>,
+
+        /// Report rules as errors, repeatable or comma separated (added to lint.deny)
+        #[arg(long, value_delimiter = ',', value_name = "RULE")]
+        deny: Vec<Rule>,
+
+        /// Languages whose keywords are reserved (defaults to lint.languages, then all)
+        #[arg(short, long, value_delimiter = ',')]
+        language: Vec<Language>,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: PlanFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HistoryCommand {
+    /// Add the schema of a game version to the store
+    Add {
+        /// FlatBuffers schema file, merged with everything it includes
+        #[arg(short, long)]
+        fbs: PathBuf,
+
+        /// Game version the schema was dumped from, e.g. 1.45.256
+        #[arg(long)]
+        game_version: String,
+
+        /// Day the schema was dumped, YYYY-MM-DD (defaults to the date of the schema file)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Directory, or SQLite database ending in .db, .sqlite or .sqlite3
+        #[arg(short, long, default_value = "history")]
+        store: PathBuf,
+
+        /// Overwrite the schema of a game version already in the store
+        #[arg(long)]
+        replace: bool,
+    },
+    /// List the versions that added, changed or removed a declaration or field
+    Log {
+        /// Declaration, optionally namespace qualified, or Type.field
+        #[arg(value_name = "TYPE[.FIELD]")]
+        target: String,
+
+        /// Directory, or SQLite database ending in .db, .sqlite or .sqlite3
+        #[arg(short, long, default_value = "history")]
+        store: PathBuf,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: PlanFormat,
+    },
+    /// Show the version that last added or changed each field of a declaration, or each
+    /// declaration
+    Blame {
+        /// Declaration, optionally namespace qualified
+        #[arg(value_name = "TYPE")]
+        target: Option<String>,
+
+        /// Directory, or SQLite database ending in .db, .sqlite or .sqlite3
+        #[arg(short, long, default_value = "history")]
+        store: PathBuf,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: PlanFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print every effective configuration value
+    Show {
+        /// Annotate each value with the layer it came from
+        #[arg(long)]
+        origin: bool,
+    },
+    /// Check every configuration layer for unknown keys and invalid values
+    Validate,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Region {
+    Japan,
+    Global,
+}
+
+impl From<Region> for ServerRegion {
+    fn from(region: Region) -> Self {
+        match region {
+            Region::Japan => ServerRegion::Japan,
+            Region::Global => ServerRegion::Global,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -54,10 +358,274 @@ pub enum DumpTarget {
         /// Output path
         #[arg(short, long)]
         output: PathBuf,
+
+        #[command(flatten)]
+        stages: StageArgs,
+
+        #[command(flatten)]
+        schema: SchemaArgs,
     },
     Japan {
         /// Output path
         #[arg(short, long)]
         output: PathBuf,
+
+        #[command(flatten)]
+        stages: StageArgs,
+
+        #[command(flatten)]
+        schema: SchemaArgs,
     },
-}
\ No newline at end of file
+}
+
+impl DumpTarget {
+    /// The region of the target and its arguments
+    pub fn parts(&self) -> (ServerRegion, &PathBuf, &StageArgs, &SchemaArgs) {
+        match self {
+            DumpTarget::Japan {
+                output,
+                stages,
+                schema,
+            } => (ServerRegion::Japan, output, stages, schema),
+            DumpTarget::Global {
+                output,
+                stages,
+                schema,
+            } => (ServerRegion::Global, output, stages, schema),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Download the APK
+    Fetch,
+    /// Extract and check the il2cpp files, then fetch and extract the tool binaries
+    Extract,
+    /// Dump the dummy dlls with Il2CppInspector
+    Il2cpp,
+    /// Dump the flatbuffers schema with FbsDumper
+    Fbs,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Fetch => write!(f, "fetch"),
+            Stage::Extract => write!(f, "extract"),
+            Stage::Il2cpp => write!(f, "il2cpp"),
+            Stage::Fbs => write!(f, "fbs"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum Backend {
+    /// Built-in generator on top of the in-tree schema parser, Rust only
+    Native,
+    /// The flatc binary, downloaded on first use
+    Flatc,
+}
+
+impl Backend {
+    pub fn select(backend: Option<Backend>, language: Language) -> Result<Backend> {
+        match (backend, language) {
+            (Some(Backend::Native) | None, Language::Rust) => Ok(Backend::Native),
+            (Some(Backend::Native), language) => Err(eyre!(
+                "The native backend only generates rust, use --backend flatc for {}",
+                language
+            )),
+            (_, _) => Ok(Backend::Flatc),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Native => write!(f, "native"),
+            Backend::Flatc => write!(f, "flatc"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum Package {
+    /// Cargo crate with a manifest and lib.rs around the Rust code
+    Cargo,
+    /// Python project with a pyproject.toml around the Python code
+    Python,
+    /// npm package with a package.json around the TypeScript or JavaScript code
+    Npm,
+    /// NuGet package with a .csproj around the C# code
+    Nuget,
+}
+
+impl Package {
+    pub fn accepts(&self, language: Language) -> bool {
+        match self {
+            Package::Cargo => language == Language::Rust,
+            Package::Python => language == Language::Python,
+            Package::Npm => matches!(language, Language::TypeScript | Language::JavaScript),
+            Package::Nuget => language == Language::CSharp,
+        }
+    }
+}
+
+impl fmt::Display for Package {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Package::Cargo => write!(f, "cargo"),
+            Package::Python => write!(f, "python"),
+            Package::Npm => write!(f, "npm"),
+            Package::Nuget => write!(f, "nuget"),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct FilterArgs {
+    /// Only generate tables matching these globs and the types they use, repeatable or comma separated
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Skip tables matching these globs unless an included table uses them
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub exclude: Vec<String>,
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct CodegenArgs {
+    /// Code generator to use (defaults to native for Rust and flatc otherwise)
+    #[arg(long)]
+    pub backend: Option<Backend>,
+
+    /// Also emit helpers that decrypt and load the game's Excel tables (rust, csharp and python)
+    #[arg(long)]
+    pub decrypt_helpers: bool,
+}
+
+impl CodegenArgs {
+    /// Backend generating `language`, checking the decrypt helpers are available for it
+    pub fn select(&self, language: Language, object_api: bool) -> Result<Backend> {
+        let backend = Backend::select(self.backend, language)?;
+        if !self.decrypt_helpers {
+            return Ok(backend);
+        }
+
+        if !object_api {
+            return Err(eyre!(
+                "Decrypt helpers unpack tables and need gen_object_api for {}",
+                language
+            ));
+        }
+        match (language, backend) {
+            (Language::Rust, Backend::Native) | (Language::CSharp | Language::Python, _) => {
+                Ok(backend)
+            }
+            (Language::Rust, _) => Err(eyre!("Decrypt helpers for rust need the native backend")),
+            (language, _) => Err(eyre!("Decrypt helpers are not available for {}", language)),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct PackageArgs {
+    /// Package the generated code, repeatable or comma separated
+    #[arg(long, value_name = "KIND", value_delimiter = ',')]
+    pub package: Vec<Package>,
+
+    /// Package name (defaults to the output directory name)
+    #[arg(long, visible_alias = "crate-name", requires = "package")]
+    pub package_name: Option<String>,
+
+    /// Package version, usually the game version the schema was dumped from
+    #[arg(long, requires = "package")]
+    pub package_version: Option<String>,
+
+    /// Version the packages by the day the schema was dumped instead, YYYY-MM-DD
+    #[arg(long, requires = "package", conflicts_with = "package_version")]
+    pub dump_date: Option<String>,
+}
+
+impl PackageArgs {
+    /// `--package-version`, or `--dump-date` as `YEAR.MONTH.DAY`
+    pub fn version(&self) -> Result<String> {
+        match (&self.package_version, &self.dump_date) {
+            (Some(version), _) => Ok(version.clone()),
+            (None, Some(date)) => PackageMetadata::date_version(date),
+            (None, None) => Err(eyre!(
+                "Pass --package-version or --dump-date to version the packages"
+            )),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SchemaArgs {
+    /// Format the dumped schema, see `bafb schema fmt`
+    #[arg(
+        long,
+        value_name = "ORDER",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "alphabetical"
+    )]
+    pub format: Option<Order>,
+
+    /// Export the dumped schema next to it, see `bafb schema export`
+    #[arg(long, value_name = "FORMAT", value_delimiter = ',')]
+    pub export: Vec<ExportFormat>,
+
+    /// Write CHANGELOG.md comparing the dumped schema with a previous schema, output directory
+    /// or history store, see `bafb schema changelog`
+    #[arg(long, value_name = "PREVIOUS", requires = "game_version")]
+    pub changelog: Option<PathBuf>,
+
+    /// Game version the changelog is headed with
+    #[arg(long, value_name = "VERSION", requires = "changelog")]
+    pub game_version: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct StageArgs {
+    /// Pipeline stages to run (defaults to all)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["from", "to"])]
+    pub stages: Vec<Stage>,
+
+    /// First pipeline stage to run
+    #[arg(long)]
+    pub from: Option<Stage>,
+
+    /// Last pipeline stage to run
+    #[arg(long)]
+    pub to: Option<Stage>,
+}
+
+impl StageArgs {
+    pub fn resolve(&self) -> Result<Vec<Stage>> {
+        let mut stages = if self.stages.is_empty() {
+            let from = self.from.unwrap_or(Stage::Fetch);
+            let to = self.to.unwrap_or(Stage::Fbs);
+            if from > to {
+                return Err(eyre!(
+                    "--from {} comes after --to {}, no stage would run",
+                    from,
+                    to
+                ));
+            }
+
+            Stage::value_variants()
+                .iter()
+                .copied()
+                .filter(|stage| (from..=to).contains(stage))
+                .collect()
+        } else {
+            self.stages.clone()
+        };
+
+        stages.sort();
+        stages.dedup();
+        Ok(stages)
+    }
+}
@@ -1,212 +1,630 @@
-use crate::wrappers::flatc::FlatCOptions;
+use crate::cli::validate::ConfigValidator;
+use crate::helpers::config::*;
+use crate::schema::lint::{LintOptions, Rule};
+use crate::tools::postprocess::PostProcessOptions;
+use crate::wrappers::flatc::{CppStd, FlatCOptions, Language};
 use crate::wrappers::fbs_dumper::FbsDumperOptions;
 use crate::wrappers::il2cpp_dumper::Il2CppDumperOptions;
 
-use eyre::{eyre, Result};
+use eyre::{eyre, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use toml::{Table, Value};
+
+pub trait ConfigValue {
+    type Value: Clone;
+
+    fn set(&mut self, value: Self::Value);
+}
+
+impl<T: Clone> ConfigValue for Option<T> {
+    type Value = T;
+
+    fn set(&mut self, value: T) {
+        *self = Some(value);
+    }
+}
+
+impl<T: Clone> ConfigValue for Vec<T> {
+    type Value = Vec<T>;
+
+    fn set(&mut self, value: Vec<T>) {
+        *self = value;
+    }
+}
+
+impl ConfigValue for bool {
+    type Value = bool;
+
+    fn set(&mut self, value: bool) {
+        *self = value;
+    }
+}
+
+impl ConfigValue for PathBuf {
+    type Value = PathBuf;
+
+    fn set(&mut self, value: PathBuf) {
+        *self = value;
+    }
+}
+
+macro_rules! partial_options {
+    ($partial:ident => $options:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(Debug, Clone, Default, Deserialize, Serialize)]
+        #[serde(default, deny_unknown_fields)]
+        pub struct $partial {
+            $(
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub $field: Option<<$ty as ConfigValue>::Value>,
+            )*
+        }
+
+        impl $partial {
+            pub const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            pub fn apply(&self, options: &mut $options) -> Vec<&'static str> {
+                let mut merged = Vec::new();
+
+                $(
+                    if let Some(value) = &self.$field {
+                        options.$field.set(value.clone());
+                        merged.push(stringify!($field));
+                    }
+                )*
+
+                merged
+            }
+        }
+    };
+}
+
+partial_options! {
+    FbsDumperConfig => FbsDumperOptions {
+        url: Option<String>,
+        binary_name: Option<String>,
+        dummy_dll: PathBuf,
+        game_assembly: Option<PathBuf>,
+        output_file: Option<PathBuf>,
+        namespace: Option<String>,
+        force_snake_case: bool,
+        namespace_to_look_for: Option<String>,
+        force: bool,
+        verbose: bool,
+        suppress_warnings: bool,
+    }
+}
+
+partial_options! {
+    FlatCConfig => FlatCOptions {
+        url: Option<String>,
+        binary_name: Option<String>,
+        languages: Vec<Language>,
+        grpc: bool,
+        output_path: Option<PathBuf>,
+        include_paths: Vec<PathBuf>,
+        binary: bool,
+        json: bool,
+        jsonschema: bool,
+        strict_json: bool,
+        allow_non_utf8: bool,
+        natural_utf8: bool,
+        defaults_json: bool,
+        unknown_json: bool,
+        no_prefix: bool,
+        scoped_enums: bool,
+        no_emit_min_max_enum_values: bool,
+        swift_implementation_only: bool,
+        no_includes: bool,
+        gen_mutable: bool,
+        gen_onefile: bool,
+        gen_name_strings: bool,
+        gen_object_api: bool,
+        gen_compare: bool,
+        gen_nullable: bool,
+        java_package_prefix: Option<String>,
+        java_checkerframework: bool,
+        gen_generated: bool,
+        gen_jvmstatic: bool,
+        gen_all: bool,
+        gen_json_emit: bool,
+        cpp_include: Vec<String>,
+        cpp_ptr_type: Option<String>,
+        cpp_str_type: Option<String>,
+        cpp_str_flex_ctor: bool,
+        cpp_field_case_style: Option<String>,
+        no_cpp_direct_copy: bool,
+        cpp_std: Option<CppStd>,
+        cpp_static_reflection: bool,
+        object_prefix: Option<String>,
+        object_suffix: Option<String>,
+        go_namespace: Option<String>,
+        go_import: Option<String>,
+        go_module_name: Option<String>,
+        raw_binary: bool,
+        size_prefixed: bool,
+        proto: bool,
+        proto_namespace_suffix: Option<String>,
+        oneof_union: bool,
+        keep_proto_id: bool,
+        proto_id_gap: Option<String>,
+        schema: bool,
+        bfbs_filenames: Option<PathBuf>,
+        bfbs_absolute_paths: bool,
+        bfbs_comments: bool,
+        bfbs_builtins: bool,
+        bfbs_gen_embed: bool,
+        conform: Option<PathBuf>,
+        conform_includes: Vec<PathBuf>,
+        filename_suffix: Option<String>,
+        filename_ext: Option<String>,
+        include_prefix: Option<PathBuf>,
+        keep_prefix: bool,
+        reflect_types: bool,
+        reflect_names: bool,
+        rust_serialize: bool,
+        rust_module_root_file: bool,
+        root_type: Option<String>,
+        require_explicit_ids: bool,
+        force_defaults: bool,
+        force_empty: bool,
+        force_empty_vectors: bool,
+        flexbuffers: bool,
+        no_warnings: bool,
+        warnings_as_errors: bool,
+        cs_global_alias: bool,
+        cs_gen_json_serializer: bool,
+        json_nested_bytes: bool,
+        ts_flat_files: bool,
+        ts_entry_points: bool,
+        annotate_sparse_vectors: bool,
+        annotate: Option<PathBuf>,
+        no_leak_private_annotation: bool,
+        python_no_type_prefix_suffix: bool,
+        python_typing: bool,
+        python_version: Option<String>,
+        python_gen_numpy: bool,
+        ts_omit_entrypoint: bool,
+        file_names_only: bool,
+        grpc_filename_suffix: Option<String>,
+        grpc_additional_header: Vec<String>,
+        grpc_search_path: Option<String>,
+        grpc_use_system_headers: bool,
+        grpc_python_typed_handlers: bool,
+    }
+}
+
+partial_options! {
+    Il2CppDumperConfig => Il2CppDumperOptions {
+        url: Option<String>,
+        binary_name: Option<String>,
+        il2cpp: PathBuf,
+        metadata: Option<PathBuf>,
+        output: Option<PathBuf>,
+        unity_version: Option<String>,
+        compiler_type: Option<String>,
+        output_csharp_stub: bool,
+        layout: Option<String>,
+        flatten_hierarchy: bool,
+        output_disassembler_metadata: bool,
+        disassembler: Option<String>,
+        output_cpp_scaffolding: bool,
+        sorting_mode: Option<String>,
+        suppress_metadata: bool,
+        compilable: bool,
+        separate_assembly_attributes: bool,
+        output_dummy_dlls: bool,
+        output_vs_solution: bool,
+        unity_path: Option<PathBuf>,
+        unity_assemblies_path: Option<PathBuf>,
+        extract_il2cpp_files: bool,
+    }
+}
+
+partial_options! {
+    LintConfig => LintOptions {
+        allow: Vec<Rule>,
+        deny: Vec<Rule>,
+        languages: Vec<Language>,
+    }
+}
+
+partial_options! {
+    PostProcessConfig => PostProcessOptions {
+        header: Option<String>,
+        prelude: Vec<String>,
+        strip_banners: bool,
+        flatten: bool,
+        module_root: Option<String>,
+        merge: Vec<PathBuf>,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Default,
+    User(PathBuf),
+    Project(PathBuf),
+    Env(String),
+    Cli,
+    Profile(String, Box<Origin>),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Default => write!(f, "default"),
+            Origin::User(path) => write!(f, "user config {}", path.display()),
+            Origin::Project(path) => write!(f, "project config {}", path.display()),
+            Origin::Env(name) => write!(f, "environment variable {}", name),
+            Origin::Cli => write!(f, "--set"),
+            Origin::Profile(name, origin) => write!(f, "profile {} in {}", name, origin),
+        }
+    }
+}
+
+struct Layer {
+    origin: Origin,
+    table: Table,
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default)]
+    pub fbs_dumper: FbsDumperConfig,
+    #[serde(default)]
+    pub flatc: FlatCConfig,
+    #[serde(default)]
+    pub il2cpp_dumper: Il2CppDumperConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub postprocess: PostProcessConfig,
+}
 
 #[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     #[serde(default)]
-    pub fbs_dumper: FbsDumperOptions,
+    pub fbs_dumper: FbsDumperConfig,
     #[serde(default)]
-    pub flatc: FlatCOptions,
+    pub flatc: FlatCConfig,
     #[serde(default)]
-    pub il2cpp_dumper: Il2CppDumperOptions,
+    pub il2cpp_dumper: Il2CppDumperConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub postprocess: PostProcessConfig,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(skip)]
+    pub origins: BTreeMap<String, Origin>,
+    #[serde(skip)]
+    pub layers: Vec<Origin>,
 }
 
 impl Config {
-    pub fn load(custom_path: Option<&Path>) -> Result<Self> {
-        let config_path = match custom_path {
+    pub fn load(
+        custom_path: Option<&Path>,
+        overrides: &[String],
+        profile: Option<&str>,
+    ) -> Result<Self> {
+        let mut layers = Vec::new();
+
+        if let Some(path) = Self::user_config_path()
+            && path.exists()
+        {
+            layers.push(Self::file_layer(&path, Origin::User(path.clone()))?);
+        }
+
+        match custom_path {
             Some(path) => {
                 if !path.exists() {
                     return Err(eyre!("Config file not found: {}", path.display()));
                 }
-                Some(path)
+                layers.push(Self::file_layer(path, Origin::Project(path.to_path_buf()))?);
             }
             None => {
-                let default_path = Path::new("config.toml");
-                if default_path.exists() {
-                    Some(default_path)
-                } else {
-                    None
+                for name in [CONFIG_FILE, PROJECT_CONFIG_FILE] {
+                    let path = Path::new(name);
+                    if path.exists() {
+                        layers.push(Self::file_layer(path, Origin::Project(path.to_path_buf()))?);
+                    }
                 }
             }
+        }
+
+        layers.extend(Self::env_layers());
+
+        let mut cli = Table::new();
+        for entry in overrides {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| eyre!("Invalid override '{}', expected KEY=VALUE", entry))?;
+            let path: Vec<&str> = key.trim().split('.').collect();
+            Self::insert_path(&mut cli, &path, Self::parse_value(value.trim()));
+        }
+        layers.push(Layer {
+            origin: Origin::Cli,
+            table: cli,
+            content: None,
+        });
+
+        Self::from_layers(layers, profile)
+    }
+
+    fn from_layers(layers: Vec<Layer>, profile: Option<&str>) -> Result<Self> {
+        let mut validator = ConfigValidator::new();
+        for layer in &layers {
+            validator.validate(&layer.origin, &layer.table, layer.content.as_deref());
+        }
+
+        let issues = validator.into_issues();
+        if !issues.is_empty() {
+            let lines: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            return Err(eyre!("Invalid configuration:\n  {}", lines.join("\n  ")));
+        }
+
+        let mut merged = Table::new();
+        let mut origins = BTreeMap::new();
+        let mut sources = Vec::new();
+
+        for layer in layers {
+            if layer.table.is_empty() {
+                continue;
+            }
+
+            Self::record_origins(&layer.table, "", &layer.origin, &mut origins);
+            Self::merge_tables(&mut merged, layer.table);
+            sources.push(layer.origin);
+        }
+
+        if let Some(name) = profile {
+            Self::apply_profile(&mut merged, &mut origins, name)?;
+        }
+
+        let mut config: Config = Value::Table(merged).try_into()?;
+        config.origins = origins;
+        config.layers = sources;
+
+        Ok(config)
+    }
+
+    fn apply_profile(
+        merged: &mut Table,
+        origins: &mut BTreeMap<String, Origin>,
+        name: &str,
+    ) -> Result<()> {
+        let profiles = merged
+            .get("profiles")
+            .and_then(Value::as_table)
+            .cloned()
+            .unwrap_or_default();
+
+        let Some(Value::Table(profile)) = profiles.get(name) else {
+            let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            let mut message = format!("Unknown profile `{}`", name);
+            match ConfigValidator::suggest(name, &names) {
+                Some(suggestion) => message.push_str(&format!(", did you mean `{}`?", suggestion)),
+                None if names.is_empty() => message.push_str(", no profiles are defined"),
+                None => message.push_str(&format!(", available profiles: {}", names.join(", "))),
+            }
+            return Err(eyre!(message));
         };
 
-        match config_path {
-            Some(path) => {
-                let content = std::fs::read_to_string(path)?;
-                Ok(toml::from_str(&content)?)
+        for (section, entries) in profile {
+            let Value::Table(entries) = entries else {
+                continue;
+            };
+
+            for (key, value) in entries {
+                let path = format!("{}.{}", section, key);
+                if matches!(origins.get(&path), Some(Origin::Env(_) | Origin::Cli)) {
+                    continue;
+                }
+
+                let origin = origins
+                    .get(&format!("profiles.{}.{}", name, path))
+                    .cloned()
+                    .unwrap_or(Origin::Default);
+                origins.insert(path, Origin::Profile(name.to_string(), Box::new(origin)));
+
+                Self::insert_path(merged, &[section, key], value.clone());
             }
-            None => Ok(Config::default()),
         }
+
+        Ok(())
+    }
+
+    fn user_config_path() -> Option<PathBuf> {
+        let base = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+        Some(base.join(CONFIG_DIR).join(CONFIG_FILE))
     }
 
-    pub fn merge_fbs_dumper_config(&self, _options: &mut FbsDumperOptions) {
-        let _default_options = FbsDumperOptions::default();
-        
-        macro_rules! merge_field {
-            ($field:ident) => {
-                if self.fbs_dumper.$field != _default_options.$field {
-                    _options.$field = self.fbs_dumper.$field.clone();
+    fn file_layer(path: &Path, origin: Origin) -> Result<Layer> {
+        let content = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read config {}", path.display()))?;
+        let table: Table = toml::from_str(&content)
+            .wrap_err_with(|| format!("Failed to parse config {}", path.display()))?;
+
+        Ok(Layer {
+            origin,
+            table,
+            content: Some(content),
+        })
+    }
+
+    fn env_layers() -> Vec<Layer> {
+        let mut vars: Vec<(String, String)> = env::vars()
+            .filter(|(name, _)| {
+                name.starts_with(CONFIG_ENV_PREFIX) && name.contains(CONFIG_ENV_SEPARATOR)
+            })
+            .collect();
+        vars.sort();
+
+        vars.into_iter()
+            .map(|(name, value)| {
+                let key = name[CONFIG_ENV_PREFIX.len()..].to_lowercase();
+                let path: Vec<&str> = key.split(CONFIG_ENV_SEPARATOR).collect();
+
+                let mut table = Table::new();
+                Self::insert_path(&mut table, &path, Self::parse_value(&value));
+
+                Layer {
+                    origin: Origin::Env(name),
+                    table,
+                    content: None,
                 }
-            };
+            })
+            .collect()
+    }
+
+    fn parse_value(raw: &str) -> Value {
+        toml::from_str::<Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| Value::String(raw.to_string()))
+    }
+
+    fn insert_path(table: &mut Table, path: &[&str], value: Value) {
+        match path {
+            [] => {}
+            [key] => {
+                table.insert(key.to_string(), value);
+            }
+            [key, rest @ ..] => {
+                let entry = table
+                    .entry(key.to_string())
+                    .or_insert_with(|| Value::Table(Table::new()));
+                if !entry.is_table() {
+                    *entry = Value::Table(Table::new());
+                }
+                if let Value::Table(child) = entry {
+                    Self::insert_path(child, rest, value);
+                }
+            }
         }
+    }
 
-        merge_field!(dummy_dll);
-        merge_field!(game_assembly);
-        merge_field!(output_file);
-        merge_field!(namespace);
-        merge_field!(force_snake_case);
-        merge_field!(namespace_to_look_for);
-        merge_field!(force);
-        merge_field!(verbose);
-        merge_field!(suppress_warnings);
-        merge_field!(url);
-        merge_field!(binary_name);
-    }
-
-    pub fn merge_flatc_config(&self, _options: &mut FlatCOptions) {
-        let _default_options = FlatCOptions::default();
-        
-        macro_rules! merge_field {
-            ($field:ident) => {
-                if self.flatc.$field != _default_options.$field {
-                    _options.$field = self.flatc.$field.clone();
+    fn merge_tables(base: &mut Table, layer: Table) {
+        for (key, value) in layer {
+            match (base.get_mut(&key), value) {
+                (Some(Value::Table(existing)), Value::Table(child)) => {
+                    Self::merge_tables(existing, child)
                 }
+                (_, value) => {
+                    base.insert(key, value);
+                }
+            }
+        }
+    }
+
+    fn record_origins(
+        table: &Table,
+        prefix: &str,
+        origin: &Origin,
+        origins: &mut BTreeMap<String, Origin>,
+    ) {
+        for (key, value) in table {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
             };
+
+            match value {
+                Value::Table(child) => Self::record_origins(child, &path, origin, origins),
+                _ => {
+                    origins.insert(path, origin.clone());
+                }
+            }
         }
+    }
+
+    pub fn origin(&self, key: &str) -> &Origin {
+        self.origins.get(key).unwrap_or(&Origin::Default)
+    }
 
-        merge_field!(languages);
-        merge_field!(grpc);
-        merge_field!(output_path);
-        merge_field!(include_paths);
-        merge_field!(binary);
-        merge_field!(json);
-        merge_field!(jsonschema);
-        merge_field!(strict_json);
-        merge_field!(allow_non_utf8);
-        merge_field!(natural_utf8);
-        merge_field!(defaults_json);
-        merge_field!(unknown_json);
-        merge_field!(no_prefix);
-        merge_field!(scoped_enums);
-        merge_field!(no_emit_min_max_enum_values);
-        merge_field!(swift_implementation_only);
-        merge_field!(no_includes);
-        merge_field!(gen_mutable);
-        merge_field!(gen_onefile);
-        merge_field!(gen_name_strings);
-        merge_field!(gen_object_api);
-        merge_field!(gen_compare);
-        merge_field!(gen_nullable);
-        merge_field!(java_package_prefix);
-        merge_field!(java_checkerframework);
-        merge_field!(gen_generated);
-        merge_field!(gen_jvmstatic);
-        merge_field!(gen_all);
-        merge_field!(gen_json_emit);
-        merge_field!(cpp_include);
-        merge_field!(cpp_ptr_type);
-        merge_field!(cpp_str_type);
-        merge_field!(cpp_str_flex_ctor);
-        merge_field!(cpp_field_case_style);
-        merge_field!(no_cpp_direct_copy);
-        merge_field!(cpp_std);
-        merge_field!(cpp_static_reflection);
-        merge_field!(object_prefix);
-        merge_field!(object_suffix);
-        merge_field!(go_namespace);
-        merge_field!(go_import);
-        merge_field!(go_module_name);
-        merge_field!(raw_binary);
-        merge_field!(size_prefixed);
-        merge_field!(proto);
-        merge_field!(proto_namespace_suffix);
-        merge_field!(oneof_union);
-        merge_field!(keep_proto_id);
-        merge_field!(proto_id_gap);
-        merge_field!(schema);
-        merge_field!(bfbs_filenames);
-        merge_field!(bfbs_absolute_paths);
-        merge_field!(bfbs_comments);
-        merge_field!(bfbs_builtins);
-        merge_field!(bfbs_gen_embed);
-        merge_field!(conform);
-        merge_field!(conform_includes);
-        merge_field!(filename_suffix);
-        merge_field!(filename_ext);
-        merge_field!(include_prefix);
-        merge_field!(keep_prefix);
-        merge_field!(reflect_types);
-        merge_field!(reflect_names);
-        merge_field!(rust_serialize);
-        merge_field!(rust_module_root_file);
-        merge_field!(root_type);
-        merge_field!(require_explicit_ids);
-        merge_field!(force_defaults);
-        merge_field!(force_empty);
-        merge_field!(force_empty_vectors);
-        merge_field!(flexbuffers);
-        merge_field!(no_warnings);
-        merge_field!(warnings_as_errors);
-        merge_field!(cs_global_alias);
-        merge_field!(cs_gen_json_serializer);
-        merge_field!(json_nested_bytes);
-        merge_field!(ts_flat_files);
-        merge_field!(ts_entry_points);
-        merge_field!(annotate_sparse_vectors);
-        merge_field!(annotate);
-        merge_field!(no_leak_private_annotation);
-        merge_field!(python_no_type_prefix_suffix);
-        merge_field!(python_typing);
-        merge_field!(python_version);
-        merge_field!(python_gen_numpy);
-        merge_field!(ts_omit_entrypoint);
-        merge_field!(file_names_only);
-        merge_field!(grpc_filename_suffix);
-        merge_field!(grpc_additional_header);
-        merge_field!(grpc_search_path);
-        merge_field!(grpc_use_system_headers);
-        merge_field!(grpc_python_typed_handlers);
-        merge_field!(url);
-        merge_field!(binary_name);
-    }
-
-    pub fn merge_il2cpp_dumper_config(&self, _options: &mut Il2CppDumperOptions) {
-        let _default_options = Il2CppDumperOptions::default();
-        
-        macro_rules! merge_field {
-            ($field:ident) => {
-                if self.il2cpp_dumper.$field != _default_options.$field {
-                    _options.$field = self.il2cpp_dumper.$field.clone();
+    pub fn show(&self, with_origin: bool) -> Result<String> {
+        let sections = [
+            (
+                "fbs_dumper",
+                Table::try_from(FbsDumperOptions::default())?,
+                Table::try_from(&self.fbs_dumper)?,
+            ),
+            (
+                "flatc",
+                Table::try_from(FlatCOptions::default())?,
+                Table::try_from(&self.flatc)?,
+            ),
+            (
+                "il2cpp_dumper",
+                Table::try_from(Il2CppDumperOptions::default())?,
+                Table::try_from(&self.il2cpp_dumper)?,
+            ),
+            (
+                "lint",
+                Table::try_from(LintOptions::default())?,
+                Table::try_from(&self.lint)?,
+            ),
+            (
+                "postprocess",
+                Table::try_from(PostProcessOptions::default())?,
+                Table::try_from(&self.postprocess)?,
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, mut effective, set) in sections {
+            effective.extend(set);
+
+            if !output.is_empty() {
+                writeln!(output)?;
+            }
+            writeln!(output, "[{}]", name)?;
+
+            for (key, value) in &effective {
+                if with_origin {
+                    let origin = self.origin(&format!("{}.{}", name, key));
+                    writeln!(output, "{} = {}  # {}", key, value, origin)?;
+                } else {
+                    writeln!(output, "{} = {}", key, value)?;
                 }
-            };
+            }
         }
 
-        merge_field!(il2cpp);
-        merge_field!(metadata);
-        merge_field!(output);
-        merge_field!(unity_version);
-        merge_field!(compiler_type);
-        merge_field!(output_csharp_stub);
-        merge_field!(layout);
-        merge_field!(flatten_hierarchy);
-        merge_field!(output_disassembler_metadata);
-        merge_field!(disassembler);
-        merge_field!(output_cpp_scaffolding);
-        merge_field!(sorting_mode);
-        merge_field!(suppress_metadata);
-        merge_field!(compilable);
-        merge_field!(separate_assembly_attributes);
-        merge_field!(output_dummy_dlls);
-        merge_field!(output_vs_solution);
-        merge_field!(unity_path);
-        merge_field!(unity_assemblies_path);
-        merge_field!(extract_il2cpp_files);
-        merge_field!(url);
-        merge_field!(binary_name);
+        Ok(output)
+    }
+
+    pub fn merge_fbs_dumper_config(&self, options: &mut FbsDumperOptions) -> Vec<&'static str> {
+        self.fbs_dumper.apply(options)
+    }
+
+    pub fn merge_flatc_config(&self, options: &mut FlatCOptions) -> Vec<&'static str> {
+        self.flatc.apply(options)
+    }
+
+    pub fn merge_il2cpp_dumper_config(
+        &self,
+        options: &mut Il2CppDumperOptions,
+    ) -> Vec<&'static str> {
+        self.il2cpp_dumper.apply(options)
+    }
+
+    pub fn merge_lint_config(&self, options: &mut LintOptions) -> Vec<&'static str> {
+        self.lint.apply(options)
+    }
+
+    pub fn merge_postprocess_config(&self, options: &mut PostProcessOptions) -> Vec<&'static str> {
+        self.postprocess.apply(options)
     }
 }
\ No newline at end of file
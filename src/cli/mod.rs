@@ -1,3 +1,5 @@
 pub mod args;
 pub mod config;
-pub mod parse;
\ No newline at end of file
+pub mod parse;
+pub mod plan;
+pub mod validate;
\ No newline at end of file
@@ -1,33 +1,82 @@
-use crate::cli::args::{Args, Commands, DumpTarget};
+use crate::cli::args::{
+    Args, Backend, CodegenArgs, Commands, ConfigCommand, DumpTarget, FilterArgs, HistoryCommand, Package,
+    PackageArgs, Region, SchemaCommand, Stage,
+};
 use crate::cli::config::Config;
+use crate::cli::plan::{PlanFormat, PlanOption};
+use crate::codegen::decrypt;
+use crate::codegen::rust::{RustGenerator, RustOptions};
 use crate::helpers::config::*;
+use crate::schema::Schema;
+use crate::schema::changelog::{Changelog, Previous};
+use crate::schema::doc::{DocFormat, Site, Sources};
+use crate::schema::export::sql::Dialect;
+use crate::schema::export::{self, ExportFormat};
+use crate::schema::filter::SchemaFilter;
+use crate::schema::format::{self, Order};
+use crate::schema::history::{History, Store, Version};
+use crate::schema::lint::{LintOptions, Linter, Rule, Severity};
+use crate::schema::split;
+use crate::tools::cache::DumpCache;
+use crate::tools::decoded::DecodedData;
 use crate::tools::extract::ToolsExtractor;
+use crate::tools::package::cargo::CargoPackage;
+use crate::tools::package::npm::NpmPackage;
+use crate::tools::package::nuget::NugetPackage;
+use crate::tools::package::python::PythonPackage;
+use crate::tools::package::{GeneratedPackage, PackageMetadata};
+use crate::tools::postprocess::{PostProcessOptions, PostProcessor};
 use crate::tools::fetch::ToolsFetcher;
+use crate::tools::import::Importer;
+use crate::tools::serve::Server;
+use crate::tools::preflight::{Preflight, PreflightReport};
 use crate::wrappers::fbs_dumper::{FbsDumper, FbsDumperOptions};
 use crate::wrappers::flatc::{FlatC, FlatCOptions, Language};
 use crate::wrappers::il2cpp_dumper::{Il2CppDumper, Il2CppDumperOptions};
 
 use baad::apk::{ApkExtractor, ApkFetcher};
 use baad::helpers::{ServerConfig, ServerRegion};
-use baad::info;
+use baad::{error, info};
 use baad::utils::file;
-use clap::CommandFactory;
-use eyre::Result;
+use clap::{CommandFactory, ValueEnum};
+use eyre::{eyre, Context, Result};
+use std::fs;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+pub struct GenerateJob {
+    pub language: Language,
+    pub backend: Backend,
+    pub decrypt: bool,
+    pub output: PathBuf,
+    pub source: PathBuf,
+    pub options: FlatCOptions,
+    pub sources: Vec<PlanOption>,
+    pub processor: Option<PostProcessor>,
+    pub package: Option<Box<dyn GeneratedPackage>>,
+}
+
 pub struct CommandHandler {
-    args: Args,
-    config: Config,
+    pub args: Args,
+    pub config: Config,
 }
 
 impl CommandHandler {
     pub fn new(args: Args) -> Result<Self> {
-        let config = Config::load(args.config.as_deref())?;
+        let config = Config::load(
+            args.config.as_deref(),
+            &args.overrides,
+            args.profile.as_deref(),
+        )?;
         Ok(Self { args, config })
     }
 
     pub async fn handle(&self) -> Result<()> {
+        if let Some(format) = self.args.dry_run {
+            return self.handle_plan()?.print(format);
+        }
+
         if self.args.clean {
             info!("Cleaning data...");
 
@@ -41,9 +90,39 @@ impl CommandHandler {
             Some(Commands::Dump { target }) => self.handle_dump(target).await,
             Some(Commands::Generate {
                 fbs,
+                region,
                 language,
                 output,
-            }) => self.execute_generate(fbs, language, output).await,
+                filter,
+                codegen,
+                package,
+            }) => {
+                let fbs = match (fbs, region) {
+                    (Some(fbs), _) => fbs.clone(),
+                    (None, Some(region)) => self.resolve_schema((*region).into()).await?,
+                    (None, None) => return Err(eyre!("Pass --fbs or --region")),
+                };
+                let fbs = self.filter_schema(&fbs, filter)?;
+                self.execute_generate(&fbs, language, output.as_deref(), codegen, package)
+                    .await
+            }
+            Some(Commands::Inspect { region }) => self.execute_inspect(*region).await,
+            Some(Commands::Schema { command }) => self.handle_schema(command),
+            Some(Commands::Import {
+                fbs,
+                data,
+                output,
+                script,
+            }) => self.execute_import(fbs, data, output, *script),
+            Some(Commands::Serve {
+                fbs,
+                data,
+                host,
+                port,
+            }) => self.execute_serve(fbs, data, SocketAddr::new(*host, *port)).await,
+            Some(Commands::Postprocess { output }) => self.execute_postprocess(output),
+            Some(Commands::History { command }) => self.handle_history(command),
+            Some(Commands::Config { command }) => self.handle_config(command),
             None => {
                 if self.args.update {
                     self.handle_update().await?;
@@ -54,10 +133,584 @@ impl CommandHandler {
     }
 
     async fn handle_dump(&self, target: &DumpTarget) -> Result<()> {
-        match target {
-            DumpTarget::Japan { output } => self.execute_dump(ServerRegion::Japan, output).await,
-            DumpTarget::Global { output } => self.execute_dump(ServerRegion::Global, output).await,
+        let (region, output, stages, schema) = target.parts();
+        let stages = stages.resolve()?;
+
+        // Read before the dump, the previous schema may be the one it replaces
+        let previous = match (&schema.changelog, &schema.game_version) {
+            (Some(path), Some(version)) if stages.contains(&Stage::Fbs) => {
+                Some((Previous::load(path)?, version))
+            }
+            _ => None,
+        };
+
+        self.execute_dump(region, output, &stages).await?;
+        if stages.contains(&Stage::Fbs) {
+            let fbs = output.join(SCHEMA_FILE);
+            if let Some(order) = schema.format {
+                self.execute_schema_fmt(std::slice::from_ref(&fbs), order, false)?;
+            }
+            for format in &schema.export {
+                self.execute_schema_export(&fbs, *format, None)?;
+            }
+            if let Some((previous, version)) = &previous {
+                self.execute_schema_changelog(&fbs, previous, None, version)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_schema(&self, command: &SchemaCommand) -> Result<()> {
+        match command {
+            SchemaCommand::Split { fbs, output } => self.execute_schema_split(fbs, output),
+            SchemaCommand::Merge { fbs, output } => self.execute_schema_merge(fbs, output),
+            SchemaCommand::Fmt { fbs, order, check } => {
+                self.execute_schema_fmt(fbs, *order, *check)
+            }
+            SchemaCommand::Export {
+                fbs,
+                format,
+                output,
+            } => self.execute_schema_export(fbs, *format, output.as_deref()),
+            SchemaCommand::Lint {
+                fbs,
+                allow,
+                deny,
+                language,
+                format,
+            } => self.execute_schema_lint(fbs, allow, deny, language, *format),
+            SchemaCommand::Doc {
+                fbs,
+                output,
+                format,
+                region,
+                history,
+            } => self.execute_schema_doc(fbs, output, *format, region, history.as_deref()),
+            SchemaCommand::Changelog {
+                fbs,
+                previous,
+                output,
+                game_version,
+            } => self.execute_schema_changelog(
+                fbs,
+                &Previous::load(previous)?,
+                output.as_deref(),
+                game_version,
+            ),
+        }
+    }
+
+    fn execute_schema_split(&self, fbs: &Path, output: &Path) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let index = fbs
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| eyre!("{} is not a file", fbs.display()))?;
+        let files = split::split(&schema, &index)?;
+        for file in &files {
+            let path = output.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, file.schema.to_string())
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        let index = output.join(&index);
+        let reloaded = Schema::load(&index)?;
+        reloaded
+            .check()
+            .and_then(|_| split::verify(&schema, &reloaded))
+            .wrap_err_with(|| format!("{} does not match {}", index.display(), fbs.display()))?;
+
+        info!(
+            success = true,
+            "Split {} declarations into {} files, include {}",
+            schema.declarations.len(),
+            files.len(),
+            index.display()
+        );
+
+        Ok(())
+    }
+
+    fn execute_schema_merge(&self, fbs: &Path, output: &Path) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let merged = schema.to_string();
+        let reparsed = Schema::parse(&merged)?;
+        split::verify(&schema, &reparsed)
+            .wrap_err_with(|| format!("Merging {} lost declarations", fbs.display()))?;
+
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(output, merged)
+            .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+
+        info!(
+            success = true,
+            "Merged {} declarations into {}",
+            schema.declarations.len(),
+            output.display()
+        );
+
+        Ok(())
+    }
+
+    fn execute_schema_fmt(&self, files: &[PathBuf], order: Order, check: bool) -> Result<()> {
+        let mut unformatted = Vec::new();
+        for fbs in files {
+            let source = fs::read_to_string(fbs)
+                .wrap_err_with(|| format!("Failed to read {}", fbs.display()))?;
+            let schema = Schema::parse(&source)
+                .wrap_err_with(|| format!("Failed to parse {}", fbs.display()))?;
+            let formatted = format::format(&schema, order);
+            if formatted == source {
+                continue;
+            }
+
+            let reparsed = Schema::parse(&formatted)?;
+            split::verify(&schema, &reparsed).wrap_err_with(|| {
+                format!("Formatting {} changed its declarations", fbs.display())
+            })?;
+
+            if !check {
+                fs::write(fbs, formatted)
+                    .wrap_err_with(|| format!("Failed to write {}", fbs.display()))?;
+            }
+            unformatted.push(fbs.display().to_string());
+        }
+
+        match (check, unformatted.len()) {
+            (true, 0) => info!(success = true, "{} schemas are formatted", files.len()),
+            (true, _) => {
+                return Err(eyre!("Not formatted: {}", unformatted.join(", ")));
+            }
+            (false, count) => info!(
+                success = true,
+                "Formatted {} of {} schemas in {} order",
+                count,
+                files.len(),
+                order
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn execute_schema_export(
+        &self,
+        fbs: &Path,
+        format: ExportFormat,
+        output: Option<&Path>,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let output = output.map_or_else(|| export::output_path(fbs, format), Path::to_path_buf);
+        let exported = export::export(&schema, format)
+            .wrap_err_with(|| format!("Failed to export {} as {}", fbs.display(), format))?;
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&output, exported)
+            .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+
+        info!(
+            success = true,
+            "Exported {} declarations as {} to {}",
+            schema.declarations.len(),
+            format,
+            output.display()
+        );
+
+        Ok(())
+    }
+
+    fn execute_schema_doc(
+        &self,
+        fbs: &Path,
+        output: &Path,
+        format: DocFormat,
+        regions: &[String],
+        history: Option<&Path>,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let mut sources = Sources::default();
+        for region in regions {
+            let (name, path) = region
+                .split_once('=')
+                .ok_or_else(|| eyre!("Expected NAME=FBS, got {}", region))?;
+            sources
+                .regions
+                .push((name.to_string(), Schema::load(Path::new(path))?));
+        }
+        if let Some(history) = history {
+            sources.history = Some(History::load(history)?);
+        }
+
+        let files = Site::new(&schema, &sources)?.render(format);
+        fs::create_dir_all(output)
+            .wrap_err_with(|| format!("Failed to create {}", output.display()))?;
+        for file in &files {
+            let path = output.join(&file.path);
+            fs::write(&path, &file.content)
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        info!(
+            success = true,
+            "Documented {} declarations as {} in {}",
+            schema.declarations.len(),
+            format,
+            output.display()
+        );
+
+        Ok(())
+    }
+
+    fn execute_schema_changelog(
+        &self,
+        fbs: &Path,
+        previous: &Previous,
+        output: Option<&Path>,
+        game_version: &str,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let output = output.map_or_else(|| fbs.with_file_name(CHANGELOG_FILE), Path::to_path_buf);
+        let changelog = Changelog::new(&previous.schema, &schema);
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&output, changelog.markdown(game_version, &previous.name))
+            .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+
+        info!(
+            success = true,
+            "Wrote the changes since {} to {}: {}",
+            previous.name,
+            output.display(),
+            changelog.summary()
+        );
+
+        Ok(())
+    }
+
+    fn handle_history(&self, command: &HistoryCommand) -> Result<()> {
+        match command {
+            HistoryCommand::Add {
+                fbs,
+                game_version,
+                date,
+                store,
+                replace,
+            } => self.execute_history_add(fbs, game_version, date.as_deref(), store, *replace),
+            HistoryCommand::Log {
+                target,
+                store,
+                format,
+            } => self.execute_history_log(target, store, *format),
+            HistoryCommand::Blame {
+                target,
+                store,
+                format,
+            } => self.execute_history_blame(target.as_deref(), store, *format),
+        }
+    }
+
+    fn execute_history_add(
+        &self,
+        fbs: &Path,
+        game_version: &str,
+        date: Option<&str>,
+        store: &Path,
+        replace: bool,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let date = match date {
+            Some(date) => {
+                let parts: Vec<&str> = date.split('-').collect();
+                let valid = parts.len() == 3
+                    && parts.iter().zip([4, 2, 2]).all(|(part, length)| {
+                        part.len() == length && part.chars().all(|c| c.is_ascii_digit())
+                    });
+                if !valid {
+                    return Err(eyre!("Expected a YYYY-MM-DD date, got {}", date));
+                }
+                date.to_string()
+            }
+            None => {
+                let modified = fs::metadata(fbs)
+                    .and_then(|metadata| metadata.modified())
+                    .wrap_err_with(|| format!("Failed to read the date of {}", fbs.display()))?;
+                let (year, month, day) = PackageMetadata::civil_date(modified);
+                format!("{:04}-{:02}-{:02}", year, month, day)
+            }
+        };
+
+        let declarations = schema.declarations.len();
+        let version = Version {
+            version: game_version.to_string(),
+            date: Some(date),
+            schema,
+        };
+        Store::open(store).add(&version, replace)?;
+
+        info!(
+            success = true,
+            "Added {} declarations of {} to {}",
+            declarations,
+            game_version,
+            store.display()
+        );
+
+        Ok(())
+    }
+
+    fn execute_history_log(&self, target: &str, store: &Path, format: PlanFormat) -> Result<()> {
+        let history = History::load(store)?;
+        let (declaration, member) = history.resolve(target)?;
+        let entries = history.log(&declaration, member.as_deref());
+
+        match format {
+            PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+            PlanFormat::Text => {
+                for entry in &entries {
+                    println!(
+                        "{} ({}) {}",
+                        entry.version,
+                        entry.date.unwrap_or("undated"),
+                        entry.change
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute_history_blame(
+        &self,
+        target: Option<&str>,
+        store: &Path,
+        format: PlanFormat,
+    ) -> Result<()> {
+        let history = History::load(store)?;
+        let declaration = target
+            .map(|target| match history.resolve(target)? {
+                (declaration, None) => Ok(declaration),
+                (_, Some(_)) => Err(eyre!("Blame a declaration rather than {}", target)),
+            })
+            .transpose()?;
+        let blames = history.blame(declaration.as_deref());
+        if let Some(declaration) = &declaration
+            && blames.is_empty()
+        {
+            return Err(eyre!("The newest version does not declare {}", declaration));
+        }
+
+        match format {
+            PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&blames)?),
+            PlanFormat::Text => {
+                let width = blames.iter().map(|blame| blame.name.len()).max().unwrap_or(0);
+                let versions = blames
+                    .iter()
+                    .map(|blame| blame.version.len())
+                    .max()
+                    .unwrap_or(0);
+                for blame in &blames {
+                    println!(
+                        "{:versions$} {:10} {:width$} {}",
+                        blame.version,
+                        blame.date.unwrap_or("undated"),
+                        blame.name,
+                        blame.current,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute_import(
+        &self,
+        fbs: &Path,
+        data: &Path,
+        output: &Path,
+        script: Option<Dialect>,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let decoded = DecodedData::load(&schema, data)?;
+        for skipped in &decoded.skipped {
+            info!("Skipped {}, it is not named after a row type", skipped.display());
+        }
+
+        let importer = Importer::new(&schema)?;
+        let rows = match script {
+            Some(dialect) => {
+                let (script, rows) = importer.script(&decoded, dialect)?;
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)
+                        .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+                }
+                fs::write(output, script)
+                    .wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+                rows
+            }
+            None => importer
+                .sqlite(&decoded, output)
+                .wrap_err_with(|| format!("Failed to import {}", data.display()))?,
+        };
+
+        info!(
+            success = true,
+            "Imported {} rows of {} tables to {}",
+            rows,
+            decoded.tables.len(),
+            output.display()
+        );
+
+        Ok(())
+    }
+
+    async fn execute_serve(&self, fbs: &Path, data: &Path, address: SocketAddr) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        schema
+            .check()
+            .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+
+        let decoded = DecodedData::load(&schema, data)?;
+        for skipped in &decoded.skipped {
+            info!("Skipped {}, it is not named after a row type", skipped.display());
+        }
+
+        let server = Server::new(&schema, decoded)?;
+        let tables = server.tables();
+        server
+            .run(address, |address| {
+                println!("Serving {} tables on http://{}", tables, address);
+            })
+            .await
+    }
+
+    pub fn lint_options(
+        &self,
+        allow: &[Rule],
+        deny: &[Rule],
+        languages: &[Language],
+    ) -> Result<(LintOptions, Vec<PlanOption>)> {
+        let hardcoded = LintOptions::default();
+
+        let mut lint_options = hardcoded.clone();
+        let merged = self.config.merge_lint_config(&mut lint_options);
+        let sources = PlanOption::collect(&lint_options, &hardcoded, &merged)?;
+
+        // Rules passed on the command line add to the configured ones
+        lint_options.allow.extend_from_slice(allow);
+        lint_options.deny.extend_from_slice(deny);
+        if !languages.is_empty() {
+            lint_options.languages = languages.to_vec();
+        }
+
+        Ok((lint_options, sources))
+    }
+
+    fn execute_schema_lint(
+        &self,
+        fbs: &Path,
+        allow: &[Rule],
+        deny: &[Rule],
+        languages: &[Language],
+        format: PlanFormat,
+    ) -> Result<()> {
+        let schema = Schema::load(fbs)?;
+        let (options, _) = self.lint_options(allow, deny, languages)?;
+        let findings = Linter::new(&schema, options).run();
+
+        match format {
+            PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&findings)?),
+            PlanFormat::Text => {
+                for finding in &findings {
+                    println!("{}", finding);
+                }
+            }
+        }
+
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .count();
+        if errors > 0 {
+            return Err(eyre!(
+                "{} has {} errors and {} warnings",
+                fbs.display(),
+                errors,
+                findings.len() - errors
+            ));
+        }
+
+        info!(
+            success = true,
+            "{} declarations checked, {} warnings",
+            schema.declarations.len(),
+            findings.len()
+        );
+
+        Ok(())
+    }
+
+    fn execute_postprocess(&self, output: &Path) -> Result<()> {
+        let (options, _) = self.postprocess_options()?;
+        let root = PostProcessor::new(options).merge_roots(output)?;
+
+        info!(success = true, "Wrote {}", root.display());
+        Ok(())
+    }
+
+    fn handle_config(&self, command: &ConfigCommand) -> Result<()> {
+        match command {
+            ConfigCommand::Show { origin } => print!("{}", self.config.show(*origin)?),
+            ConfigCommand::Validate => {
+                for layer in &self.config.layers {
+                    info!("Loaded {}", layer);
+                }
+                info!(success = true, "Configuration is valid");
+            }
         }
+
+        Ok(())
     }
 
     async fn handle_update(&self) -> Result<()> {
@@ -66,10 +719,12 @@ impl CommandHandler {
         let japan_config = ServerConfig::new(ServerRegion::Japan, None, None)?;
         let apk_fetcher = ApkFetcher::new(japan_config.clone())?;
         apk_fetcher.download_apk(true).await?;
+        self.remove_il2cpp_files(&japan_config)?;
 
         let global_config = ServerConfig::new(ServerRegion::Global, None, None)?;
         let apk_fetcher = ApkFetcher::new(global_config.clone())?;
         apk_fetcher.download_apk(true).await?;
+        self.remove_il2cpp_files(&global_config)?;
 
         let tool_fetcher = ToolsFetcher::new()?;
         tool_fetcher
@@ -123,30 +778,176 @@ impl CommandHandler {
         Ok(())
     }
 
-    async fn execute_dump(&self, region: ServerRegion, output: &Path) -> Result<()> {
+    async fn execute_dump(
+        &self,
+        region: ServerRegion,
+        output: &Path,
+        stages: &[Stage],
+    ) -> Result<()> {
         let server_config = ServerConfig::new(region, None, None)?;
 
-        self.prepare_dump_files(&server_config).await?;
-        let (il2cpp_dumper, fbs_dumper) = self.prepare_dumper_tools()?;
-        self.run_il2cpp_dumper(&il2cpp_dumper, &server_config, output)?;
-        self.run_fbs_dumper(&fbs_dumper, &server_config, output)?;
+        for &stage in stages {
+            info!("Running {} stage...", stage);
+
+            match stage {
+                Stage::Fetch => self.fetch_apk(&server_config).await?,
+                Stage::Extract => {
+                    // Catch encrypted or corrupt builds before fetching the dumpers
+                    self.extract_il2cpp_files(&server_config)?;
+                    self.run_preflight(&server_config)?;
+                    self.fetch_dumper_tools().await?;
+                    self.prepare_dumper_tools()?;
+                }
+                Stage::Il2cpp => {
+                    let (libil2cpp, metadata) = self.get_il2cpp_paths(&server_config);
+                    Self::require_input(&libil2cpp, Stage::Extract)?;
+                    Self::require_input(&metadata, Stage::Extract)?;
+
+                    if !stages.contains(&Stage::Extract) {
+                        self.run_preflight(&server_config)?;
+                    }
+                    let (il2cpp_dumper, _) = self.prepare_dumper_tools()?;
+                    self.run_il2cpp_dumper(&il2cpp_dumper, &server_config, output)?;
+                }
+                Stage::Fbs => {
+                    let (libil2cpp, _) = self.get_il2cpp_paths(&server_config);
+                    Self::require_input(&libil2cpp, Stage::Extract)?;
+                    Self::require_input(&output.join("dll"), Stage::Il2cpp)?;
+
+                    let (_, fbs_dumper) = self.prepare_dumper_tools()?;
+                    self.run_fbs_dumper(&fbs_dumper, &server_config, output)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_schema(&self, region: ServerRegion) -> Result<PathBuf> {
+        let server_config = ServerConfig::new(region, None, None)?;
+        self.fetch_apk(&server_config).await?;
+        self.extract_il2cpp_files(&server_config)?;
+
+        let cache = Self::dump_cache(region)?;
+        let (libil2cpp, metadata) = self.get_il2cpp_paths(&server_config);
+        let fingerprint = DumpCache::fingerprint(&libil2cpp, &metadata)?;
+
+        if let Some(schema) = cache.schema(&fingerprint) {
+            info!(
+                success = true,
+                "Reusing cached {} dump for build {}",
+                Self::region_name(region),
+                fingerprint
+            );
+            return Ok(schema);
+        }
+
+        info!(
+            "No cached {} dump for build {}, dumping...",
+            Self::region_name(region),
+            fingerprint
+        );
+        self.execute_dump(region, &cache.entry(&fingerprint), Stage::value_variants())
+            .await?;
+
+        cache.schema(&fingerprint).ok_or_else(|| {
+            eyre!(
+                "FbsDumper did not write {} to {}",
+                SCHEMA_FILE,
+                cache.entry(&fingerprint).display()
+            )
+        })
+    }
+
+    pub fn dump_cache(region: ServerRegion) -> Result<DumpCache> {
+        DumpCache::new(&Self::region_name(region).to_lowercase())
+    }
+
+    fn require_input(path: &Path, stage: Stage) -> Result<()> {
+        if !path.exists() {
+            return Err(eyre!(
+                "Missing {}, run the {} stage first",
+                path.display(),
+                stage
+            ));
+        }
+        Ok(())
+    }
+
+    async fn execute_inspect(&self, region: Region) -> Result<()> {
+        let server_config = ServerConfig::new(region.into(), None, None)?;
+
+        self.fetch_apk(&server_config).await?;
+        self.extract_il2cpp_files(&server_config)?;
+        let report = self.run_preflight(&server_config)?;
+        println!("{}", report);
 
         Ok(())
     }
 
-    async fn execute_generate(&self, fbs: &Path, language: &Language, output: &Path) -> Result<()> {
-        self.prepare_generate_files().await?;
-        let flatc = self.prepare_generate_tools()?;
-        self.run_flatc(&flatc, language, fbs, output)?;
+    /// Writes the tables picked by `--include`/`--exclude` to a schema of their own
+    fn filter_schema(&self, fbs: &Path, filter: &FilterArgs) -> Result<PathBuf> {
+        let schema_filter = SchemaFilter::new(&filter.include, &filter.exclude)?;
+        if schema_filter.is_empty() {
+            return Ok(fbs.to_path_buf());
+        }
+
+        let schema = Schema::load(fbs)?;
+        let filtered = schema_filter.apply(&schema)?;
+        let path = SchemaFilter::output_path(fbs);
+        fs::write(&path, filtered.to_string())
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+
+        info!(
+            success = true,
+            "Kept {} of {} types in {}",
+            filtered.declarations.len(),
+            schema.declarations.len(),
+            path.display()
+        );
+
+        Ok(path)
+    }
+
+    async fn execute_generate(
+        &self,
+        fbs: &Path,
+        languages: &[Language],
+        output: Option<&Path>,
+        codegen: &CodegenArgs,
+        package: &PackageArgs,
+    ) -> Result<()> {
+        let flatc = if self.needs_flatc(languages, output, codegen)? {
+            self.prepare_generate_files().await?;
+            Some(self.prepare_generate_tools()?)
+        } else {
+            None
+        };
+        self.run_generate(flatc.as_ref(), languages, fbs, output, codegen, package)?;
 
         Ok(())
     }
 
+    pub fn needs_flatc(
+        &self,
+        languages: &[Language],
+        output: Option<&Path>,
+        codegen: &CodegenArgs,
+    ) -> Result<bool> {
+        for (language, options, _) in self.flatc_options(languages, output)? {
+            if codegen.select(language, options.gen_object_api)? == Backend::Flatc {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
     async fn prepare_generate_files(&self) -> Result<()> {
         let tool_fetcher = ToolsFetcher::new()?;
 
-        let il2cpp_zip_path = file::get_data_path("tools/Flatc.zip")?;
-        if !il2cpp_zip_path.exists() {
+        let flatc_zip_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, FLATC_FILE))?;
+        if !flatc_zip_path.exists() {
             tool_fetcher
                 .flatc(self.config.flatc.url.as_deref().unwrap_or(FLATC_REPO))
                 .await?;
@@ -171,19 +972,48 @@ impl CommandHandler {
         Ok(flatc)
     }
 
-    async fn prepare_dump_files(&self, server_config: &Rc<ServerConfig>) -> Result<()> {
+    /// Downloads the APK when outdated, dropping the il2cpp files extracted from the old one
+    async fn fetch_apk(&self, server_config: &Rc<ServerConfig>) -> Result<()> {
         let apk_fetcher = ApkFetcher::new(server_config.clone())?;
-        let apk_extractor = ApkExtractor::new(server_config.clone())?;
-        let tool_fetcher = ToolsFetcher::new()?;
+        let outdated = apk_fetcher.check_version().await?.is_some();
 
         apk_fetcher.download_apk(false).await?;
+        if outdated {
+            self.remove_il2cpp_files(server_config)?;
+        }
 
+        Ok(())
+    }
+
+    fn remove_il2cpp_files(&self, server_config: &ServerConfig) -> Result<()> {
+        let (libil2cpp, metadata) = self.get_il2cpp_paths(server_config);
+        for path in [libil2cpp, metadata] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .wrap_err_with(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn extract_il2cpp_files(&self, server_config: &Rc<ServerConfig>) -> Result<()> {
         let (libil2cpp, metadata) = self.get_il2cpp_paths(server_config);
         if !libil2cpp.exists() || !metadata.exists() {
-            apk_extractor.extract_il2cpp()?;
+            let apk_extractor = ApkExtractor::new(server_config.clone())?;
+            apk_extractor
+                .extract_il2cpp()
+                .wrap_err("Failed to extract il2cpp files, run the fetch stage first")?;
         }
 
-        let il2cpp_zip_path = file::get_data_path("tools/Il2CppInspectorRedux.zip")?;
+        Ok(())
+    }
+
+    async fn fetch_dumper_tools(&self) -> Result<()> {
+        let tool_fetcher = ToolsFetcher::new()?;
+
+        let il2cpp_zip_path =
+            file::get_data_path(&format!("{}/{}", TOOLS_DIR, IL2CPP_INSPECTOR_FILE))?;
         if !il2cpp_zip_path.exists() {
             tool_fetcher
                 .il2cpp_dumper(
@@ -196,7 +1026,7 @@ impl CommandHandler {
                 .await?;
         }
 
-        let fbs_zip_path = file::get_data_path("tools/FbsDumperV2.zip")?;
+        let fbs_zip_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, FBS_DUMPER_FILE))?;
         if !fbs_zip_path.exists() {
             tool_fetcher
                 .fbs_dumper(
@@ -238,7 +1068,7 @@ impl CommandHandler {
         Ok((il2cpp_dumper, fbs_dumper))
     }
 
-    fn get_il2cpp_paths(&self, server_config: &ServerConfig) -> (PathBuf, PathBuf) {
+    pub fn get_il2cpp_paths(&self, server_config: &ServerConfig) -> (PathBuf, PathBuf) {
         let libil2cpp = match server_config.region {
             ServerRegion::Japan => file::get_data_path("il2cpp/japan/libil2cpp.so"),
             ServerRegion::Global => file::get_data_path("il2cpp/global/libil2cpp.so"),
@@ -255,17 +1085,30 @@ impl CommandHandler {
         )
     }
 
-    fn run_il2cpp_dumper(
+    fn run_preflight(&self, server_config: &ServerConfig) -> Result<PreflightReport> {
+        info!("Checking il2cpp files...");
+
+        let (libil2cpp, metadata) = self.get_il2cpp_paths(server_config);
+        let report = Preflight::new(libil2cpp, metadata).run()?;
+
+        info!(
+            success = true,
+            "Metadata version {}, {} library",
+            report.metadata.version,
+            report.libil2cpp.arch
+        );
+
+        Ok(report)
+    }
+
+    pub fn il2cpp_dumper_options(
         &self,
-        il2cpp_dumper: &Il2CppDumper,
         server_config: &ServerConfig,
         output: &Path,
-    ) -> Result<()> {
-        info!("Dumping il2cpp...");
-
+    ) -> Result<(Il2CppDumperOptions, Vec<PlanOption>)> {
         let (libil2cpp, metadata) = self.get_il2cpp_paths(server_config);
 
-        let mut il2cpp_options = Il2CppDumperOptions {
+        let hardcoded = Il2CppDumperOptions {
             il2cpp: libil2cpp,
             metadata: Some(metadata),
             output: Some(output.to_path_buf()),
@@ -276,7 +1119,22 @@ impl CommandHandler {
             ..Default::default()
         };
 
-        self.config.merge_il2cpp_dumper_config(&mut il2cpp_options);
+        let mut il2cpp_options = hardcoded.clone();
+        let merged = self.config.merge_il2cpp_dumper_config(&mut il2cpp_options);
+        let sources = PlanOption::collect(&il2cpp_options, &hardcoded, &merged)?;
+
+        Ok((il2cpp_options, sources))
+    }
+
+    fn run_il2cpp_dumper(
+        &self,
+        il2cpp_dumper: &Il2CppDumper,
+        server_config: &ServerConfig,
+        output: &Path,
+    ) -> Result<()> {
+        info!("Dumping il2cpp...");
+
+        let (il2cpp_options, _) = self.il2cpp_dumper_options(server_config, output)?;
 
         il2cpp_dumper.run(il2cpp_options)?;
 
@@ -285,28 +1143,37 @@ impl CommandHandler {
         Ok(())
     }
 
-    fn run_fbs_dumper(
+    pub fn fbs_dumper_options(
         &self,
-        fbs_dumper: &FbsDumper,
         server_config: &ServerConfig,
         output: &Path,
-    ) -> Result<()> {
-        info!("Dumping fbs...");
-
+    ) -> Result<(FbsDumperOptions, Vec<PlanOption>)> {
         let (libil2cpp, _) = self.get_il2cpp_paths(server_config);
 
-        let mut fbs_options = FbsDumperOptions {
+        let hardcoded = FbsDumperOptions {
             dummy_dll: output.join("dll"),
             game_assembly: Some(libil2cpp),
-            output_file: Some(output.join("BlueArchive.fbs")),
-            namespace: Some(match server_config.region {
-                ServerRegion::Japan => "Japan".to_string(),
-                ServerRegion::Global => "Global".to_string(),
-            }),
+            output_file: Some(output.join(SCHEMA_FILE)),
+            namespace: Some(Self::region_name(server_config.region).to_string()),
             ..Default::default()
         };
 
-        self.config.merge_fbs_dumper_config(&mut fbs_options);
+        let mut fbs_options = hardcoded.clone();
+        let merged = self.config.merge_fbs_dumper_config(&mut fbs_options);
+        let sources = PlanOption::collect(&fbs_options, &hardcoded, &merged)?;
+
+        Ok((fbs_options, sources))
+    }
+
+    fn run_fbs_dumper(
+        &self,
+        fbs_dumper: &FbsDumper,
+        server_config: &ServerConfig,
+        output: &Path,
+    ) -> Result<()> {
+        info!("Dumping fbs...");
+
+        let (fbs_options, _) = self.fbs_dumper_options(server_config, output)?;
 
         fbs_dumper.run(fbs_options)?;
 
@@ -315,18 +1182,14 @@ impl CommandHandler {
         Ok(())
     }
 
-    fn run_flatc(
+    fn flatc_options(
         &self,
-        flatc: &FlatC,
-        languages: &Language,
-        fbs: &Path,
-        output: &Path,
-    ) -> Result<()> {
-        info!("Generating flatbuffers...");
-
-        let mut flatc_options = FlatCOptions {
-            languages: vec![*languages],
-            output_path: Some(output.to_path_buf()),
+        languages: &[Language],
+        output: Option<&Path>,
+    ) -> Result<Vec<(Language, FlatCOptions, Vec<PlanOption>)>> {
+        let hardcoded = FlatCOptions {
+            languages: languages.to_vec(),
+            output_path: output.map(Path::to_path_buf),
             no_warnings: true,
             scoped_enums: true,
             gen_object_api: true,
@@ -335,14 +1198,290 @@ impl CommandHandler {
             ..Default::default()
         };
 
-        self.config.merge_flatc_config(&mut flatc_options);
+        let mut flatc_options = hardcoded.clone();
+        let mut merged = self.config.merge_flatc_config(&mut flatc_options);
+
+        // Explicit command line arguments win over the configuration and profile
+        if !languages.is_empty() {
+            flatc_options.languages = hardcoded.languages.clone();
+            merged.retain(|field| *field != "languages");
+        }
+        if output.is_some() {
+            flatc_options.output_path = hardcoded.output_path.clone();
+            merged.retain(|field| *field != "output_path");
+        }
+
+        let mut selected: Vec<Language> = Vec::new();
+        for language in &flatc_options.languages {
+            if !selected.contains(language) {
+                selected.push(*language);
+            }
+        }
+        if selected.is_empty() {
+            return Err(eyre!("No language selected, pass --language or set flatc.languages"));
+        }
+        let Some(output) = flatc_options.output_path.clone() else {
+            return Err(eyre!("No output path selected, pass --output or set flatc.output_path"));
+        };
+
+        // A single language keeps writing straight into the output directory
+        let split = selected.len() > 1;
+
+        selected
+            .into_iter()
+            .map(|language| {
+                let mut options = flatc_options.for_language(language);
+                let mut hardcoded = hardcoded.for_language(language);
+                if split {
+                    options.output_path = Some(output.join(language.to_string()));
+                    hardcoded.output_path = options.output_path.clone();
+                }
+
+                let sources = PlanOption::collect(&options, &hardcoded, &merged)?;
+                Ok((language, options, sources))
+            })
+            .collect()
+    }
+
+    pub fn postprocess_options(&self) -> Result<(PostProcessOptions, Vec<PlanOption>)> {
+        let hardcoded = PostProcessOptions::default();
+
+        let mut postprocess_options = hardcoded.clone();
+        let merged = self.config.merge_postprocess_config(&mut postprocess_options);
+        let sources = PlanOption::collect(&postprocess_options, &hardcoded, &merged)?;
+
+        Ok((postprocess_options, sources))
+    }
+
+    pub fn generate_jobs(
+        &self,
+        languages: &[Language],
+        output: Option<&Path>,
+        codegen: &CodegenArgs,
+        package: &PackageArgs,
+        version: &str,
+        flatbuffers_version: &str,
+    ) -> Result<Vec<GenerateJob>> {
+        let jobs = self.flatc_options(languages, output)?;
+        let (postprocess, _) = self.postprocess_options()?;
+
+        for kind in &package.package {
+            if !jobs.iter().any(|(language, _, _)| kind.accepts(*language)) {
+                return Err(eyre!(
+                    "--package {} needs a matching language among the generated languages",
+                    kind
+                ));
+            }
+        }
+
+        // Split outputs live in <output>/<language>, packages are named after <output>
+        let split = jobs.len() > 1;
+
+        jobs.into_iter()
+            .map(|(language, mut options, sources)| {
+                let backend = codegen.select(language, options.gen_object_api)?;
+                let output = options.output_path.clone().unwrap_or_default();
 
-        flatc.compile(flatc_options, vec![fbs.to_path_buf()], vec![])?;
+                let package = match package.package.iter().find(|kind| kind.accepts(language)) {
+                    Some(kind) => {
+                        let name = match &package.package_name {
+                            Some(name) => name.clone(),
+                            None => std::path::absolute(&output)?
+                                .ancestors()
+                                .nth(if split { 1 } else { 0 })
+                                .and_then(Path::file_name)
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .ok_or_else(|| eyre!("Pass --package-name"))?,
+                        };
+                        let metadata = PackageMetadata::new(&name, version, flatbuffers_version);
+
+                        Some(match kind {
+                            Package::Cargo => {
+                                let crate_package =
+                                    CargoPackage::new(metadata, options.rust_serialize);
+                                Box::new(match &postprocess.module_root {
+                                    Some(module_root) => crate_package.module_root(module_root),
+                                    None => crate_package,
+                                }) as Box<dyn GeneratedPackage>
+                            }
+                            Package::Python => Box::new(PythonPackage::new(
+                                metadata,
+                                options.python_version.clone(),
+                            )),
+                            Package::Npm => Box::new(NpmPackage::new(
+                                metadata,
+                                language == Language::TypeScript,
+                            )),
+                            Package::Nuget => Box::new(NugetPackage::new(
+                                metadata,
+                                options.cs_gen_json_serializer,
+                            )),
+                        })
+                    }
+                    None => None,
+                };
+
+                let source = match &package {
+                    Some(package) => package.source_dir(&output),
+                    None => output.clone(),
+                };
+
+                let processor = (language == Language::Rust && postprocess.is_enabled())
+                    .then(|| PostProcessor::new(postprocess.clone()));
+                options.output_path = Some(match &processor {
+                    Some(processor) => processor.generated_dir(&source),
+                    None => source.clone(),
+                });
+
+                Ok(GenerateJob {
+                    language,
+                    backend,
+                    decrypt: codegen.decrypt_helpers,
+                    output,
+                    source,
+                    options,
+                    sources,
+                    processor,
+                    package,
+                })
+            })
+            .collect()
+    }
+
+    fn run_generate(
+        &self,
+        flatc: Option<&FlatC>,
+        languages: &[Language],
+        fbs: &Path,
+        output: Option<&Path>,
+        codegen: &CodegenArgs,
+        package: &PackageArgs,
+    ) -> Result<()> {
+        info!("Generating flatbuffers...");
+
+        // The runtime libraries are versioned in lockstep with flatc
+        let (version, flatbuffers_version) = if package.package.is_empty() {
+            (String::new(), FLATBUFFERS_VERSION.to_string())
+        } else {
+            let version = package.version()?;
+            let flatbuffers_version = flatc
+                .and_then(|flatc| flatc.version().ok())
+                .unwrap_or_else(|| FLATBUFFERS_VERSION.to_string());
+            (version, flatbuffers_version)
+        };
+        let jobs = self.generate_jobs(
+            languages,
+            output,
+            codegen,
+            package,
+            &version,
+            &flatbuffers_version,
+        )?;
+
+        // Parsed once up front so schema errors are reported before any job starts
+        let schema = if jobs
+            .iter()
+            .any(|job| job.backend == Backend::Native || job.decrypt)
+        {
+            let schema = Schema::load(fbs)?;
+            schema
+                .check()
+                .wrap_err_with(|| format!("Invalid schema {}", fbs.display()))?;
+            Some(schema)
+        } else {
+            None
+        };
+        let schema = schema.as_ref();
+
+        let results: Vec<(Language, PathBuf, Result<()>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs
+                .into_iter()
+                .map(|job| {
+                    let output = job.output.clone();
+                    let handle = scope.spawn(move || {
+                        match job.backend {
+                            Backend::Native => {
+                                let schema = schema.ok_or_else(|| eyre!("Schema is not loaded"))?;
+                                let generated = job.options.output_path.clone().unwrap_or_default();
+                                let options = RustOptions {
+                                    decrypt_helpers: job.decrypt,
+                                    ..RustOptions::from(&job.options)
+                                };
+                                RustGenerator::new(schema, options)?.generate(&generated)?;
+                            }
+                            Backend::Flatc => {
+                                let flatc = flatc.ok_or_else(|| eyre!("FlatC is not prepared"))?;
+                                let generated = job.options.output_path.clone().unwrap_or_default();
+                                flatc.compile(job.options, vec![fbs.to_path_buf()], vec![])?;
+                                if job.decrypt {
+                                    let schema =
+                                        schema.ok_or_else(|| eyre!("Schema is not loaded"))?;
+                                    match job.language {
+                                        Language::CSharp => {
+                                            decrypt::csharp::generate(schema, &generated)?
+                                        }
+                                        Language::Python => {
+                                            decrypt::python::generate(schema, &generated)?
+                                        }
+                                        _ => Vec::new(),
+                                    };
+                                }
+                            }
+                        }
+                        if let Some(processor) = job.processor {
+                            processor.run(&job.source)?;
+                        }
+                        if let Some(package) = job.package {
+                            package.write(&job.output)?;
+                        }
+                        Ok(())
+                    });
+                    (job.language, output, handle)
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|(language, output, handle)| {
+                    let result = handle
+                        .join()
+                        .unwrap_or_else(|_| Err(eyre!("Generator thread panicked")));
+                    (language, output, result)
+                })
+                .collect()
+        });
+
+        let mut failed = Vec::new();
+        for (language, output, result) in results {
+            match result {
+                Ok(()) => info!(
+                    success = true,
+                    "Generated {} code in {}",
+                    language,
+                    output.display()
+                ),
+                Err(err) => {
+                    error!("Failed to generate {} code: {:#}", language, err);
+                    failed.push(language.to_string());
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(eyre!("Generation failed for: {}", failed.join(", ")));
+        }
 
         info!(success = true, "Successfully generated");
 
         Ok(())
     }
+
+    pub fn region_name(region: ServerRegion) -> &'static str {
+        match region {
+            ServerRegion::Japan => "Japan",
+            ServerRegion::Global => "Global",
+        }
+    }
 }
 
 pub async fn run(args: Args) -> Result<()> {
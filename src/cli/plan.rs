@@ -0,0 +1,733 @@
+use crate::cli::args::{
+    Backend, CodegenArgs, Commands, FilterArgs, HistoryCommand, PackageArgs, SchemaCommand, Stage,
+};
+use crate::cli::parse::CommandHandler;
+use crate::helpers::config::*;
+use crate::schema::export;
+use crate::schema::filter::SchemaFilter;
+use crate::tools::cache::DumpCache;
+use crate::tools::extract::ToolsExtractor;
+use crate::wrappers::fbs_dumper::FbsDumper;
+use crate::wrappers::flatc::{FlatC, Language};
+use crate::wrappers::il2cpp_dumper::Il2CppDumper;
+
+use baad::helpers::{ServerConfig, ServerRegion};
+use baad::utils::file;
+use clap::ValueEnum;
+use eyre::{eyre, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum PlanFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionSource {
+    Default,
+    Hardcoded,
+    Config,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanOption {
+    pub name: String,
+    pub value: Value,
+    pub source: OptionSource,
+}
+
+impl PlanOption {
+    pub fn collect<T: Serialize + Default>(
+        options: &T,
+        hardcoded: &T,
+        config_fields: &[&str],
+    ) -> Result<Vec<Self>> {
+        let default = serde_json::to_value(T::default())?;
+        let hardcoded = serde_json::to_value(hardcoded)?;
+        let Value::Object(options) = serde_json::to_value(options)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(options
+            .into_iter()
+            .map(|(name, value)| {
+                let source = if config_fields.contains(&name.as_str()) {
+                    OptionSource::Config
+                } else if hardcoded.get(&name) != default.get(&name) {
+                    OptionSource::Hardcoded
+                } else {
+                    OptionSource::Default
+                };
+
+                Self { name, value, source }
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanCommand {
+    pub tool: String,
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub options: Vec<PlanOption>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlanStep {
+    Remove {
+        path: PathBuf,
+    },
+    Download {
+        name: String,
+        source: String,
+        destination: Option<PathBuf>,
+    },
+    Extract {
+        name: String,
+        source: String,
+        destination: PathBuf,
+    },
+    Run(PlanCommand),
+    Process {
+        name: String,
+        path: PathBuf,
+        options: Vec<PlanOption>,
+    },
+    Skip {
+        name: String,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Plan {
+    pub steps: Vec<PlanStep>,
+}
+
+impl Plan {
+    pub fn push(&mut self, step: PlanStep) {
+        self.steps.push(step);
+    }
+
+    pub fn print(&self, format: PlanFormat) -> Result<()> {
+        match format {
+            PlanFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+            PlanFormat::Text => self.print_text(),
+        }
+        Ok(())
+    }
+
+    fn quote(arg: &str) -> String {
+        if arg.is_empty() || arg.contains(char::is_whitespace) {
+            format!("\"{}\"", arg)
+        } else {
+            arg.to_string()
+        }
+    }
+
+    fn print_text(&self) {
+        for step in &self.steps {
+            match step {
+                PlanStep::Remove { path } => println!("[remove]   {}", path.display()),
+                PlanStep::Download {
+                    name,
+                    source,
+                    destination,
+                } => match destination {
+                    Some(destination) => println!(
+                        "[download] {} from {} -> {}",
+                        name,
+                        source,
+                        destination.display()
+                    ),
+                    None => println!("[download] {} from {}", name, source),
+                },
+                PlanStep::Extract {
+                    name,
+                    source,
+                    destination,
+                } => println!(
+                    "[extract]  {} from {} -> {}",
+                    name,
+                    source,
+                    destination.display()
+                ),
+                PlanStep::Skip { name, reason } => println!("[skip]     {}: {}", name, reason),
+                PlanStep::Run(command) => {
+                    println!("[run]      {}", command.tool);
+
+                    let argv: Vec<String> = std::iter::once(command.program.to_string_lossy())
+                        .chain(command.args.iter().map(Into::into))
+                        .map(|arg| Self::quote(&arg))
+                        .collect();
+                    println!("           $ {}", argv.join(" "));
+
+                    Self::print_options(&command.options);
+                }
+                PlanStep::Process {
+                    name,
+                    path,
+                    options,
+                } => {
+                    println!("[process]  {} in {}", name, path.display());
+                    Self::print_options(options);
+                }
+            }
+        }
+    }
+
+    fn print_options(options: &[PlanOption]) {
+        let mut omitted = 0;
+        for option in options {
+            if option.source == OptionSource::Default {
+                omitted += 1;
+                continue;
+            }
+
+            let source = match option.source {
+                OptionSource::Default => "default",
+                OptionSource::Hardcoded => "hardcoded",
+                OptionSource::Config => "config",
+            };
+            println!("           {} = {} ({})", option.name, option.value, source);
+        }
+
+        if omitted > 0 {
+            println!("           ({} options left at their defaults)", omitted);
+        }
+    }
+}
+
+impl CommandHandler {
+    pub fn handle_plan(&self) -> Result<Plan> {
+        let mut plan = Plan::default();
+
+        if self.args.clean {
+            plan.push(PlanStep::Remove {
+                path: file::get_data_path(TOOLS_DIR)?,
+            });
+        }
+
+        match &self.args.command {
+            Some(Commands::Dump { target }) => {
+                let (region, output, stages, schema) = target.parts();
+                let stages = stages.resolve()?;
+
+                self.plan_dump(&mut plan, region, output, &stages)?;
+                if stages.contains(&Stage::Fbs) {
+                    if schema.format.is_some() {
+                        plan.push(PlanStep::Process {
+                            name: "schema fmt".to_string(),
+                            path: output.join(SCHEMA_FILE),
+                            options: Vec::new(),
+                        });
+                    }
+                    for format in &schema.export {
+                        plan.push(PlanStep::Process {
+                            name: format!("schema export {}", format),
+                            path: export::output_path(&output.join(SCHEMA_FILE), *format),
+                            options: Vec::new(),
+                        });
+                    }
+                    if schema.changelog.is_some() {
+                        plan.push(PlanStep::Process {
+                            name: "schema changelog".to_string(),
+                            path: output.join(CHANGELOG_FILE),
+                            options: Vec::new(),
+                        });
+                    }
+                }
+            }
+            Some(Commands::Generate {
+                fbs,
+                region,
+                language,
+                output,
+                filter,
+                codegen,
+                package,
+            }) => {
+                let fbs = match (fbs, region) {
+                    (Some(fbs), _) => fbs.clone(),
+                    (None, Some(region)) => self.plan_schema(&mut plan, (*region).into())?,
+                    (None, None) => return Err(eyre!("Pass --fbs or --region")),
+                };
+                let fbs = self.plan_filter(&mut plan, &fbs, filter)?;
+                self.plan_generate(
+                    &mut plan,
+                    &fbs,
+                    language,
+                    output.as_deref(),
+                    codegen,
+                    package,
+                )?
+            }
+            Some(Commands::Inspect { region }) => {
+                let server_config = ServerConfig::new((*region).into(), None, None)?;
+                self.plan_apk(&mut plan, server_config.region, false);
+                self.plan_il2cpp_files(&mut plan, &server_config);
+            }
+            Some(Commands::Schema { command }) => match command {
+                SchemaCommand::Split { output, .. } => plan.push(PlanStep::Process {
+                    name: "schema split".to_string(),
+                    path: output.clone(),
+                    options: Vec::new(),
+                }),
+                SchemaCommand::Merge { output, .. } => plan.push(PlanStep::Process {
+                    name: "schema merge".to_string(),
+                    path: output.clone(),
+                    options: Vec::new(),
+                }),
+                SchemaCommand::Export {
+                    fbs,
+                    format,
+                    output,
+                } => plan.push(PlanStep::Process {
+                    name: format!("schema export {}", format),
+                    path: output
+                        .clone()
+                        .unwrap_or_else(|| export::output_path(fbs, *format)),
+                    options: Vec::new(),
+                }),
+                SchemaCommand::Doc { output, format, .. } => plan.push(PlanStep::Process {
+                    name: format!("schema doc {}", format),
+                    path: output.clone(),
+                    options: Vec::new(),
+                }),
+                SchemaCommand::Changelog { fbs, output, .. } => plan.push(PlanStep::Process {
+                    name: "schema changelog".to_string(),
+                    path: output
+                        .clone()
+                        .unwrap_or_else(|| fbs.with_file_name(CHANGELOG_FILE)),
+                    options: Vec::new(),
+                }),
+                SchemaCommand::Lint {
+                    fbs,
+                    allow,
+                    deny,
+                    language,
+                    ..
+                } => {
+                    let (_, options) = self.lint_options(allow, deny, language)?;
+                    plan.push(PlanStep::Process {
+                        name: "schema lint".to_string(),
+                        path: fbs.clone(),
+                        options,
+                    });
+                }
+                SchemaCommand::Fmt { fbs, check, .. } => {
+                    let name = if *check {
+                        "schema fmt --check"
+                    } else {
+                        "schema fmt"
+                    };
+                    for path in fbs {
+                        plan.push(PlanStep::Process {
+                            name: name.to_string(),
+                            path: path.clone(),
+                            options: Vec::new(),
+                        });
+                    }
+                }
+            },
+            Some(Commands::Import { output, script, .. }) => plan.push(PlanStep::Process {
+                name: match script {
+                    Some(dialect) => format!("import --script {}", dialect),
+                    None => "import".to_string(),
+                },
+                path: output.clone(),
+                options: Vec::new(),
+            }),
+            Some(Commands::Serve { data, .. }) => plan.push(PlanStep::Process {
+                name: "serve".to_string(),
+                path: data.clone(),
+                options: Vec::new(),
+            }),
+            Some(Commands::Postprocess { output }) => {
+                let (_, options) = self.postprocess_options()?;
+                plan.push(PlanStep::Process {
+                    name: "postprocess".to_string(),
+                    path: output.clone(),
+                    options,
+                });
+            }
+            Some(Commands::History { command }) => match command {
+                HistoryCommand::Add { store, .. } => plan.push(PlanStep::Process {
+                    name: "history add".to_string(),
+                    path: store.clone(),
+                    options: Vec::new(),
+                }),
+                HistoryCommand::Log { store, .. } => plan.push(PlanStep::Process {
+                    name: "history log".to_string(),
+                    path: store.clone(),
+                    options: Vec::new(),
+                }),
+                HistoryCommand::Blame { store, .. } => plan.push(PlanStep::Process {
+                    name: "history blame".to_string(),
+                    path: store.clone(),
+                    options: Vec::new(),
+                }),
+            },
+            Some(Commands::Config { .. }) => {}
+            None => {
+                if self.args.update {
+                    self.plan_update(&mut plan)?;
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_update(&self, plan: &mut Plan) -> Result<()> {
+        self.plan_apk(plan, ServerRegion::Japan, true);
+        self.plan_apk(plan, ServerRegion::Global, true);
+
+        let tools = [
+            (
+                IL2CPP_INSPECTOR_FILE,
+                IL2CPP_INSPECTOR_DIR,
+                self.config.il2cpp_dumper.url.as_deref().unwrap_or(IL2CPP_INSPECTOR_REPO),
+                self.config
+                    .il2cpp_dumper
+                    .binary_name
+                    .as_deref()
+                    .unwrap_or(IL2CPP_INSPECTOR_BINARY),
+            ),
+            (
+                FBS_DUMPER_FILE,
+                FBS_DUMPER_DIR,
+                self.config.fbs_dumper.url.as_deref().unwrap_or(FBS_DUMPER_REPO),
+                self.config
+                    .fbs_dumper
+                    .binary_name
+                    .as_deref()
+                    .unwrap_or(FBS_DUMPER_BINARY),
+            ),
+            (
+                FLATC_FILE,
+                FLATC_DIR,
+                self.config.flatc.url.as_deref().unwrap_or(FLATC_REPO),
+                self.config.flatc.binary_name.as_deref().unwrap_or(FLATC_BINARY),
+            ),
+        ];
+
+        for (zip_file, dir, repo, binary_name) in tools {
+            self.plan_tool_download(plan, zip_file, repo, true)?;
+            self.plan_tool_extract(plan, zip_file, dir, binary_name, true)?;
+        }
+
+        Ok(())
+    }
+
+    fn plan_dump(
+        &self,
+        plan: &mut Plan,
+        region: ServerRegion,
+        output: &Path,
+        stages: &[Stage],
+    ) -> Result<()> {
+        let server_config = ServerConfig::new(region, None, None)?;
+        let tool_extractor = ToolsExtractor::new()?;
+
+        let il2cpp_binary_name = self
+            .config
+            .il2cpp_dumper
+            .binary_name
+            .as_deref()
+            .unwrap_or(IL2CPP_INSPECTOR_BINARY);
+        let fbs_binary_name = self
+            .config
+            .fbs_dumper
+            .binary_name
+            .as_deref()
+            .unwrap_or(FBS_DUMPER_BINARY);
+
+        for stage in stages {
+            match stage {
+                Stage::Fetch => self.plan_apk(plan, region, false),
+                Stage::Extract => {
+                    self.plan_il2cpp_files(plan, &server_config);
+                    self.plan_tool_download(
+                        plan,
+                        IL2CPP_INSPECTOR_FILE,
+                        self.config
+                            .il2cpp_dumper
+                            .url
+                            .as_deref()
+                            .unwrap_or(IL2CPP_INSPECTOR_REPO),
+                        false,
+                    )?;
+                    self.plan_tool_download(
+                        plan,
+                        FBS_DUMPER_FILE,
+                        self.config
+                            .fbs_dumper
+                            .url
+                            .as_deref()
+                            .unwrap_or(FBS_DUMPER_REPO),
+                        false,
+                    )?;
+                    self.plan_tool_extract(
+                        plan,
+                        IL2CPP_INSPECTOR_FILE,
+                        IL2CPP_INSPECTOR_DIR,
+                        il2cpp_binary_name,
+                        false,
+                    )?;
+                    self.plan_tool_extract(
+                        plan,
+                        FBS_DUMPER_FILE,
+                        FBS_DUMPER_DIR,
+                        fbs_binary_name,
+                        false,
+                    )?;
+                }
+                Stage::Il2cpp => {
+                    let (options, sources) = self.il2cpp_dumper_options(&server_config, output)?;
+                    plan.push(PlanStep::Run(PlanCommand {
+                        tool: IL2CPP_INSPECTOR_DIR.to_string(),
+                        program: tool_extractor
+                            .binary_path(il2cpp_binary_name, IL2CPP_INSPECTOR_DIR)?,
+                        args: Il2CppDumper::args(&options).to_strings(),
+                        options: sources,
+                    }));
+                }
+                Stage::Fbs => {
+                    let (options, sources) = self.fbs_dumper_options(&server_config, output)?;
+                    plan.push(PlanStep::Run(PlanCommand {
+                        tool: FBS_DUMPER_DIR.to_string(),
+                        program: tool_extractor.binary_path(fbs_binary_name, FBS_DUMPER_DIR)?,
+                        args: FbsDumper::args(&options).to_strings(),
+                        options: sources,
+                    }));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plan_schema(&self, plan: &mut Plan, region: ServerRegion) -> Result<PathBuf> {
+        let server_config = ServerConfig::new(region, None, None)?;
+        let cache = Self::dump_cache(region)?;
+        let (libil2cpp, metadata) = self.get_il2cpp_paths(&server_config);
+
+        // The build is only known once the il2cpp files have been extracted
+        let fingerprint = if libil2cpp.exists() && metadata.exists() {
+            DumpCache::fingerprint(&libil2cpp, &metadata)?
+        } else {
+            "<build>".to_string()
+        };
+
+        if let Some(schema) = cache.schema(&fingerprint) {
+            self.plan_apk(plan, region, false);
+            self.plan_il2cpp_files(plan, &server_config);
+            plan.push(PlanStep::Skip {
+                name: format!("{} dump", Self::region_name(region)),
+                reason: format!("cached schema at {}", schema.display()),
+            });
+            return Ok(schema);
+        }
+
+        let output = cache.entry(&fingerprint);
+        self.plan_dump(plan, region, &output, Stage::value_variants())?;
+
+        Ok(output.join(SCHEMA_FILE))
+    }
+
+    fn plan_filter(&self, plan: &mut Plan, fbs: &Path, filter: &FilterArgs) -> Result<PathBuf> {
+        if SchemaFilter::new(&filter.include, &filter.exclude)?.is_empty() {
+            return Ok(fbs.to_path_buf());
+        }
+
+        let path = SchemaFilter::output_path(fbs);
+        plan.push(PlanStep::Process {
+            name: "schema filter".to_string(),
+            path: path.clone(),
+            options: Vec::new(),
+        });
+        Ok(path)
+    }
+
+    fn plan_generate(
+        &self,
+        plan: &mut Plan,
+        fbs: &Path,
+        languages: &[Language],
+        output: Option<&Path>,
+        codegen: &CodegenArgs,
+        package: &PackageArgs,
+    ) -> Result<()> {
+        let flatc_binary_name = self
+            .config
+            .flatc
+            .binary_name
+            .as_deref()
+            .unwrap_or(FLATC_BINARY);
+
+        if self.needs_flatc(languages, output, codegen)? {
+            self.plan_tool_download(
+                plan,
+                FLATC_FILE,
+                self.config.flatc.url.as_deref().unwrap_or(FLATC_REPO),
+                false,
+            )?;
+            self.plan_tool_extract(plan, FLATC_FILE, FLATC_DIR, flatc_binary_name, false)?;
+        }
+
+        let program = ToolsExtractor::new()?.binary_path(flatc_binary_name, FLATC_DIR)?;
+        let files = vec![fbs.to_path_buf()];
+        let (_, postprocess_sources) = self.postprocess_options()?;
+
+        let version = if package.package.is_empty() {
+            String::new()
+        } else {
+            package.version()?
+        };
+
+        let jobs = self.generate_jobs(
+            languages,
+            output,
+            codegen,
+            package,
+            &version,
+            FLATBUFFERS_VERSION,
+        )?;
+        for job in jobs {
+            match job.backend {
+                Backend::Native => plan.push(PlanStep::Process {
+                    name: format!("native {} codegen", job.language),
+                    path: job.options.output_path.clone().unwrap_or_default(),
+                    options: job.sources,
+                }),
+                Backend::Flatc => {
+                    plan.push(PlanStep::Run(PlanCommand {
+                        tool: format!("{} ({})", FLATC_DIR, job.language),
+                        program: program.clone(),
+                        args: FlatC::args(&job.options, &files, &[]).to_strings(),
+                        options: job.sources,
+                    }));
+                    if job.decrypt {
+                        plan.push(PlanStep::Process {
+                            name: format!("{} decrypt helpers", job.language),
+                            path: job.options.output_path.clone().unwrap_or_default(),
+                            options: Vec::new(),
+                        });
+                    }
+                }
+            }
+
+            if job.processor.is_some() {
+                plan.push(PlanStep::Process {
+                    name: format!("{} output", job.language),
+                    path: job.source,
+                    options: postprocess_sources.clone(),
+                });
+            }
+
+            if let Some(package) = job.package {
+                plan.push(PlanStep::Process {
+                    name: package.to_string(),
+                    path: job.output,
+                    options: Vec::new(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plan_apk(&self, plan: &mut Plan, region: ServerRegion, forced: bool) {
+        plan.push(PlanStep::Download {
+            name: format!("{} APK", Self::region_name(region)),
+            source: if forced {
+                "game server".to_string()
+            } else {
+                "game server (skipped when up to date)".to_string()
+            },
+            destination: None,
+        });
+    }
+
+    fn plan_il2cpp_files(&self, plan: &mut Plan, server_config: &ServerConfig) {
+        let (libil2cpp, metadata) = self.get_il2cpp_paths(server_config);
+
+        if libil2cpp.exists() && metadata.exists() {
+            plan.push(PlanStep::Skip {
+                name: "il2cpp files".to_string(),
+                reason: "already extracted".to_string(),
+            });
+        } else {
+            plan.push(PlanStep::Extract {
+                name: "il2cpp files".to_string(),
+                source: format!("{} APK", Self::region_name(server_config.region)),
+                destination: libil2cpp.parent().unwrap_or(&libil2cpp).to_path_buf(),
+            });
+        }
+    }
+
+    fn plan_tool_download(
+        &self,
+        plan: &mut Plan,
+        zip_file: &str,
+        repo: &str,
+        forced: bool,
+    ) -> Result<()> {
+        let zip_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, zip_file))?;
+
+        if zip_path.exists() && !forced {
+            plan.push(PlanStep::Skip {
+                name: zip_file.to_string(),
+                reason: "already downloaded".to_string(),
+            });
+            return Ok(());
+        }
+
+        let source = if repo.starts_with(HTTP_PREFIX) || repo.starts_with(HTTPS_PREFIX) {
+            repo.to_string()
+        } else {
+            format!("latest release of {}", repo)
+        };
+
+        plan.push(PlanStep::Download {
+            name: zip_file.to_string(),
+            source,
+            destination: Some(zip_path),
+        });
+
+        Ok(())
+    }
+
+    fn plan_tool_extract(
+        &self,
+        plan: &mut Plan,
+        zip_file: &str,
+        dir: &str,
+        binary_name: &str,
+        forced: bool,
+    ) -> Result<()> {
+        let binary_path = ToolsExtractor::new()?.binary_path(binary_name, dir)?;
+
+        if binary_path.exists() && !forced {
+            plan.push(PlanStep::Skip {
+                name: dir.to_string(),
+                reason: "already extracted".to_string(),
+            });
+        } else {
+            plan.push(PlanStep::Extract {
+                name: dir.to_string(),
+                source: zip_file.to_string(),
+                destination: binary_path,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+use crate::cli::config::{
+    Config, FbsDumperConfig, FlatCConfig, Il2CppDumperConfig, LintConfig, Origin, PostProcessConfig,
+};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use toml::de::{DeTable, DeValue};
+use toml::{Table, Value};
+
+const SECTIONS: &[&str] = &[
+    "fbs_dumper",
+    "flatc",
+    "il2cpp_dumper",
+    "lint",
+    "postprocess",
+    "profiles",
+];
+const PROFILE_SECTIONS: &[&str] = &[
+    "fbs_dumper",
+    "flatc",
+    "il2cpp_dumper",
+    "lint",
+    "postprocess",
+];
+
+const ALLOWED_VALUES: &[(&str, &str, &[&str])] = &[
+    (
+        "il2cpp_dumper",
+        "layout",
+        &["single", "namespace", "assembly", "class", "tree"],
+    ),
+    ("il2cpp_dumper", "sorting_mode", &["index", "name"]),
+    ("il2cpp_dumper", "disassembler", &["IDA", "Ghidra", "BinaryNinja"]),
+    ("flatc", "proto_id_gap", &["nop", "warn", "error"]),
+    ("flatc", "cpp_field_case_style", &["unchanged", "upper", "lower"]),
+];
+
+#[derive(Debug)]
+pub struct ConfigIssue {
+    pub origin: Origin,
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.origin, self.position) {
+            (Origin::User(path) | Origin::Project(path), Some((line, column))) => {
+                write!(f, "{}:{}:{}: {}", path.display(), line, column, self.message)
+            }
+            (origin, _) => write!(f, "{}: {}", origin, self.message),
+        }
+    }
+}
+
+struct Spans {
+    content: String,
+    keys: HashMap<String, Range<usize>>,
+    values: HashMap<String, Range<usize>>,
+}
+
+impl Spans {
+    fn parse(content: &str) -> Result<Self, ConfigIssue> {
+        let table = DeTable::parse(content).map_err(|err| ConfigIssue {
+            origin: Origin::Default,
+            position: err.span().map(|span| Self::position(content, span.start)),
+            message: err.message().to_string(),
+        })?;
+
+        let mut spans = Self {
+            content: content.to_string(),
+            keys: HashMap::new(),
+            values: HashMap::new(),
+        };
+        spans.collect(table.get_ref(), "");
+
+        Ok(spans)
+    }
+
+    fn collect(&mut self, table: &DeTable<'_>, prefix: &str) {
+        for (key, value) in table.iter() {
+            let path = if prefix.is_empty() {
+                key.get_ref().to_string()
+            } else {
+                format!("{}.{}", prefix, key.get_ref())
+            };
+
+            self.keys.insert(path.clone(), key.span());
+            self.values.insert(path.clone(), value.span());
+
+            if let DeValue::Table(child) = value.get_ref() {
+                self.collect(child, &path);
+            }
+        }
+    }
+
+    fn position(content: &str, offset: usize) -> (usize, usize) {
+        let before = &content[..offset.min(content.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |index| index + 1) + 1;
+        (line, column)
+    }
+
+    fn key(&self, path: &str) -> Option<(usize, usize)> {
+        self.keys
+            .get(path)
+            .map(|span| Self::position(&self.content, span.start))
+    }
+
+    fn value(&self, path: &str) -> Option<(usize, usize)> {
+        self.values
+            .get(path)
+            .map(|span| Self::position(&self.content, span.start))
+    }
+}
+
+pub struct ConfigValidator {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigValidator {
+    pub fn new() -> Self {
+        Self { issues: Vec::new() }
+    }
+
+    pub fn into_issues(self) -> Vec<ConfigIssue> {
+        self.issues
+    }
+
+    pub fn validate(&mut self, origin: &Origin, table: &Table, content: Option<&str>) {
+        let spans = match content.map(Spans::parse) {
+            Some(Ok(spans)) => Some(spans),
+            Some(Err(mut issue)) => {
+                issue.origin = origin.clone();
+                self.issues.push(issue);
+                return;
+            }
+            None => None,
+        };
+
+        let start = self.issues.len();
+
+        for (section, value) in table {
+            if section == "profiles" {
+                let Value::Table(profiles) = value else {
+                    self.not_a_table(origin, spans.as_ref(), section);
+                    continue;
+                };
+
+                for (name, profile) in profiles {
+                    let prefix = format!("profiles.{}", name);
+                    let Value::Table(profile) = profile else {
+                        self.not_a_table(origin, spans.as_ref(), &prefix);
+                        continue;
+                    };
+
+                    for (section, value) in profile {
+                        let path = format!("{}.{}", prefix, section);
+                        if !PROFILE_SECTIONS.contains(&section.as_str()) {
+                            self.unknown_key(origin, spans.as_ref(), &path, section, PROFILE_SECTIONS);
+                            continue;
+                        }
+                        self.validate_section(origin, spans.as_ref(), &path, section, value);
+                    }
+                }
+                continue;
+            }
+
+            if !SECTIONS.contains(&section.as_str()) {
+                self.unknown_key(origin, spans.as_ref(), section, section, SECTIONS);
+                continue;
+            }
+            self.validate_section(origin, spans.as_ref(), section, section, value);
+        }
+
+        self.issues[start..].sort_by_key(|issue| issue.position);
+    }
+
+    fn validate_section(
+        &mut self,
+        origin: &Origin,
+        spans: Option<&Spans>,
+        prefix: &str,
+        section: &str,
+        value: &Value,
+    ) {
+        let Some(fields) = Self::section_fields(section) else {
+            return;
+        };
+
+        let Value::Table(entries) = value else {
+            self.not_a_table(origin, spans, prefix);
+            return;
+        };
+
+        for (key, value) in entries {
+            let path = format!("{}.{}", prefix, key);
+
+            if !fields.contains(&key.as_str()) {
+                self.unknown_key(origin, spans, &path, key, fields);
+                continue;
+            }
+
+            let position = spans.and_then(|spans| spans.value(&path));
+            if let Err(message) = Self::check_type(section, key, value) {
+                self.push(origin, position, format!("`{}`: {}", path, message));
+            } else if let Err(message) = Self::check_allowed(section, key, value) {
+                self.push(origin, position, format!("`{}`: {}", path, message));
+            }
+        }
+    }
+
+    fn not_a_table(&mut self, origin: &Origin, spans: Option<&Spans>, path: &str) {
+        self.push(
+            origin,
+            spans.and_then(|spans| spans.value(path)),
+            format!("`{}` must be a table", path),
+        );
+    }
+
+    fn push(&mut self, origin: &Origin, position: Option<(usize, usize)>, message: String) {
+        self.issues.push(ConfigIssue {
+            origin: origin.clone(),
+            position,
+            message,
+        });
+    }
+
+    fn unknown_key(
+        &mut self,
+        origin: &Origin,
+        spans: Option<&Spans>,
+        path: &str,
+        key: &str,
+        candidates: &[&str],
+    ) {
+        let mut message = format!("unknown key `{}`", path);
+        if let Some(suggestion) = Self::suggest(key, candidates) {
+            message.push_str(&format!(", did you mean `{}`?", suggestion));
+        }
+
+        self.push(origin, spans.and_then(|spans| spans.key(path)), message);
+    }
+
+    fn section_fields(section: &str) -> Option<&'static [&'static str]> {
+        match section {
+            "fbs_dumper" => Some(FbsDumperConfig::FIELDS),
+            "flatc" => Some(FlatCConfig::FIELDS),
+            "il2cpp_dumper" => Some(Il2CppDumperConfig::FIELDS),
+            "lint" => Some(LintConfig::FIELDS),
+            "postprocess" => Some(PostProcessConfig::FIELDS),
+            _ => None,
+        }
+    }
+
+    fn check_type(section: &str, key: &str, value: &Value) -> Result<(), String> {
+        let mut entry = Table::new();
+        entry.insert(key.to_string(), value.clone());
+        let mut table = Table::new();
+        table.insert(section.to_string(), Value::Table(entry));
+
+        Value::Table(table)
+            .try_into::<Config>()
+            .map(|_| ())
+            .map_err(|err| err.message().to_string())
+    }
+
+    fn check_allowed(section: &str, key: &str, value: &Value) -> Result<(), String> {
+        let Some((_, _, allowed)) = ALLOWED_VALUES
+            .iter()
+            .find(|(s, k, _)| *s == section && *k == key)
+        else {
+            return Ok(());
+        };
+
+        let Some(value) = value.as_str() else {
+            return Ok(());
+        };
+
+        if allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+            return Ok(());
+        }
+
+        let mut message = format!(
+            "unsupported value \"{}\", expected one of {}",
+            value,
+            allowed.join(", ")
+        );
+        if let Some(suggestion) = Self::suggest(value, allowed) {
+            message.push_str(&format!(" (did you mean \"{}\"?)", suggestion));
+        }
+
+        Err(message)
+    }
+
+    pub fn suggest<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+        let input = input.to_lowercase();
+
+        candidates
+            .iter()
+            .map(|candidate| (Self::distance(&input, &candidate.to_lowercase()), *candidate))
+            .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(2))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, candidate)| candidate)
+    }
+
+    fn distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+        for (i, ca) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, cb) in b.iter().enumerate() {
+                let cost = if ca == *cb { 0 } else { 1 };
+                current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+            previous = current;
+        }
+
+        previous[b.len()]
+    }
+}
@@ -0,0 +1,366 @@
+use crate::codegen::decrypt::{
+    Conversion, EncryptedField, ExcelTable, excel_tables, namespace_dir, write_files,
+};
+use crate::codegen::{fill, to_upper_camel_case};
+use crate::schema::{Declaration, Schema};
+
+use eyre::Result;
+use std::path::{Path, PathBuf};
+
+const SERVICE_FILE: &str = "TableEncryptionService.cs";
+
+const SERVICE: &str = r#"// <auto-generated>
+//  automatically generated by bafb, do not modify
+// </auto-generated>
+
+namespace {{NAMESPACE}}
+{
+
+using System;
+using System.Text;
+
+/// <summary>Blue Archive's TableEncryptionService, without any dependencies</summary>
+public static class TableEncryptionService
+{
+  const uint Prime1 = 2654435761U;
+  const uint Prime2 = 2246822519U;
+  const uint Prime3 = 3266489917U;
+  const uint Prime4 = 668265263U;
+  const uint Prime5 = 374761393U;
+
+  static uint Rotl(uint value, int count) => (value << count) | (value >> (32 - count));
+
+  static uint ReadUInt32(byte[] bytes, int index) =>
+    (uint)bytes[index] | (uint)bytes[index + 1] << 8 | (uint)bytes[index + 2] << 16 | (uint)bytes[index + 3] << 24;
+
+  static ulong ReadUInt64(byte[] bytes) => ReadUInt32(bytes, 0) | (ulong)ReadUInt32(bytes, 4) << 32;
+
+  /// <summary>xxHash32 with a seed of 0</summary>
+  public static uint XxHash32(byte[] input)
+  {
+    unchecked
+    {
+      int length = input.Length;
+      int i = 0;
+      uint hash;
+      if (length >= 16)
+      {
+        uint v1 = Prime1 + Prime2, v2 = Prime2, v3 = 0, v4 = 0 - Prime1;
+        for (; i <= length - 16; i += 16)
+        {
+          v1 = Rotl(v1 + ReadUInt32(input, i) * Prime2, 13) * Prime1;
+          v2 = Rotl(v2 + ReadUInt32(input, i + 4) * Prime2, 13) * Prime1;
+          v3 = Rotl(v3 + ReadUInt32(input, i + 8) * Prime2, 13) * Prime1;
+          v4 = Rotl(v4 + ReadUInt32(input, i + 12) * Prime2, 13) * Prime1;
+        }
+        hash = Rotl(v1, 1) + Rotl(v2, 7) + Rotl(v3, 12) + Rotl(v4, 18);
+      }
+      else
+      {
+        hash = Prime5;
+      }
+
+      hash += (uint)length;
+      for (; i <= length - 4; i += 4)
+        hash = Rotl(hash + ReadUInt32(input, i) * Prime3, 17) * Prime4;
+      for (; i < length; i++)
+        hash = Rotl(hash + input[i] * Prime5, 11) * Prime1;
+
+      hash ^= hash >> 15;
+      hash *= Prime2;
+      hash ^= hash >> 13;
+      hash *= Prime3;
+      return hash ^ (hash >> 16);
+    }
+  }
+
+  /// <summary>MT19937 as the game seeds it</summary>
+  sealed class MersenneTwister
+  {
+    readonly uint[] state = new uint[624];
+    int index = 624;
+
+    public MersenneTwister(uint seed)
+    {
+      state[0] = seed;
+      for (int i = 1; i < 624; i++)
+        state[i] = unchecked(1812433253U * (state[i - 1] ^ (state[i - 1] >> 30)) + (uint)i);
+    }
+
+    void Twist()
+    {
+      for (int i = 0; i < 624; i++)
+      {
+        uint y = (state[i] & 0x80000000U) | (state[(i + 1) % 624] & 0x7FFFFFFFU);
+        uint next = state[(i + 397) % 624] ^ (y >> 1);
+        if ((y & 1) != 0)
+          next ^= 0x9908B0DFU;
+        state[i] = next;
+      }
+      index = 0;
+    }
+
+    uint Next()
+    {
+      if (index >= 624)
+        Twist();
+      uint y = state[index++];
+      y ^= y >> 11;
+      y ^= (y << 7) & 0x9D2C5680U;
+      y ^= (y << 15) & 0xEFC60000U;
+      return y ^ (y >> 18);
+    }
+
+    /// <summary>Fills four bytes at a time from 31 bit outputs</summary>
+    public byte[] NextBytes(int length)
+    {
+      var bytes = new byte[length];
+      for (int i = 0; i < length; i += 4)
+      {
+        uint value = Next() >> 1;
+        for (int j = 0; j < 4 && i + j < length; j++)
+          bytes[i + j] = (byte)(value >> (8 * j));
+      }
+      return bytes;
+    }
+  }
+
+  /// <summary>Key the fields of a row type are encrypted with</summary>
+  public static byte[] CreateKey(string name) =>
+    new MersenneTwister(XxHash32(Encoding.UTF8.GetBytes(name))).NextBytes(8);
+
+  /// <summary>Undoes the encryption of a whole table file, name is the table type</summary>
+  public static void Xor(string name, byte[] data)
+  {
+    var stream = new MersenneTwister(XxHash32(Encoding.UTF8.GetBytes(name))).NextBytes(data.Length);
+    for (int i = 0; i < data.Length; i++)
+      data[i] ^= stream[i];
+  }
+
+  public static int Convert(int value, byte[] key) => value ^ unchecked((int)ReadUInt32(key, 0));
+
+  public static uint Convert(uint value, byte[] key) => value ^ ReadUInt32(key, 0);
+
+  public static long Convert(long value, byte[] key) => value ^ unchecked((long)ReadUInt64(key));
+
+  public static ulong Convert(ulong value, byte[] key) => value ^ ReadUInt64(key);
+
+  public static float Convert(float value, byte[] key) => value == 0 ? 0 : Convert((int)value, key) * 0.00001f;
+
+  public static double Convert(double value, byte[] key) => value == 0 ? 0 : Convert((long)value, key) * 0.00001;
+
+  /// <summary>Strings are base64 of UTF-16 text xored with the key, anything else is returned as is</summary>
+  public static string Convert(string value, byte[] key)
+  {
+    if (value == null)
+      return null;
+    byte[] bytes;
+    try
+    {
+      bytes = global::System.Convert.FromBase64String(value);
+    }
+    catch (FormatException)
+    {
+      return value;
+    }
+    if (bytes.Length % 2 != 0)
+      return value;
+    for (int i = 0; i < bytes.Length; i++)
+      bytes[i] ^= key[i % key.Length];
+    return Encoding.Unicode.GetString(bytes);
+  }
+}
+
+}
+"#;
+
+/// Writes `TableEncryptionService.cs` and a `<Table>Loader.cs` per Excel table below `output`
+pub fn generate(schema: &Schema, output: &Path) -> Result<Vec<PathBuf>> {
+    write_files(output, render(schema))
+}
+
+pub fn render(schema: &Schema) -> Vec<(PathBuf, String)> {
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+
+    for excel in excel_tables(schema) {
+        let dir = namespace_dir(&excel.table.namespace);
+        let service = dir.join(SERVICE_FILE);
+        if !files.iter().any(|(path, _)| *path == service) {
+            files.push((
+                service,
+                fill(SERVICE, &[("NAMESPACE", namespace(&excel.table.namespace))]),
+            ));
+        }
+        files.push((
+            dir.join(format!("{}Loader.cs", excel.table.name)),
+            loader(&excel),
+        ));
+    }
+
+    files
+}
+
+/// flatc puts types without a namespace in the global one, the helpers need a named one
+fn namespace(namespace: &str) -> &str {
+    if namespace.is_empty() {
+        "TableEncryption"
+    } else {
+        namespace
+    }
+}
+
+fn qualified(declaration: &Declaration, suffix: &str) -> String {
+    format!("global::{}{}", declaration.qualified_name(), suffix)
+}
+
+fn cast(conversion: Conversion) -> &'static str {
+    match conversion {
+        Conversion::Int => "int",
+        Conversion::UInt => "uint",
+        Conversion::Long => "long",
+        Conversion::ULong => "ulong",
+        Conversion::Float => "float",
+        Conversion::Double => "double",
+        Conversion::String => "string",
+    }
+}
+
+fn statement(field: &EncryptedField) -> String {
+    let property = format!("row.{}", to_upper_camel_case(&field.field.name));
+    let value = |target: &str| match field.enumeration {
+        Some(enumeration) => format!(
+            "({})TableEncryptionService.Convert(({}){}, Key)",
+            qualified(enumeration, ""),
+            cast(field.conversion),
+            target
+        ),
+        None => format!("TableEncryptionService.Convert({}, Key)", target),
+    };
+
+    if field.vector {
+        format!(
+            "    if ({0} != null)\n      for (int i = 0; i < {0}.Count; i++)\n        {0}[i] = {1};\n",
+            property,
+            value(&format!("{}[i]", property))
+        )
+    } else if field.optional() && field.conversion != Conversion::String {
+        format!(
+            "    if ({0}.HasValue)\n      {0} = {1};\n",
+            property,
+            value(&format!("{}.Value", property))
+        )
+    } else {
+        format!("    {} = {};\n", property, value(&property))
+    }
+}
+
+fn loader(excel: &ExcelTable) -> String {
+    let table = &excel.table.name;
+    let statements: String = excel.fields.iter().map(statement).collect();
+    let rows = format!(
+        "    if (table.{0} != null)\n      foreach (var row in table.{0})\n        Decrypt(row);\n",
+        to_upper_camel_case(&excel.list.name)
+    );
+
+    fill(
+        r#"// <auto-generated>
+//  automatically generated by bafb, do not modify
+// </auto-generated>
+
+namespace {{NAMESPACE}}
+{
+
+/// <summary>Decrypts and loads {{TABLE}} as the game stores it</summary>
+public static class {{TABLE}}Loader
+{
+  static readonly byte[] Key = TableEncryptionService.CreateKey("{{KEY}}");
+
+  /// <summary>Decrypts a {{TABLE}} and unpacks it with every row decrypted</summary>
+  public static {{TABLE}}T LoadDecrypted(byte[] bytes)
+  {
+    var data = (byte[])bytes.Clone();
+    TableEncryptionService.Xor("{{TABLE}}", data);
+    var table = {{TABLE}}.GetRootAs{{TABLE}}(new Google.FlatBuffers.ByteBuffer(data)).UnPack();
+{{ROWS}}    return table;
+  }
+
+  /// <summary>Decrypts the fields the game stores encrypted with the {{KEY}} key</summary>
+  public static void Decrypt({{ROW}} row)
+  {
+{{STATEMENTS}}  }
+}
+
+}
+"#,
+        &[
+            ("NAMESPACE", namespace(&excel.table.namespace)),
+            ("TABLE", table),
+            ("KEY", excel.key_name()),
+            ("ROW", &qualified(excel.row, "T")),
+            ("ROWS", &rows),
+            ("STATEMENTS", &statements),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::decrypt::vectors;
+
+    #[test]
+    fn renders_a_loader_per_table() {
+        let files = render(&Schema::parse(vectors::SCHEMA).unwrap());
+        let paths: Vec<&Path> = files.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("FlatData/TableEncryptionService.cs"),
+                Path::new("FlatData/CharacterExcelTableLoader.cs"),
+            ]
+        );
+        assert!(files[0].1.contains("namespace FlatData\n"));
+
+        let loader = &files[1].1;
+        assert!(loader.contains(
+            "static readonly byte[] Key = TableEncryptionService.CreateKey(\"Character\");"
+        ));
+        assert!(loader.contains("TableEncryptionService.Xor(\"CharacterExcelTable\", data);"));
+        assert!(
+            loader.contains("public static void Decrypt(global::FlatData.CharacterExcelT row)")
+        );
+        assert!(loader.contains("      foreach (var row in table.DataList)\n"));
+        for statement in [
+            "    row.Id = TableEncryptionService.Convert(row.Id, Key);\n",
+            "    if (row.Cost.HasValue)\n      row.Cost = TableEncryptionService.Convert(row.Cost.Value, Key);\n",
+            "    row.Name = TableEncryptionService.Convert(row.Name, Key);\n",
+            "    row.Grade = (global::FlatData.Grade)TableEncryptionService.Convert((int)row.Grade, Key);\n",
+            "    if (row.Tags != null)\n      for (int i = 0; i < row.Tags.Count; i++)\n        row.Tags[i] = TableEncryptionService.Convert(row.Tags[i], Key);\n",
+        ] {
+            assert!(loader.contains(statement), "{}\n{}", statement, loader);
+        }
+        assert!(!loader.contains("row.Flag"));
+    }
+
+    #[test]
+    fn names_the_namespace_of_global_tables() {
+        let schema = Schema::parse(
+            "table ItemExcel { Id: long; }\n\
+             table ItemExcelTable { DataList: [ItemExcel]; }\n\
+             table ShopExcel { Id: long; }\n\
+             table ShopExcelTable { DataList: [ShopExcel]; }\n",
+        )
+        .unwrap();
+        let files = render(&schema);
+
+        // One service shared by both tables
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].0, Path::new("TableEncryptionService.cs"));
+        assert!(files[0].1.contains("namespace TableEncryption\n"));
+        assert!(
+            files[1]
+                .1
+                .contains("public static void Decrypt(global::ItemExcelT row)")
+        );
+    }
+}
@@ -0,0 +1,216 @@
+pub mod csharp;
+pub mod python;
+pub mod rust;
+
+use crate::schema::{Declaration, Field, Kind, Scalar, Schema, Type};
+
+use eyre::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix of the table types that hold the rows of an Excel table
+pub const TABLE_SUFFIX: &str = "ExcelTable";
+/// Suffix of the row types, stripped to get the name the field key is derived from
+pub const ROW_SUFFIX: &str = "Excel";
+
+/// How the game stores an encrypted value, one per `TableEncryptionService.Convert` overload
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Conversion {
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+}
+
+impl Conversion {
+    /// Narrower integers and bools are stored as is
+    pub fn of(scalar: Scalar) -> Option<Self> {
+        Some(match scalar {
+            Scalar::Int => Conversion::Int,
+            Scalar::UInt => Conversion::UInt,
+            Scalar::Long => Conversion::Long,
+            Scalar::ULong => Conversion::ULong,
+            Scalar::Float => Conversion::Float,
+            Scalar::Double => Conversion::Double,
+            _ => return None,
+        })
+    }
+}
+
+/// A row field that is decrypted on load
+pub struct EncryptedField<'a> {
+    pub field: &'a Field,
+    pub conversion: Conversion,
+    /// Enum the value is wrapped in, converted through its underlying type
+    pub enumeration: Option<&'a Declaration>,
+    pub vector: bool,
+}
+
+impl EncryptedField<'_> {
+    /// Optional scalars and non-required offsets are nullable in the object API
+    pub fn optional(&self) -> bool {
+        if self.vector || self.conversion == Conversion::String {
+            !self.field.has_attribute("required")
+        } else {
+            self.field.default.as_deref() == Some("null")
+        }
+    }
+}
+
+/// An `*ExcelTable` with its list of `*Excel` rows
+pub struct ExcelTable<'a> {
+    pub table: &'a Declaration,
+    pub list: &'a Field,
+    pub row: &'a Declaration,
+    pub fields: Vec<EncryptedField<'a>>,
+}
+
+impl ExcelTable<'_> {
+    /// Name `TableEncryptionService.CreateKey` is called with for the row fields
+    pub fn key_name(&self) -> &str {
+        self.row
+            .name
+            .strip_suffix(ROW_SUFFIX)
+            .unwrap_or(&self.row.name)
+    }
+}
+
+/// Every table named `*ExcelTable` whose first vector of tables holds its rows
+pub fn excel_tables(schema: &Schema) -> Vec<ExcelTable<'_>> {
+    let mut tables = Vec::new();
+
+    for table in &schema.declarations {
+        if !matches!(table.kind, Kind::Table(_)) || !table.name.ends_with(TABLE_SUFFIX) {
+            continue;
+        }
+
+        let rows = table.fields().iter().find_map(|field| match &field.ty {
+            Type::Vector(element) if !field.has_attribute("deprecated") => match element.as_ref() {
+                Type::Named(name) => schema
+                    .resolve(name, &table.namespace)
+                    .filter(|row| matches!(row.kind, Kind::Table(_)))
+                    .map(|row| (field, row)),
+                _ => None,
+            },
+            _ => None,
+        });
+
+        if let Some((list, row)) = rows {
+            tables.push(ExcelTable {
+                table,
+                list,
+                row,
+                fields: encrypted_fields(schema, row),
+            });
+        }
+    }
+
+    tables
+}
+
+fn encrypted_fields<'a>(schema: &'a Schema, row: &'a Declaration) -> Vec<EncryptedField<'a>> {
+    let mut fields = Vec::new();
+
+    for field in row.fields() {
+        if field.has_attribute("deprecated") {
+            continue;
+        }
+
+        let (element, vector) = match &field.ty {
+            Type::Vector(element) => (element.as_ref(), true),
+            other => (other, false),
+        };
+        let (conversion, enumeration) = match element {
+            Type::Scalar(scalar) => (Conversion::of(*scalar), None),
+            Type::String => (Some(Conversion::String), None),
+            Type::Named(name) => match schema.resolve(name, &row.namespace) {
+                Some(declaration) => match &declaration.kind {
+                    Kind::Enum(underlying, _) => (Conversion::of(*underlying), Some(declaration)),
+                    _ => (None, None),
+                },
+                None => (None, None),
+            },
+            Type::Vector(_) | Type::Array(_, _) => (None, None),
+        };
+
+        if let Some(conversion) = conversion {
+            fields.push(EncryptedField {
+                field,
+                conversion,
+                enumeration,
+                vector,
+            });
+        }
+    }
+
+    fields
+}
+
+/// Directory flatc writes the types of `namespace` to, one level per namespace part
+pub fn namespace_dir(namespace: &str) -> PathBuf {
+    namespace
+        .split('.')
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Writes `files` relative to `output` and returns their paths
+pub fn write_files(output: &Path, files: Vec<(PathBuf, String)>) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+
+    for (relative, content) in files {
+        let path = output.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, content)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Values every language's helpers must reproduce
+#[cfg(test)]
+pub mod vectors {
+    /// Published xxHash32 digests with a seed of 0, the last one covers the 16 byte lanes
+    pub const XXHASH32: &[(&str, u32)] = &[
+        ("", 0x02CC_5D05),
+        ("a", 0x550D_7456),
+        ("abc", 0x32D1_53FF),
+        ("Nobody inspects the spammish repetition", 0xE229_3B2F),
+    ];
+
+    /// First MT19937 output for the reference seed
+    pub const MERSENNE_TWISTER: (u32, u32) = (5489, 3_499_211_612);
+
+    pub const CREATE_KEY: &[(&str, [u8; 8])] = &[
+        (
+            "Character",
+            [0xFE, 0xC3, 0x76, 0x30, 0x62, 0xC0, 0x0A, 0x68],
+        ),
+        ("Scenario", [0xE1, 0x94, 0xBC, 0x0B, 0xD1, 0x79, 0xB7, 0x0E]),
+    ];
+
+    /// Excel table with a field of every conversion, in a namespace and nullable where it can be
+    pub const SCHEMA: &str = "namespace FlatData;\n\
+        enum Grade : int { Low, High }\n\
+        table CharacterExcel {\n\
+          Id: long;\n\
+          Level: int;\n\
+          Cost: uint = null;\n\
+          Hash: ulong;\n\
+          Scale: float;\n\
+          Ratio: double;\n\
+          Name: string;\n\
+          Grade: Grade;\n\
+          Tags: [int];\n\
+          Flag: bool;\n\
+        }\n\
+        table CharacterExcelTable { DataList: [CharacterExcel]; }\n";
+}
@@ -0,0 +1,386 @@
+use crate::codegen::decrypt::{
+    Conversion, EncryptedField, ExcelTable, excel_tables, namespace_dir, write_files,
+};
+use crate::codegen::{fill, to_lower_camel_case};
+use crate::schema::Schema;
+
+use eyre::Result;
+use std::path::{Path, PathBuf};
+
+const SERVICE_MODULE: &str = "TableEncryptionService";
+
+const SERVICE: &str = r#"# automatically generated by bafb, do not modify
+
+"""Blue Archive's TableEncryptionService, without any dependencies"""
+
+import base64
+import binascii
+
+_MASK32 = 0xFFFFFFFF
+_MASK64 = 0xFFFFFFFFFFFFFFFF
+
+_PRIME32_1 = 0x9E3779B1
+_PRIME32_2 = 0x85EBCA77
+_PRIME32_3 = 0xC2B2AE3D
+_PRIME32_4 = 0x27D4EB2F
+_PRIME32_5 = 0x165667B1
+
+
+def _rotl(value, count):
+    return ((value << count) | (value >> (32 - count))) & _MASK32
+
+
+def _read_u32(data, index):
+    return int.from_bytes(data[index:index + 4], "little")
+
+
+def xxhash32(data):
+    """xxHash32 with a seed of 0"""
+    length = len(data)
+    i = 0
+    if length >= 16:
+        lanes = [(_PRIME32_1 + _PRIME32_2) & _MASK32, _PRIME32_2, 0, -_PRIME32_1 & _MASK32]
+        while i <= length - 16:
+            for lane in range(4):
+                word = _read_u32(data, i + lane * 4)
+                lanes[lane] = _rotl((lanes[lane] + word * _PRIME32_2) & _MASK32, 13) * _PRIME32_1 & _MASK32
+            i += 16
+        value = (_rotl(lanes[0], 1) + _rotl(lanes[1], 7) + _rotl(lanes[2], 12) + _rotl(lanes[3], 18)) & _MASK32
+    else:
+        value = _PRIME32_5
+
+    value = (value + length) & _MASK32
+    while i <= length - 4:
+        value = _rotl((value + _read_u32(data, i) * _PRIME32_3) & _MASK32, 17) * _PRIME32_4 & _MASK32
+        i += 4
+    while i < length:
+        value = _rotl((value + data[i] * _PRIME32_5) & _MASK32, 11) * _PRIME32_1 & _MASK32
+        i += 1
+
+    value ^= value >> 15
+    value = value * _PRIME32_2 & _MASK32
+    value ^= value >> 13
+    value = value * _PRIME32_3 & _MASK32
+    return value ^ (value >> 16)
+
+
+class MersenneTwister(object):
+    """MT19937 as the game seeds it"""
+
+    def __init__(self, seed):
+        self.state = [0] * 624
+        self.state[0] = seed & _MASK32
+        for i in range(1, 624):
+            previous = self.state[i - 1]
+            self.state[i] = (1812433253 * (previous ^ (previous >> 30)) + i) & _MASK32
+        self.index = 624
+
+    def _twist(self):
+        state = self.state
+        for i in range(624):
+            y = (state[i] & 0x80000000) | (state[(i + 1) % 624] & 0x7FFFFFFF)
+            following = state[(i + 397) % 624] ^ (y >> 1)
+            if y & 1:
+                following ^= 0x9908B0DF
+            state[i] = following
+        self.index = 0
+
+    def next_u32(self):
+        if self.index >= 624:
+            self._twist()
+        y = self.state[self.index]
+        self.index += 1
+        y ^= y >> 11
+        y ^= (y << 7) & 0x9D2C5680
+        y ^= (y << 15) & 0xEFC60000
+        return y ^ (y >> 18)
+
+    def next_bytes(self, length):
+        """Fills four bytes at a time from 31 bit outputs"""
+        output = bytearray()
+        while len(output) < length:
+            output += (self.next_u32() >> 1).to_bytes(4, "little")
+        return bytes(output[:length])
+
+
+def create_key(name):
+    """Key the fields of a row type are encrypted with"""
+    return MersenneTwister(xxhash32(name.encode("utf-8"))).next_bytes(8)
+
+
+def xor(name, data):
+    """Undoes the encryption of a whole table file, name is the table type"""
+    stream = MersenneTwister(xxhash32(name.encode("utf-8"))).next_bytes(len(data))
+    value = int.from_bytes(data, "little") ^ int.from_bytes(stream, "little")
+    return value.to_bytes(len(data), "little")
+
+
+def _signed(value, bits):
+    return value - (1 << bits) if value >> (bits - 1) else value
+
+
+def convert_int(value, key):
+    return _signed((value ^ int.from_bytes(key[:4], "little")) & _MASK32, 32)
+
+
+def convert_uint(value, key):
+    return (value ^ int.from_bytes(key[:4], "little")) & _MASK32
+
+
+def convert_long(value, key):
+    return _signed((value ^ int.from_bytes(key, "little")) & _MASK64, 64)
+
+
+def convert_ulong(value, key):
+    return (value ^ int.from_bytes(key, "little")) & _MASK64
+
+
+def convert_float(value, key):
+    return 0.0 if value == 0 else convert_int(int(value), key) * 0.00001
+
+
+def convert_double(value, key):
+    return 0.0 if value == 0 else convert_long(int(value), key) * 0.00001
+
+
+def convert_string(value, key):
+    """Strings are base64 of UTF-16 text xored with the key, anything else is returned as is"""
+    if value is None:
+        return None
+    if isinstance(value, bytes):
+        value = value.decode("utf-8")
+    try:
+        data = bytearray(base64.b64decode(value, validate=True))
+    except (binascii.Error, ValueError):
+        return value
+    if len(data) % 2:
+        return value
+    for i in range(len(data)):
+        data[i] ^= key[i % len(key)]
+    try:
+        return data.decode("utf-16-le")
+    except UnicodeDecodeError:
+        return value
+"#;
+
+/// Writes `TableEncryptionService.py` and a `<Table>Loader.py` per Excel table below `output`
+pub fn generate(schema: &Schema, output: &Path) -> Result<Vec<PathBuf>> {
+    write_files(output, render(schema))
+}
+
+pub fn render(schema: &Schema) -> Vec<(PathBuf, String)> {
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+
+    for excel in excel_tables(schema) {
+        let dir = namespace_dir(&excel.table.namespace);
+        let service = dir.join(format!("{}.py", SERVICE_MODULE));
+        if !files.iter().any(|(path, _)| *path == service) {
+            files.push((service, SERVICE.to_string()));
+        }
+        files.push((
+            dir.join(format!("{}Loader.py", excel.table.name)),
+            loader(&excel),
+        ));
+    }
+
+    files
+}
+
+/// Absolute module path of `name` in `namespace`, flatc makes a package per namespace part
+fn module(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn function(conversion: Conversion) -> &'static str {
+    match conversion {
+        Conversion::Int => "convert_int",
+        Conversion::UInt => "convert_uint",
+        Conversion::Long => "convert_long",
+        Conversion::ULong => "convert_ulong",
+        Conversion::Float => "convert_float",
+        Conversion::Double => "convert_double",
+        Conversion::String => "convert_string",
+    }
+}
+
+/// Enums are plain integers in the object API, so they convert like their underlying type
+fn statement(field: &EncryptedField) -> String {
+    let attribute = format!("row.{}", to_lower_camel_case(&field.field.name));
+    let convert = function(field.conversion);
+
+    if field.vector {
+        format!(
+            "        if {0} is not None:\n            {0} = [{1}(value, key) for value in {0}]\n",
+            attribute, convert
+        )
+    } else if field.optional() && field.conversion != Conversion::String {
+        format!(
+            "        if {0} is not None:\n            {0} = {1}({0}, key)\n",
+            attribute, convert
+        )
+    } else {
+        format!("        {0} = {1}({0}, key)\n", attribute, convert)
+    }
+}
+
+fn loader(excel: &ExcelTable) -> String {
+    let namespace = &excel.table.namespace;
+    let mut functions: Vec<&str> = excel
+        .fields
+        .iter()
+        .map(|field| function(field.conversion))
+        .collect();
+    functions.sort_unstable();
+    functions.dedup();
+    functions.extend(["create_key", "xor"]);
+
+    let statements: String = if excel.fields.is_empty() {
+        "        pass\n".to_string()
+    } else {
+        excel.fields.iter().map(statement).collect()
+    };
+    let key = if excel.fields.is_empty() {
+        String::new()
+    } else {
+        format!("        key = {}Loader.key\n", excel.table.name)
+    };
+
+    fill(
+        r#"# automatically generated by bafb, do not modify
+
+from {{TABLE_MODULE}} import {{TABLE}}, {{TABLE}}T
+from {{SERVICE_MODULE}} import {{FUNCTIONS}}
+
+
+class {{TABLE}}Loader(object):
+    """Decrypts and loads {{TABLE}} as the game stores it"""
+
+    key = create_key("{{KEY}}")
+
+    @staticmethod
+    def load_decrypted(data):
+        """Decrypts a {{TABLE}} and unpacks it with every row decrypted"""
+        table = {{TABLE}}T.InitFromObj({{TABLE}}.GetRootAs(xor("{{TABLE}}", data), 0))
+        for row in table.{{LIST}} or []:
+            {{TABLE}}Loader.decrypt(row)
+        return table
+
+    @staticmethod
+    def decrypt(row):
+        """Decrypts the fields the game stores encrypted with the {{KEY}} key"""
+{{KEY_LOCAL}}{{STATEMENTS}}"#,
+        &[
+            ("TABLE_MODULE", &module(namespace, &excel.table.name)),
+            ("TABLE", &excel.table.name),
+            ("SERVICE_MODULE", &module(namespace, SERVICE_MODULE)),
+            ("FUNCTIONS", &functions.join(", ")),
+            ("KEY", excel.key_name()),
+            ("LIST", &to_lower_camel_case(&excel.list.name)),
+            ("KEY_LOCAL", &key),
+            ("STATEMENTS", &statements),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::decrypt::vectors;
+    use std::fs;
+    use std::process::Command;
+
+    fn rendered() -> Vec<(PathBuf, String)> {
+        render(&Schema::parse(vectors::SCHEMA).unwrap())
+    }
+
+    #[test]
+    fn renders_a_loader_per_table() {
+        let files = rendered();
+        let paths: Vec<&Path> = files.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("FlatData/TableEncryptionService.py"),
+                Path::new("FlatData/CharacterExcelTableLoader.py"),
+            ]
+        );
+
+        let loader = &files[1].1;
+        assert!(loader.contains(
+            "from FlatData.TableEncryptionService import convert_double, convert_float, convert_int, convert_long, convert_string, convert_uint, convert_ulong, create_key, xor\n"
+        ));
+        assert!(loader.contains("    key = create_key(\"Character\")\n"));
+        assert!(loader.contains("xor(\"CharacterExcelTable\", data)"));
+        assert!(loader.contains("for row in table.dataList or []:"));
+        for statement in [
+            "        row.id = convert_long(row.id, key)\n",
+            "        if row.cost is not None:\n            row.cost = convert_uint(row.cost, key)\n",
+            "        row.scale = convert_float(row.scale, key)\n",
+            "        row.name = convert_string(row.name, key)\n",
+            "        row.grade = convert_int(row.grade, key)\n",
+            "        if row.tags is not None:\n            row.tags = [convert_int(value, key) for value in row.tags]\n",
+        ] {
+            assert!(loader.contains(statement), "{}\n{}", statement, loader);
+        }
+        assert!(!loader.contains("row.flag"));
+    }
+
+    /// Runs `script` next to the rendered helpers, `None` without a python3 to run it with
+    fn python(script: &str) -> Option<String> {
+        let dir = std::env::temp_dir().join(format!("bafb-decrypt-python-{}", std::process::id()));
+        write_files(&dir, rendered()).unwrap();
+
+        let output = Command::new("python3")
+            .arg("-c")
+            .arg(script)
+            .current_dir(dir.join("FlatData"))
+            .output();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let output = output.ok()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    #[test]
+    fn matches_the_rust_helpers() {
+        let mut script = String::from("from TableEncryptionService import *\n");
+        for (input, _) in vectors::XXHASH32 {
+            script.push_str(&format!("print(xxhash32({:?}.encode()))\n", input));
+        }
+        script.push_str(&format!(
+            "print(MersenneTwister({}).next_u32())\n",
+            vectors::MERSENNE_TWISTER.0
+        ));
+        for (name, _) in vectors::CREATE_KEY {
+            script.push_str(&format!("print(list(create_key({:?})))\n", name));
+        }
+        // The loader has to be valid Python, its flatc imports are not there to run it
+        script.push_str("compile(open('CharacterExcelTableLoader.py').read(), 'loader', 'exec')\n");
+
+        let Some(printed) = python(&script) else {
+            eprintln!("python3 is not available, skipping");
+            return;
+        };
+
+        let mut expected: Vec<String> = vectors::XXHASH32
+            .iter()
+            .map(|(_, digest)| digest.to_string())
+            .collect();
+        expected.push(vectors::MERSENNE_TWISTER.1.to_string());
+        expected.extend(
+            vectors::CREATE_KEY
+                .iter()
+                .map(|(_, key)| format!("{:?}", key)),
+        );
+        assert_eq!(printed.lines().collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::codegen::decrypt::{Conversion, EncryptedField};
+use crate::codegen::fill;
+
+/// File of the `table_encryption` module, placed at the root of the generated modules
+pub const SUPPORT_FILE: &str = "table_encryption_generated.rs";
+
+/// Source of the `table_encryption` module, also compiled into the tests below
+const SUPPORT: &str = include_str!("table_encryption.rs");
+
+pub fn support() -> String {
+    SUPPORT.to_string()
+}
+
+fn function(conversion: Conversion) -> &'static str {
+    match conversion {
+        Conversion::Int => "convert_i32",
+        Conversion::UInt => "convert_u32",
+        Conversion::Long => "convert_i64",
+        Conversion::ULong => "convert_u64",
+        Conversion::Float => "convert_f32",
+        Conversion::Double => "convert_f64",
+        Conversion::String => "convert_string",
+    }
+}
+
+/// Statement decrypting `field` of `self`, `module` is the path of `table_encryption`
+pub fn decrypt_field(
+    field: &EncryptedField,
+    name: &str,
+    module: &str,
+    enumeration: Option<&str>,
+) -> String {
+    let convert = format!("{}::{}", module, function(field.conversion));
+    let value = |target: &str| match (field.conversion, enumeration) {
+        (Conversion::String, _) => format!("{}({}, &key)", convert, target),
+        (_, Some(path)) => format!("{}({}({}.0, &key))", path, convert, target),
+        (_, None) => format!("{}({}, &key)", convert, target),
+    };
+
+    let iterator = match (field.vector, field.optional()) {
+        (false, false) => {
+            return format!(
+                "    self.{} = {};\n",
+                name,
+                value(&format!("self.{}", name))
+            );
+        }
+        (true, true) => format!("self.{}.iter_mut().flatten()", name),
+        _ => format!("self.{}.iter_mut()", name),
+    };
+    let element = match (field.conversion, enumeration) {
+        (Conversion::String, _) | (_, Some(_)) => "v",
+        _ => "*v",
+    };
+    format!(
+        "    for v in {} {{\n      *v = {};\n    }}\n",
+        iterator,
+        value(element)
+    )
+}
+
+/// `decrypt` on the object API type of a row
+pub fn row_impl(object: &str, key_name: &str, module: &str, statements: &str) -> String {
+    if statements.is_empty() {
+        return fill(
+            r#"impl {{OBJECT}} {
+  /// None of the fields are encrypted
+  pub fn decrypt(&mut self) {}
+}
+"#,
+            &[("OBJECT", object)],
+        );
+    }
+
+    fill(
+        r#"impl {{OBJECT}} {
+  /// Decrypts the fields the game stores encrypted with the `{{KEY}}` key
+  pub fn decrypt(&mut self) {
+    let key = {{MODULE}}::create_key("{{KEY}}");
+{{STATEMENTS}}  }
+}
+"#,
+        &[
+            ("OBJECT", object),
+            ("KEY", key_name),
+            ("MODULE", module),
+            ("STATEMENTS", statements),
+        ],
+    )
+}
+
+/// `load_decrypted` on the reader of an Excel table
+pub fn table_impl(table: &str, object: &str, list: &str, required: bool, module: &str) -> String {
+    let rows = if required {
+        format!("table.{}.iter_mut()", list)
+    } else {
+        format!("table.{}.iter_mut().flatten()", list)
+    };
+
+    fill(
+        r#"impl {{TABLE}}<'_> {
+  /// Decrypts a `{{TABLE}}` as the game stores it and unpacks it with every row decrypted
+  pub fn load_decrypted(bytes: &[u8]) -> Result<{{OBJECT}}, flatbuffers::InvalidFlatbuffer> {
+    let mut data = bytes.to_vec();
+    {{MODULE}}::xor("{{TABLE}}", &mut data);
+    let mut table = flatbuffers::root::<{{TABLE}}>(&data)?.unpack();
+    for row in {{ROWS}} {
+      row.decrypt();
+    }
+    Ok(table)
+  }
+}
+"#,
+        &[
+            ("TABLE", table),
+            ("OBJECT", object),
+            ("MODULE", module),
+            ("ROWS", &rows),
+        ],
+    )
+}
+
+#[cfg(test)]
+#[allow(dead_code, clippy::all)]
+#[rustfmt::skip]
+#[path = "table_encryption.rs"]
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use super::support::table_encryption::{MersenneTwister, convert_i32, create_key, xxhash32};
+    use crate::codegen::decrypt::vectors;
+
+    #[test]
+    fn hashes_known_vectors() {
+        for &(input, digest) in vectors::XXHASH32 {
+            assert_eq!(xxhash32(input.as_bytes()), digest, "{:?}", input);
+        }
+
+        let (seed, first) = vectors::MERSENNE_TWISTER;
+        assert_eq!(MersenneTwister::new(seed).next_u32(), first);
+    }
+
+    #[test]
+    fn creates_known_keys() {
+        for &(name, key) in vectors::CREATE_KEY {
+            assert_eq!(create_key(name), key, "{}", name);
+        }
+
+        // Converting twice with the same key gives the value back
+        let key = create_key("Character");
+        assert_eq!(convert_i32(convert_i32(12345, &key), &key), 12345);
+    }
+}
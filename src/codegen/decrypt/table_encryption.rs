@@ -0,0 +1,218 @@
+// automatically generated by bafb, do not modify
+// @generated
+
+/// Blue Archive's `TableEncryptionService`, without any dependencies
+pub mod table_encryption {
+  extern crate alloc;
+  use alloc::string::String;
+  use alloc::vec::Vec;
+
+  const PRIME32_1: u32 = 0x9E37_79B1;
+  const PRIME32_2: u32 = 0x85EB_CA77;
+  const PRIME32_3: u32 = 0xC2B2_AE3D;
+  const PRIME32_4: u32 = 0x27D4_EB2F;
+  const PRIME32_5: u32 = 0x1656_67B1;
+
+  fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+  }
+
+  /// xxHash32 with a seed of 0
+  pub fn xxhash32(input: &[u8]) -> u32 {
+    let mut chunks = input.chunks_exact(16);
+    let mut hash = if input.len() >= 16 {
+      let mut lanes = [
+        PRIME32_1.wrapping_add(PRIME32_2),
+        PRIME32_2,
+        0,
+        0u32.wrapping_sub(PRIME32_1),
+      ];
+      for chunk in &mut chunks {
+        for (lane, word) in lanes.iter_mut().zip(chunk.chunks_exact(4)) {
+          *lane = lane
+            .wrapping_add(read_u32(word).wrapping_mul(PRIME32_2))
+            .rotate_left(13)
+            .wrapping_mul(PRIME32_1);
+        }
+      }
+      lanes[0]
+        .rotate_left(1)
+        .wrapping_add(lanes[1].rotate_left(7))
+        .wrapping_add(lanes[2].rotate_left(12))
+        .wrapping_add(lanes[3].rotate_left(18))
+    } else {
+      PRIME32_5
+    };
+
+    hash = hash.wrapping_add(input.len() as u32);
+    let mut rest = chunks.remainder();
+    while rest.len() >= 4 {
+      hash = hash
+        .wrapping_add(read_u32(rest).wrapping_mul(PRIME32_3))
+        .rotate_left(17)
+        .wrapping_mul(PRIME32_4);
+      rest = &rest[4..];
+    }
+    for &byte in rest {
+      hash = hash
+        .wrapping_add(u32::from(byte).wrapping_mul(PRIME32_5))
+        .rotate_left(11)
+        .wrapping_mul(PRIME32_1);
+    }
+
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(PRIME32_2);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(PRIME32_3);
+    hash ^ (hash >> 16)
+  }
+
+  /// MT19937 as the game seeds it
+  pub struct MersenneTwister {
+    state: [u32; 624],
+    index: usize,
+  }
+
+  impl MersenneTwister {
+    pub fn new(seed: u32) -> Self {
+      let mut state = [0u32; 624];
+      state[0] = seed;
+      for i in 1..624 {
+        state[i] = 1_812_433_253u32
+          .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+          .wrapping_add(i as u32);
+      }
+      Self { state, index: 624 }
+    }
+
+    fn twist(&mut self) {
+      for i in 0..624 {
+        let y = (self.state[i] & 0x8000_0000) | (self.state[(i + 1) % 624] & 0x7FFF_FFFF);
+        let mut next = self.state[(i + 397) % 624] ^ (y >> 1);
+        if y & 1 != 0 {
+          next ^= 0x9908_B0DF;
+        }
+        self.state[i] = next;
+      }
+      self.index = 0;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+      if self.index >= 624 {
+        self.twist();
+      }
+      let mut y = self.state[self.index];
+      self.index += 1;
+      y ^= y >> 11;
+      y ^= (y << 7) & 0x9D2C_5680;
+      y ^= (y << 15) & 0xEFC6_0000;
+      y ^ (y >> 18)
+    }
+
+    /// Fills four bytes at a time from 31 bit outputs
+    pub fn next_bytes(&mut self, length: usize) -> Vec<u8> {
+      let mut bytes = Vec::with_capacity(length + 3);
+      while bytes.len() < length {
+        bytes.extend_from_slice(&(self.next_u32() >> 1).to_le_bytes());
+      }
+      bytes.truncate(length);
+      bytes
+    }
+  }
+
+  /// Key the fields of a row type are encrypted with
+  pub fn create_key(name: &str) -> [u8; 8] {
+    let bytes = MersenneTwister::new(xxhash32(name.as_bytes())).next_bytes(8);
+    let mut key = [0u8; 8];
+    key.copy_from_slice(&bytes);
+    key
+  }
+
+  /// Undoes the encryption of a whole table file, `name` is the table type
+  pub fn xor(name: &str, data: &mut [u8]) {
+    let stream = MersenneTwister::new(xxhash32(name.as_bytes())).next_bytes(data.len());
+    for (byte, key) in data.iter_mut().zip(stream) {
+      *byte ^= key;
+    }
+  }
+
+  pub fn convert_i32(value: i32, key: &[u8; 8]) -> i32 {
+    value ^ read_u32(key) as i32
+  }
+
+  pub fn convert_u32(value: u32, key: &[u8; 8]) -> u32 {
+    value ^ read_u32(key)
+  }
+
+  pub fn convert_i64(value: i64, key: &[u8; 8]) -> i64 {
+    value ^ i64::from_le_bytes(*key)
+  }
+
+  pub fn convert_u64(value: u64, key: &[u8; 8]) -> u64 {
+    value ^ u64::from_le_bytes(*key)
+  }
+
+  pub fn convert_f32(value: f32, key: &[u8; 8]) -> f32 {
+    if value == 0.0 {
+      return 0.0;
+    }
+    convert_i32(value as i32, key) as f32 * 0.00001
+  }
+
+  pub fn convert_f64(value: f64, key: &[u8; 8]) -> f64 {
+    if value == 0.0 {
+      return 0.0;
+    }
+    convert_i64(value as i64, key) as f64 * 0.00001
+  }
+
+  fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    fn sextet(c: u8) -> Option<u32> {
+      Some(match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+      } as u32)
+    }
+
+    let input = input.as_bytes();
+    if input.len() % 4 != 0 {
+      return None;
+    }
+    let mut output = Vec::with_capacity(input.len() / 4 * 3);
+    for chunk in input.chunks_exact(4) {
+      let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+      if padding > 2 {
+        return None;
+      }
+      let mut group = 0u32;
+      for &c in &chunk[..4 - padding] {
+        group = (group << 6) | sextet(c)?;
+      }
+      group <<= 6 * padding as u32;
+      output.extend_from_slice(&group.to_be_bytes()[1..4 - padding]);
+    }
+    Some(output)
+  }
+
+  /// Strings are base64 of UTF-16 text xored with the key, anything else is returned as is
+  pub fn convert_string(value: &str, key: &[u8; 8]) -> String {
+    let Some(mut bytes) = base64_decode(value) else {
+      return String::from(value);
+    };
+    if bytes.len() % 2 != 0 {
+      return String::from(value);
+    }
+    for (i, byte) in bytes.iter_mut().enumerate() {
+      *byte ^= key[i % key.len()];
+    }
+    let units: Vec<u16> = bytes
+      .chunks_exact(2)
+      .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+      .collect();
+    String::from_utf16(&units).unwrap_or_else(|_| String::from(value))
+  }
+}
@@ -0,0 +1,78 @@
+pub mod decrypt;
+pub mod rust;
+
+/// Replaces every `{{KEY}}` in `template`, like the code writer flatc's generators use
+pub fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut output = template.to_string();
+    for (key, value) in values {
+        output = output.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    output
+}
+
+/// `CamelCase` to `snake_case` with the same rules as flatc
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut output = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if i == 0 {
+            output.push(c.to_ascii_lowercase());
+        } else if c == '_' {
+            output.push('_');
+        } else if !c.is_ascii_lowercase() {
+            let previous = chars[i - 1];
+            if previous.is_ascii_lowercase() || (previous.is_ascii_digit() && !c.is_ascii_digit()) {
+                output.push('_');
+            }
+            output.push(c.to_ascii_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+/// `CamelCase` to `SCREAMING_SNAKE_CASE`
+pub fn to_screaming_snake_case(name: &str) -> String {
+    to_snake_case(name).to_ascii_uppercase()
+}
+
+/// flatc's `ToCamelCase`, drops underscores and uppercases the letter after them
+fn to_camel_case(name: &str, upper: bool) -> String {
+    let mut output = String::new();
+    let mut capitalize = false;
+
+    for (i, c) in name.chars().enumerate() {
+        if i == 0 {
+            output.push(if upper {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            });
+        } else if c == '_' && !capitalize {
+            capitalize = true;
+        } else if capitalize {
+            output.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            output.push(c);
+        }
+    }
+    if capitalize {
+        output.push('_');
+    }
+
+    output
+}
+
+/// `snake_case` or `CamelCase` to `UpperCamelCase`
+pub fn to_upper_camel_case(name: &str) -> String {
+    to_camel_case(name, true)
+}
+
+/// `snake_case` or `CamelCase` to `lowerCamelCase`
+pub fn to_lower_camel_case(name: &str) -> String {
+    to_camel_case(name, false)
+}
@@ -0,0 +1,2371 @@
+use crate::codegen::decrypt::{self, ExcelTable};
+use crate::codegen::{fill, to_screaming_snake_case, to_snake_case};
+use crate::helpers::config::MODULE_ROOT_FILE;
+use crate::schema::parser::parse_integer;
+use crate::schema::{Declaration, EnumValue, Field, Kind, Scalar, Schema, Type};
+use crate::wrappers::flatc::FlatCOptions;
+
+use eyre::{Context, Result, eyre};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FILE_HEADER: &str = "\
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+";
+
+const SERDE_HEADER: &str = "\
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+";
+
+const FILE_IMPORTS: &str = "\
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+";
+
+const MODULE_ROOT_HEADER: &str = "\
+// Automatically generated by the Flatbuffers compiler. Do not modify.
+// @generated
+";
+
+const SAFETY: &str = "\
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+";
+
+/// Names flatc suffixes with `_` because they clash with Rust or the generated code
+pub const KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "pub",
+    "ref",
+    "return",
+    "Self",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "unsafe",
+    "use",
+    "where",
+    "while",
+    "abstract",
+    "alignof",
+    "become",
+    "box",
+    "do",
+    "final",
+    "macro",
+    "offsetof",
+    "override",
+    "priv",
+    "proc",
+    "pure",
+    "sizeof",
+    "typeof",
+    "unsized",
+    "virtual",
+    "yield",
+    "std",
+    "usize",
+    "isize",
+    "u8",
+    "i8",
+    "u16",
+    "i16",
+    "u32",
+    "i32",
+    "u64",
+    "i64",
+    "u128",
+    "i128",
+    "f32",
+    "f64",
+    "follow",
+    "push",
+    "to_little_endian",
+    "from_little_endian",
+    "ENUM_MAX",
+    "ENUM_MIN",
+    "ENUM_VALUES",
+];
+
+/// The flatc options the native backend understands
+#[derive(Debug, Clone)]
+pub struct RustOptions {
+    pub object_api: bool,
+    pub serialize: bool,
+    pub module_root_file: bool,
+    pub object_prefix: String,
+    pub object_suffix: String,
+    /// Emit `load_decrypted` on Excel tables, see `--decrypt-helpers`
+    pub decrypt_helpers: bool,
+}
+
+impl From<&FlatCOptions> for RustOptions {
+    fn from(options: &FlatCOptions) -> Self {
+        Self {
+            object_api: options.gen_object_api,
+            serialize: options.rust_serialize,
+            module_root_file: options.rust_module_root_file,
+            object_prefix: options.object_prefix.clone().unwrap_or_default(),
+            object_suffix: options
+                .object_suffix
+                .clone()
+                .unwrap_or_else(|| "T".to_string()),
+            decrypt_helpers: false,
+        }
+    }
+}
+
+/// A table field or vector element type with its references resolved
+#[derive(Clone)]
+enum FieldType<'a> {
+    Scalar(Scalar),
+    Enum(&'a Declaration, Scalar),
+    Struct(&'a Declaration),
+    Table(&'a Declaration),
+    Union(&'a Declaration),
+    String,
+    Vector(Box<FieldType<'a>>),
+}
+
+impl FieldType<'_> {
+    fn is_scalar(&self) -> bool {
+        matches!(self, FieldType::Scalar(_) | FieldType::Enum(_, _))
+    }
+
+    /// Size flatc sorts table fields by when building
+    fn size(&self) -> usize {
+        match self {
+            FieldType::Scalar(scalar) | FieldType::Enum(_, scalar) => scalar.size(),
+            _ => 4,
+        }
+    }
+}
+
+/// A struct field placed at its byte offset
+struct StructMember<'a> {
+    field: &'a Field,
+    ty: FieldType<'a>,
+    offset: usize,
+}
+
+struct StructLayout<'a> {
+    members: Vec<StructMember<'a>>,
+    size: usize,
+    alignment: usize,
+}
+
+/// Generates the Rust code `flatc --rust` would, without the flatc binary
+pub struct RustGenerator<'a> {
+    schema: &'a Schema,
+    options: RustOptions,
+    excel_tables: Vec<ExcelTable<'a>>,
+}
+
+impl<'a> RustGenerator<'a> {
+    pub fn new(schema: &'a Schema, options: RustOptions) -> Result<Self> {
+        schema.check()?;
+
+        for declaration in &schema.declarations {
+            if declaration.has_attribute("bit_flags") {
+                return Err(eyre!(
+                    "bit_flags enum {} is not supported by the native backend, use --backend flatc",
+                    declaration.qualified_name()
+                ));
+            }
+            for field in declaration.fields() {
+                if matches!(field.ty, Type::Array(_, _)) {
+                    return Err(eyre!(
+                        "Fixed length array {}.{} is not supported by the native backend, use --backend flatc",
+                        declaration.qualified_name(),
+                        field.name
+                    ));
+                }
+            }
+        }
+
+        let excel_tables = if options.decrypt_helpers {
+            if !options.object_api {
+                return Err(eyre!(
+                    "Decrypt helpers unpack tables and need gen_object_api"
+                ));
+            }
+            decrypt::excel_tables(schema)
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            schema,
+            options,
+            excel_tables,
+        })
+    }
+
+    /// Writes every generated file below `output` and returns their paths
+    pub fn generate(&self, output: &Path) -> Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+
+        for (relative, content) in self.render()? {
+            let path = output.join(&relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, content)
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+
+    /// Every generated file relative to the output directory, in generation order
+    pub fn render(&self) -> Result<Vec<(PathBuf, String)>> {
+        // flatc writes enums and unions before structs and tables
+        let (enums, objects): (Vec<&Declaration>, Vec<&Declaration>) =
+            self.schema.declarations.iter().partition(|declaration| {
+                matches!(declaration.kind, Kind::Enum(_, _) | Kind::Union(_))
+            });
+
+        let mut files = Vec::new();
+        for declaration in enums.into_iter().chain(objects) {
+            let content = match &declaration.kind {
+                Kind::Enum(underlying, values) => {
+                    self.enumeration(declaration, *underlying, values)
+                }
+                Kind::Union(_) => self.union(declaration)?,
+                Kind::Struct(_) => self.structure(declaration)?,
+                Kind::Table(_) => self.table(declaration)?,
+            };
+
+            let mut path: PathBuf = Self::namespace_modules(&declaration.namespace)
+                .iter()
+                .collect();
+            path.push(format!("{}_generated.rs", to_snake_case(&declaration.name)));
+            files.push((path, format!("{}{}", self.file_header(), content)));
+        }
+
+        if self.options.decrypt_helpers {
+            files.push((
+                PathBuf::from(decrypt::rust::SUPPORT_FILE),
+                decrypt::rust::support(),
+            ));
+        }
+
+        if self.options.module_root_file {
+            let root = self.module_root(&files);
+            files.push((PathBuf::from(MODULE_ROOT_FILE), root));
+        }
+
+        Ok(files)
+    }
+
+    fn file_header(&self) -> String {
+        if self.options.serialize {
+            format!("{}{}{}", FILE_HEADER, SERDE_HEADER, FILE_IMPORTS)
+        } else {
+            format!("{}{}", FILE_HEADER, FILE_IMPORTS)
+        }
+    }
+
+    fn module_root(&self, files: &[(PathBuf, String)]) -> String {
+        #[derive(Default)]
+        struct Module {
+            children: BTreeMap<String, Module>,
+            files: Vec<String>,
+        }
+
+        fn render(module: &Module, depth: usize, output: &mut String) {
+            let indent = "  ".repeat(depth);
+            for (name, child) in &module.children {
+                output.push_str(&format!("{}pub mod {} {{\n", indent, name));
+                output.push_str(&format!("{}  use super::*;\n", indent));
+                render(child, depth + 1, output);
+                output.push_str(&format!("{}}} // {}\n", indent, name));
+            }
+            for file in &module.files {
+                output.push_str(&format!("{}mod {};\n", indent, file));
+                output.push_str(&format!("{}pub use self::{}::*;\n", indent, file));
+            }
+        }
+
+        let mut root = Module::default();
+        for (path, _) in files {
+            let mut module = &mut root;
+            if let Some(parent) = path.parent() {
+                for component in parent.iter() {
+                    module = module
+                        .children
+                        .entry(component.to_string_lossy().into_owned())
+                        .or_default();
+                }
+            }
+            if let Some(stem) = path.file_stem() {
+                module.files.push(stem.to_string_lossy().into_owned());
+            }
+        }
+
+        let mut output = MODULE_ROOT_HEADER.to_string();
+        render(&root, 0, &mut output);
+        output
+    }
+
+    fn namespace_modules(namespace: &str) -> Vec<String> {
+        namespace
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .map(to_snake_case)
+            .collect()
+    }
+
+    fn escape(name: &str) -> String {
+        if KEYWORDS.contains(&name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Binding names equal to a type are parsed as patterns, so those get an extra `_`
+    fn local_name(&self, name: &str) -> String {
+        let name = Self::escape(name);
+        if self
+            .schema
+            .declarations
+            .iter()
+            .any(|declaration| declaration.name == name)
+        {
+            format!("{}_", name)
+        } else {
+            name
+        }
+    }
+
+    /// A struct literal field, shortened when the local has the field's name
+    fn initializer(name: &str, local: &str) -> String {
+        if name == local {
+            name.to_string()
+        } else {
+            format!("{}: {}", name, local)
+        }
+    }
+
+    fn doc(lines: &[String], indent: &str) -> String {
+        lines
+            .iter()
+            .map(|line| format!("{}///{}\n", indent, line))
+            .collect()
+    }
+
+    /// Path of `target` as seen from code generated for `namespace`
+    fn type_path(&self, target: &Declaration, namespace: &str) -> String {
+        let name = Self::escape(&target.name);
+        if target.namespace == namespace {
+            return name;
+        }
+
+        // Generated files sit one module below their namespace
+        let depth = Self::namespace_modules(namespace).len() + 1;
+        let mut path = "super::".repeat(depth);
+        for module in Self::namespace_modules(&target.namespace) {
+            path.push_str(&module);
+            path.push_str("::");
+        }
+        path.push_str(&name);
+        path
+    }
+
+    fn object_name(&self, name: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.options.object_prefix, name, self.options.object_suffix
+        )
+    }
+
+    fn object_path(&self, target: &Declaration, namespace: &str) -> String {
+        let path = self.type_path(target, namespace);
+        match path.rsplit_once("::") {
+            Some((prefix, name)) => format!("{}::{}", prefix, self.object_name(name)),
+            None => self.object_name(&path),
+        }
+    }
+
+    fn resolve_type(&self, ty: &Type, namespace: &str) -> Result<FieldType<'a>> {
+        Ok(match ty {
+            Type::Scalar(scalar) => FieldType::Scalar(*scalar),
+            Type::String => FieldType::String,
+            Type::Vector(element) => {
+                FieldType::Vector(Box::new(self.resolve_type(element, namespace)?))
+            }
+            Type::Array(_, _) => return Err(eyre!("Fixed length arrays are not supported")),
+            Type::Named(name) => {
+                let target = self
+                    .schema
+                    .resolve(name, namespace)
+                    .ok_or_else(|| eyre!("Unknown type `{}`", name))?;
+                match &target.kind {
+                    Kind::Enum(underlying, _) => FieldType::Enum(target, *underlying),
+                    Kind::Struct(_) => FieldType::Struct(target),
+                    Kind::Table(_) => FieldType::Table(target),
+                    Kind::Union(_) => FieldType::Union(target),
+                }
+            }
+        })
+    }
+
+    fn scalar_type(scalar: Scalar) -> &'static str {
+        match scalar {
+            Scalar::Bool => "bool",
+            Scalar::Byte => "i8",
+            Scalar::UByte => "u8",
+            Scalar::Short => "i16",
+            Scalar::UShort => "u16",
+            Scalar::Int => "i32",
+            Scalar::UInt => "u32",
+            Scalar::Long => "i64",
+            Scalar::ULong => "u64",
+            Scalar::Float => "f32",
+            Scalar::Double => "f64",
+        }
+    }
+
+    /// Type a value of `ty` is stored as inside a vector, with `lifetime` on borrowed types
+    fn vector_element(&self, ty: &FieldType, namespace: &str, lifetime: &str) -> String {
+        match ty {
+            FieldType::Scalar(scalar) => Self::scalar_type(*scalar).to_string(),
+            FieldType::Enum(target, _) | FieldType::Struct(target) => {
+                self.type_path(target, namespace)
+            }
+            FieldType::Table(target) => format!(
+                "flatbuffers::ForwardsUOffset<{}<{}>>",
+                self.type_path(target, namespace),
+                lifetime
+            ),
+            FieldType::String => format!("flatbuffers::ForwardsUOffset<&{} str>", lifetime),
+            FieldType::Union(_) | FieldType::Vector(_) => unreachable!("rejected by Schema::check"),
+        }
+    }
+
+    /// Type a table accessor returns, before wrapping optional fields in `Option`
+    fn reader_type(&self, ty: &FieldType, namespace: &str) -> String {
+        match ty {
+            FieldType::Scalar(scalar) => Self::scalar_type(*scalar).to_string(),
+            FieldType::Enum(target, _) => self.type_path(target, namespace),
+            FieldType::Struct(target) => format!("&'a {}", self.type_path(target, namespace)),
+            FieldType::Table(target) => format!("{}<'a>", self.type_path(target, namespace)),
+            FieldType::Union(_) => "flatbuffers::Table<'a>".to_string(),
+            FieldType::String => "&'a str".to_string(),
+            FieldType::Vector(element) => format!(
+                "flatbuffers::Vector<'a, {}>",
+                self.vector_element(element, namespace, "'a")
+            ),
+        }
+    }
+
+    /// Type parameter of `Table::get` for a field
+    fn follow_type(&self, ty: &FieldType, namespace: &str) -> String {
+        match ty {
+            FieldType::Scalar(_) | FieldType::Enum(_, _) => self.reader_type(ty, namespace),
+            FieldType::Struct(target) => self.type_path(target, namespace),
+            FieldType::Table(target) => {
+                format!(
+                    "flatbuffers::ForwardsUOffset<{}>",
+                    self.type_path(target, namespace)
+                )
+            }
+            FieldType::String => "flatbuffers::ForwardsUOffset<&str>".to_string(),
+            _ => format!(
+                "flatbuffers::ForwardsUOffset<{}>",
+                self.reader_type(ty, namespace)
+            ),
+        }
+    }
+
+    fn verifier_type(&self, ty: &FieldType, namespace: &str) -> String {
+        match ty {
+            FieldType::Vector(element) => format!(
+                "flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, {}>>",
+                self.vector_element(element, namespace, "'_")
+                    .replace("<'_>", "")
+            ),
+            _ => self.follow_type(ty, namespace),
+        }
+    }
+
+    /// Type of an offset field in `Args` and the builder, borrowed for `lifetime`
+    fn offset_type(&self, ty: &FieldType, namespace: &str, lifetime: &str) -> String {
+        match ty {
+            FieldType::Table(target) => format!(
+                "flatbuffers::WIPOffset<{}<{}>>",
+                self.type_path(target, namespace),
+                lifetime
+            ),
+            FieldType::String => format!("flatbuffers::WIPOffset<&{} str>", lifetime),
+            FieldType::Vector(element) => format!(
+                "flatbuffers::WIPOffset<flatbuffers::Vector<{}, {}>>",
+                lifetime,
+                self.vector_element(element, namespace, lifetime)
+            ),
+            FieldType::Union(_) => {
+                "flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>".to_string()
+            }
+            _ => unreachable!("not an offset type"),
+        }
+    }
+
+    fn object_type(&self, ty: &FieldType, namespace: &str) -> String {
+        match ty {
+            FieldType::Scalar(_) | FieldType::Enum(_, _) => self.reader_type(ty, namespace),
+            FieldType::Struct(target) => self.object_path(target, namespace),
+            FieldType::Table(target) => format!("Box<{}>", self.object_path(target, namespace)),
+            FieldType::Union(target) => self.object_path(target, namespace),
+            FieldType::String => "String".to_string(),
+            FieldType::Vector(element) => {
+                let element = match element.as_ref() {
+                    FieldType::Table(target) => self.object_path(target, namespace),
+                    other => self.object_type(other, namespace),
+                };
+                format!("Vec<{}>", element)
+            }
+        }
+    }
+
+    fn enum_value_path(&self, target: &Declaration, value: i64, namespace: &str) -> String {
+        let path = self.type_path(target, namespace);
+        let values: &[EnumValue] = match &target.kind {
+            Kind::Enum(_, values) => values,
+            _ => &[],
+        };
+
+        match values.iter().find(|candidate| candidate.value == value) {
+            Some(found) => format!("{}::{}", path, Self::escape(&found.name)),
+            None => format!("{}({})", path, value),
+        }
+    }
+
+    /// Default of a scalar field as a Rust expression, `None` for optional scalars
+    fn default_value(
+        &self,
+        owner: &Declaration,
+        field: &Field,
+        ty: &FieldType,
+    ) -> Result<Option<String>> {
+        let raw = field.default.as_deref();
+        if raw == Some("null") {
+            return Ok(None);
+        }
+        let invalid = || {
+            eyre!(
+                "Invalid default `{}` for {}.{}",
+                raw.unwrap_or_default(),
+                owner.qualified_name(),
+                field.name
+            )
+        };
+
+        Ok(Some(match ty {
+            FieldType::Scalar(Scalar::Bool) => match raw.unwrap_or("false") {
+                "true" | "1" => "true".to_string(),
+                "false" | "0" => "false".to_string(),
+                _ => return Err(invalid()),
+            },
+            FieldType::Scalar(scalar @ (Scalar::Float | Scalar::Double)) => {
+                let rust = Self::scalar_type(*scalar);
+                match raw.unwrap_or("0").trim_start_matches('+') {
+                    "inf" | "infinity" => format!("{}::INFINITY", rust),
+                    "-inf" | "-infinity" => format!("{}::NEG_INFINITY", rust),
+                    "nan" | "-nan" => format!("{}::NAN", rust),
+                    number => format!("{:?}", number.parse::<f64>().map_err(|_| invalid())?),
+                }
+            }
+            FieldType::Scalar(scalar) => {
+                let value = parse_integer(raw.unwrap_or("0")).ok_or_else(invalid)?;
+                match scalar {
+                    Scalar::ULong => (value as u64).to_string(),
+                    _ => value.to_string(),
+                }
+            }
+            FieldType::Enum(target, _) => {
+                let value = match raw {
+                    None => 0,
+                    Some(raw) => match parse_integer(raw) {
+                        Some(value) => value,
+                        None => {
+                            let name = raw.rsplit('.').next().unwrap_or(raw);
+                            let Kind::Enum(_, values) = &target.kind else {
+                                return Err(invalid());
+                            };
+                            values
+                                .iter()
+                                .find(|value| value.name == name)
+                                .map(|value| value.value)
+                                .ok_or_else(invalid)?
+                        }
+                    },
+                };
+                self.enum_value_path(target, value, &owner.namespace)
+            }
+            _ => return Err(invalid()),
+        }))
+    }
+
+    fn enumeration(
+        &self,
+        declaration: &Declaration,
+        underlying: Scalar,
+        values: &[EnumValue],
+    ) -> String {
+        let name = Self::escape(&declaration.name);
+        let repr = Self::scalar_type(underlying);
+        let constant = to_screaming_snake_case(&declaration.name);
+        let mut code = String::new();
+
+        let min = values
+            .iter()
+            .map(|value| value.value)
+            .min()
+            .unwrap_or_default();
+        let max = values
+            .iter()
+            .map(|value| value.value)
+            .max()
+            .unwrap_or_default();
+        let (min, max) = match underlying {
+            Scalar::ULong => ((min as u64).to_string(), (max as u64).to_string()),
+            _ => (min.to_string(), max.to_string()),
+        };
+        let variants: Vec<String> = values
+            .iter()
+            .map(|value| Self::escape(&value.name))
+            .collect();
+
+        let deprecated = "#[deprecated(since = \"2.0.0\", note = \"Use associated constants instead. This will no longer be generated in 2021.\")]\n";
+        code.push_str(deprecated);
+        let _ = writeln!(code, "pub const ENUM_MIN_{}: {} = {};", constant, repr, min);
+        code.push_str(deprecated);
+        let _ = writeln!(code, "pub const ENUM_MAX_{}: {} = {};", constant, repr, max);
+        code.push_str(deprecated);
+        code.push_str("#[allow(non_camel_case_types)]\n");
+        let _ = writeln!(
+            code,
+            "pub const ENUM_VALUES_{}: [{}; {}] = [",
+            constant,
+            name,
+            values.len()
+        );
+        for variant in &variants {
+            let _ = writeln!(code, "  {}::{},", name, variant);
+        }
+        code.push_str("];\n\n");
+
+        code.push_str(&Self::doc(&declaration.doc, ""));
+        code.push_str("#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]\n");
+        code.push_str("#[repr(transparent)]\n");
+        let _ = writeln!(code, "pub struct {}(pub {});", name, repr);
+        code.push_str("#[allow(non_upper_case_globals)]\n");
+        let _ = writeln!(code, "impl {} {{", name);
+        for (value, variant) in values.iter().zip(&variants) {
+            code.push_str(&Self::doc(&value.doc, "  "));
+            let number = match underlying {
+                Scalar::ULong => (value.value as u64).to_string(),
+                _ => value.value.to_string(),
+            };
+            let _ = writeln!(code, "  pub const {}: Self = Self({});", variant, number);
+        }
+        code.push('\n');
+        let _ = writeln!(code, "  pub const ENUM_MIN: {} = {};", repr, min);
+        let _ = writeln!(code, "  pub const ENUM_MAX: {} = {};", repr, max);
+        code.push_str("  pub const ENUM_VALUES: &'static [Self] = &[\n");
+        for variant in &variants {
+            let _ = writeln!(code, "    Self::{},", variant);
+        }
+        code.push_str("  ];\n");
+        code.push_str("  /// Returns the variant's name or \"\" if unknown.\n");
+        code.push_str("  pub fn variant_name(self) -> Option<&'static str> {\n");
+        code.push_str("    match self {\n");
+        for (value, variant) in values.iter().zip(&variants) {
+            let _ = writeln!(code, "      Self::{} => Some(\"{}\"),", variant, value.name);
+        }
+        code.push_str("      _ => None,\n");
+        code.push_str("    }\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+
+        code.push_str(&fill(
+            r#"impl ::core::fmt::Debug for {{NAME}} {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+"#,
+            &[("NAME", &name)],
+        ));
+
+        if self.options.serialize {
+            code.push_str(&fill(
+                r#"impl Serialize for {{NAME}} {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_unit_variant("{{NAME}}", self.0 as u32, self.variant_name().unwrap())
+  }
+}
+"#,
+                &[("NAME", &name)],
+            ));
+        }
+
+        code.push_str(&fill(
+            r#"impl<'a> flatbuffers::Follow<'a> for {{NAME}} {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = unsafe { flatbuffers::read_scalar_at::<{{REPR}}>(buf, loc) };
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for {{NAME}} {
+    type Output = {{NAME}};
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        unsafe { flatbuffers::emplace_scalar::<{{REPR}}>(dst, self.0) };
+    }
+}
+
+impl flatbuffers::EndianScalar for {{NAME}} {
+  type Scalar = {{REPR}};
+  #[inline]
+  fn to_little_endian(self) -> {{REPR}} {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: {{REPR}}) -> Self {
+    let b = {{REPR}}::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for {{NAME}} {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    {{REPR}}::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for {{NAME}} {}
+"#,
+            &[("NAME", &name), ("REPR", repr)],
+        ));
+
+        code
+    }
+
+    fn union(&self, declaration: &Declaration) -> Result<String> {
+        let Kind::Union(variants) = &declaration.kind else {
+            unreachable!("not a union");
+        };
+
+        let mut values = vec![EnumValue {
+            name: "NONE".to_string(),
+            value: 0,
+            explicit: true,
+            doc: Vec::new(),
+        }];
+        values.extend(variants.iter().map(|variant| EnumValue {
+            name: variant.name.clone(),
+            value: variant.value,
+            explicit: variant.explicit,
+            doc: variant.doc.clone(),
+        }));
+
+        let name = Self::escape(&declaration.name);
+        let mut code = self.enumeration(declaration, Scalar::UByte, &values);
+        let _ = writeln!(code, "pub struct {}UnionTableOffset {{}}", name);
+        code.push('\n');
+
+        if !self.options.object_api {
+            return Ok(code);
+        }
+
+        let object = self.object_name(&declaration.name);
+        let mut members = Vec::new();
+        for variant in variants {
+            let target = self
+                .schema
+                .resolve(&variant.ty, &declaration.namespace)
+                .ok_or_else(|| eyre!("Unknown type `{}`", variant.ty))?;
+            members.push((
+                Self::escape(&variant.name),
+                to_snake_case(&variant.name),
+                self.object_path(target, &declaration.namespace),
+            ));
+        }
+
+        code.push_str("#[allow(clippy::upper_case_acronyms)]\n");
+        code.push_str("#[non_exhaustive]\n");
+        code.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+        let _ = writeln!(code, "pub enum {} {{", object);
+        code.push_str("  NONE,\n");
+        for (variant, _, target) in &members {
+            let _ = writeln!(code, "  {}(Box<{}>),", variant, target);
+        }
+        code.push_str("}\n");
+        let _ = writeln!(code, "impl Default for {} {{", object);
+        code.push_str("  fn default() -> Self {\n");
+        code.push_str("    Self::NONE\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+        let _ = writeln!(code, "impl {} {{", object);
+        let _ = writeln!(
+            code,
+            "  pub fn {}_type(&self) -> {} {{",
+            to_snake_case(&declaration.name),
+            name
+        );
+        code.push_str("    match self {\n");
+        let _ = writeln!(code, "      Self::NONE => {}::NONE,", name);
+        for (variant, _, _) in &members {
+            let _ = writeln!(code, "      Self::{}(_) => {}::{},", variant, name, variant);
+        }
+        code.push_str("    }\n");
+        code.push_str("  }\n");
+        code.push_str("  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(&self, fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>) -> Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>> {\n");
+        code.push_str("    match self {\n");
+        code.push_str("      Self::NONE => None,\n");
+        for (variant, _, _) in &members {
+            let _ = writeln!(
+                code,
+                "      Self::{}(v) => Some(v.pack(fbb).as_union_value()),",
+                variant
+            );
+        }
+        code.push_str("    }\n");
+        code.push_str("  }\n");
+
+        for (variant, method, target) in &members {
+            code.push_str(&fill(
+                r#"  /// If the union variant matches, return the owned {{TARGET}}, setting the union to NONE.
+  pub fn take_{{METHOD}}(&mut self) -> Option<Box<{{TARGET}}>> {
+    if let Self::{{VARIANT}}(_) = self {
+      let v = core::mem::replace(self, Self::NONE);
+      if let Self::{{VARIANT}}(w) = v {
+        Some(w)
+      } else {
+        unreachable!()
+      }
+    } else {
+      None
+    }
+  }
+  /// If the union variant matches, return a reference to the {{TARGET}}.
+  pub fn as_{{METHOD}}(&self) -> Option<&{{TARGET}}> {
+    if let Self::{{VARIANT}}(v) = self { Some(v.as_ref()) } else { None }
+  }
+  /// If the union variant matches, return a mutable reference to the {{TARGET}}.
+  pub fn as_{{METHOD}}_mut(&mut self) -> Option<&mut {{TARGET}}> {
+    if let Self::{{VARIANT}}(v) = self { Some(v.as_mut()) } else { None }
+  }
+"#,
+                &[("VARIANT", variant), ("METHOD", method), ("TARGET", target)],
+            ));
+        }
+        code.push_str("}\n");
+
+        Ok(code)
+    }
+
+    fn struct_layout(&self, declaration: &'a Declaration) -> Result<StructLayout<'a>> {
+        let mut members = Vec::new();
+        let mut size = 0usize;
+        let mut alignment = 1;
+
+        for field in declaration.fields() {
+            let ty = self.resolve_type(&field.ty, &declaration.namespace)?;
+            let (field_size, field_alignment) = match &ty {
+                FieldType::Scalar(scalar) | FieldType::Enum(_, scalar) => {
+                    (scalar.size(), scalar.size())
+                }
+                FieldType::Struct(target) => {
+                    let layout = self.struct_layout(target)?;
+                    (layout.size, layout.alignment)
+                }
+                _ => unreachable!("rejected by Schema::check"),
+            };
+
+            size = size.next_multiple_of(field_alignment);
+            alignment = alignment.max(field_alignment);
+            members.push(StructMember {
+                field,
+                ty,
+                offset: size,
+            });
+            size += field_size;
+        }
+
+        if let Some(force_align) = declaration
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == "force_align")
+            .and_then(|attribute| attribute.value.as_deref())
+        {
+            alignment =
+                alignment.max(force_align.parse().map_err(|_| {
+                    eyre!("Invalid force_align on {}", declaration.qualified_name())
+                })?);
+        }
+
+        Ok(StructLayout {
+            members,
+            size: size.next_multiple_of(alignment),
+            alignment,
+        })
+    }
+
+    fn structure(&self, declaration: &'a Declaration) -> Result<String> {
+        let name = Self::escape(&declaration.name);
+        let namespace = &declaration.namespace;
+        let layout = self.struct_layout(declaration)?;
+        let size = layout.size.to_string();
+        let mut code = String::new();
+
+        let _ = writeln!(
+            code,
+            "// struct {}, aligned to {}",
+            declaration.name, layout.alignment
+        );
+        code.push_str(&Self::doc(&declaration.doc, ""));
+        code.push_str(&fill(
+            r#"#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct {{NAME}}(pub [u8; {{SIZE}}]);
+impl Default for {{NAME}} {
+  fn default() -> Self {
+    Self([0; {{SIZE}}])
+  }
+}
+impl ::core::fmt::Debug for {{NAME}} {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    f.debug_struct("{{NAME}}")
+"#,
+            &[("NAME", &name), ("SIZE", &size)],
+        ));
+        for member in &layout.members {
+            let field = Self::escape(&member.field.name);
+            let _ = writeln!(code, "      .field(\"{}\", &self.{}())", field, field);
+        }
+        code.push_str("      .finish()\n");
+        code.push_str("  }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&fill(
+            r#"impl flatbuffers::SimpleToVerifyInSlice for {{NAME}} {}
+impl<'a> flatbuffers::Follow<'a> for {{NAME}} {
+  type Inner = &'a {{NAME}};
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    unsafe { <&'a {{NAME}}>::follow(buf, loc) }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a {{NAME}} {
+  type Inner = &'a {{NAME}};
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    unsafe { flatbuffers::follow_cast_ref::<{{NAME}}>(buf, loc) }
+  }
+}
+impl<'b> flatbuffers::Push for {{NAME}} {
+    type Output = {{NAME}};
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = unsafe { ::core::slice::from_raw_parts(self as *const {{NAME}} as *const u8, <Self as flatbuffers::Push>::size()) };
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn alignment() -> flatbuffers::PushAlignment {
+        flatbuffers::PushAlignment::new({{ALIGN}})
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for {{NAME}} {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+"#,
+            &[("NAME", &name), ("ALIGN", &layout.alignment.to_string())],
+        ));
+
+        if self.options.serialize {
+            code.push_str(&fill(
+                r#"impl Serialize for {{NAME}} {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("{{NAME}}", {{COUNT}})?;
+"#,
+                &[
+                    ("NAME", &name),
+                    ("COUNT", &layout.members.len().to_string()),
+                ],
+            ));
+            for member in &layout.members {
+                let field = Self::escape(&member.field.name);
+                let _ = writeln!(
+                    code,
+                    "      s.serialize_field(\"{}\", &self.{}())?;",
+                    field, field
+                );
+            }
+            code.push_str("    s.end()\n");
+            code.push_str("  }\n");
+            code.push_str("}\n\n");
+        }
+
+        let _ = writeln!(code, "impl<'a> {} {{", name);
+        code.push_str("  #[allow(clippy::too_many_arguments)]\n");
+        code.push_str("  pub fn new(\n");
+        for member in &layout.members {
+            let ty = match &member.ty {
+                FieldType::Struct(target) => format!("&{}", self.type_path(target, namespace)),
+                other => self.reader_type(other, namespace),
+            };
+            let _ = writeln!(code, "    {}: {},", self.local_name(&member.field.name), ty);
+        }
+        code.push_str("  ) -> Self {\n");
+        let _ = writeln!(code, "    let mut s = Self([0; {}]);", size);
+        for member in &layout.members {
+            let _ = writeln!(
+                code,
+                "    s.set_{}({});",
+                Self::escape(&member.field.name),
+                self.local_name(&member.field.name)
+            );
+        }
+        code.push_str("    s\n");
+        code.push_str("  }\n\n");
+
+        for member in &layout.members {
+            let field = Self::escape(&member.field.name);
+            let offset = member.offset.to_string();
+            code.push_str(&Self::doc(&member.field.doc, "  "));
+
+            match &member.ty {
+                FieldType::Struct(target) => {
+                    let path = self.type_path(target, namespace);
+                    let target_size = self.struct_layout(target)?.size;
+                    code.push_str(&fill(
+                        r#"  pub fn {{FIELD}}(&self) -> &{{TYPE}} {
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid struct in this slot
+    unsafe { &*(self.0[{{OFFSET}}..].as_ptr() as *const {{TYPE}}) }
+  }
+
+  #[allow(clippy::identity_op)]
+  pub fn set_{{FIELD}}(&mut self, x: &{{TYPE}}) {
+    self.0[{{OFFSET}}..{{OFFSET}} + {{SIZE}}].copy_from_slice(&x.0)
+  }
+
+"#,
+                        &[
+                            ("FIELD", &field),
+                            ("TYPE", &path),
+                            ("OFFSET", &offset),
+                            ("SIZE", &target_size.to_string()),
+                        ],
+                    ));
+                }
+                other => {
+                    let ty = self.reader_type(other, namespace);
+                    code.push_str(&fill(
+                        r#"  pub fn {{FIELD}}(&self) -> {{TYPE}} {
+    let mut mem = core::mem::MaybeUninit::<<{{TYPE}} as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[{{OFFSET}}..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<{{TYPE}} as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_{{FIELD}}(&mut self, x: {{TYPE}}) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[{{OFFSET}}..].as_mut_ptr(),
+        core::mem::size_of::<<{{TYPE}} as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+"#,
+                        &[("FIELD", &field), ("TYPE", &ty), ("OFFSET", &offset)],
+                    ));
+                }
+            }
+        }
+
+        if self.options.object_api {
+            let object = self.object_name(&declaration.name);
+            let _ = writeln!(code, "  pub fn unpack(&self) -> {} {{", object);
+            let _ = writeln!(code, "    {} {{", object);
+            for member in &layout.members {
+                let field = Self::escape(&member.field.name);
+                match member.ty {
+                    FieldType::Struct(_) => {
+                        let _ = writeln!(code, "      {}: self.{}().unpack(),", field, field);
+                    }
+                    _ => {
+                        let _ = writeln!(code, "      {}: self.{}(),", field, field);
+                    }
+                }
+            }
+            code.push_str("    }\n");
+            code.push_str("  }\n");
+        }
+        code.push_str("}\n\n");
+
+        if self.options.object_api {
+            let object = self.object_name(&declaration.name);
+            code.push_str("#[derive(Debug, Clone, PartialEq, Default)]\n");
+            let _ = writeln!(code, "pub struct {} {{", object);
+            for member in &layout.members {
+                let _ = writeln!(
+                    code,
+                    "  pub {}: {},",
+                    Self::escape(&member.field.name),
+                    self.object_type(&member.ty, namespace)
+                );
+            }
+            code.push_str("}\n");
+            let _ = writeln!(code, "impl {} {{", object);
+            let _ = writeln!(code, "  pub fn pack(&self) -> {} {{", name);
+            let _ = writeln!(code, "    {}::new(", name);
+            for member in &layout.members {
+                let field = Self::escape(&member.field.name);
+                match member.ty {
+                    FieldType::Struct(_) => {
+                        let _ = writeln!(code, "      &self.{}.pack(),", field);
+                    }
+                    _ => {
+                        let _ = writeln!(code, "      self.{},", field);
+                    }
+                }
+            }
+            code.push_str("    )\n");
+            code.push_str("  }\n");
+            code.push_str("}\n\n");
+        }
+
+        Ok(code)
+    }
+
+    fn table(&self, declaration: &'a Declaration) -> Result<String> {
+        let name = Self::escape(&declaration.name);
+        let namespace = &declaration.namespace;
+        let fields = declaration.fields();
+        let mut code = String::new();
+
+        // Slot of every field, unions take one for the type and one for the value
+        let explicit_ids = fields.iter().any(|field| field.has_attribute("id"));
+        let mut members = Vec::new();
+        let mut next_slot = 0usize;
+        for field in fields {
+            let ty = self.resolve_type(&field.ty, namespace)?;
+            let slot = if explicit_ids {
+                field
+                    .attribute("id")
+                    .and_then(|attribute| attribute.value.as_deref())
+                    .and_then(|id| id.parse::<usize>().ok())
+                    .ok_or_else(|| {
+                        eyre!(
+                            "{}.{} needs an id because other fields of the table have one",
+                            declaration.qualified_name(),
+                            field.name
+                        )
+                    })?
+            } else if matches!(ty, FieldType::Union(_)) {
+                next_slot += 2;
+                next_slot - 1
+            } else {
+                next_slot += 1;
+                next_slot - 1
+            };
+
+            if field.has_attribute("deprecated") {
+                continue;
+            }
+            let default = if ty.is_scalar() {
+                self.default_value(declaration, field, &ty)?
+            } else {
+                None
+            };
+            members.push(TableMember {
+                field,
+                name: Self::escape(&field.name),
+                local: self.local_name(&field.name),
+                required: field.has_attribute("required"),
+                vtable: format!("VT_{}", Self::escape(&field.name).to_ascii_uppercase()),
+                slot,
+                ty,
+                default,
+            });
+        }
+
+        // Union offsets are untyped, so only borrowed fields need a lifetime on the args
+        let needs_lifetime = members
+            .iter()
+            .any(|member| !member.ty.is_scalar() && !matches!(member.ty, FieldType::Union(_)));
+        let args = if needs_lifetime {
+            format!("{}Args<'a>", name)
+        } else {
+            format!("{}Args", name)
+        };
+
+        let _ = writeln!(code, "pub enum {}Offset {{}}", name);
+        code.push_str("#[derive(Copy, Clone, PartialEq)]\n\n");
+        code.push_str(&Self::doc(&declaration.doc, ""));
+        code.push_str(&fill(
+            r#"pub struct {{NAME}}<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for {{NAME}}<'a> {
+  type Inner = {{NAME}}<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> {{NAME}}<'a> {
+"#,
+            &[("NAME", &name)],
+        ));
+
+        for member in &members {
+            if let FieldType::Union(_) = member.ty {
+                let _ = writeln!(
+                    code,
+                    "  pub const {}_TYPE: flatbuffers::VOffsetT = {};",
+                    member.vtable,
+                    4 + 2 * (member.slot - 1)
+                );
+            }
+            let _ = writeln!(
+                code,
+                "  pub const {}: flatbuffers::VOffsetT = {};",
+                member.vtable,
+                4 + 2 * member.slot
+            );
+        }
+        code.push('\n');
+
+        code.push_str(&fill(
+            r#"  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    {{NAME}} { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    {{ARGS}}
+  ) -> flatbuffers::WIPOffset<{{NAME}}<'bldr>> {
+    let mut builder = {{NAME}}Builder::new(_fbb);
+"#,
+            &[
+                ("NAME", &name),
+                (
+                    "ARGS",
+                    &if needs_lifetime {
+                        format!("args: &'args {}Args<'args>", name)
+                    } else {
+                        format!("args: &'args {}Args", name)
+                    },
+                ),
+            ],
+        ));
+
+        // Largest fields first, in reverse declaration order within a size
+        let sizes: &[usize] = if declaration.has_attribute("original_order") {
+            &[0]
+        } else {
+            &[8, 4, 2, 1]
+        };
+        for &size in sizes {
+            for member in members.iter().rev() {
+                let member_size = match member.ty {
+                    FieldType::Union(_) => 4,
+                    ref other => other.size(),
+                };
+                if size != 0 && member_size != size {
+                    continue;
+                }
+                let _ = writeln!(code, "{}", self.create_add(member));
+            }
+            // Union types are a ubyte next to a four byte offset
+            if size == 1 || size == 0 {
+                for member in members.iter().rev() {
+                    if let FieldType::Union(_) = member.ty {
+                        let _ = writeln!(
+                            code,
+                            "    builder.add_{}_type(args.{}_type);",
+                            member.name, member.name
+                        );
+                    }
+                }
+            }
+        }
+        code.push_str("    builder.finish()\n");
+        code.push_str("  }\n\n");
+
+        if self.options.object_api {
+            code.push_str(&self.table_unpack(declaration, &members));
+        }
+
+        if let Some(key) = members
+            .iter()
+            .find(|member| member.field.has_attribute("key"))
+        {
+            code.push_str(&self.key_compare(&name, key));
+        }
+
+        for member in &members {
+            code.push_str(&self.accessor(&name, namespace, member));
+        }
+        for member in &members {
+            if let FieldType::Union(target) = member.ty {
+                code.push_str(&self.union_accessors(namespace, member, target)?);
+            }
+        }
+        code.push_str("}\n\n");
+
+        // Verifier
+        let _ = writeln!(code, "impl flatbuffers::Verifiable for {}<'_> {{", name);
+        code.push_str(
+            r#"  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+"#,
+        );
+        for member in &members {
+            match member.ty {
+                FieldType::Union(target) => {
+                    let union_path = self.type_path(target, namespace);
+                    let _ = writeln!(
+                        code,
+                        "     .visit_union::<{}, _>(\"{}_type\", Self::{}_TYPE, \"{}\", Self::{}, {}, |key, v, pos| {{",
+                        union_path,
+                        member.name,
+                        member.vtable,
+                        member.name,
+                        member.vtable,
+                        member.required
+                    );
+                    code.push_str("        match key {\n");
+                    let Kind::Union(variants) = &target.kind else {
+                        unreachable!("not a union");
+                    };
+                    for variant in variants {
+                        let table = self
+                            .schema
+                            .resolve(&variant.ty, &target.namespace)
+                            .ok_or_else(|| eyre!("Unknown type `{}`", variant.ty))?;
+                        let variant_name = Self::escape(&variant.name);
+                        let _ = writeln!(
+                            code,
+                            "          {}::{} => v.verify_union_variant::<flatbuffers::ForwardsUOffset<{}>>(\"{}::{}\", pos),",
+                            union_path,
+                            variant_name,
+                            self.type_path(table, namespace),
+                            union_path,
+                            variant_name
+                        );
+                    }
+                    code.push_str("          _ => Ok(()),\n");
+                    code.push_str("        }\n");
+                    code.push_str("     })?\n");
+                }
+                ref other => {
+                    let _ = writeln!(
+                        code,
+                        "     .visit_field::<{}>(\"{}\", Self::{}, {})?",
+                        self.verifier_type(other, namespace),
+                        member.name,
+                        member.vtable,
+                        member.required
+                    );
+                }
+            }
+        }
+        code.push_str("     .finish();\n");
+        code.push_str("    Ok(())\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+
+        // Args
+        let _ = writeln!(code, "pub struct {} {{", args);
+        for member in &members {
+            if let FieldType::Union(target) = member.ty {
+                let _ = writeln!(
+                    code,
+                    "    pub {}_type: {},",
+                    member.name,
+                    self.type_path(target, namespace)
+                );
+            }
+            let _ = writeln!(
+                code,
+                "    pub {}: {},",
+                member.name,
+                self.args_type(namespace, member)
+            );
+        }
+        code.push_str("}\n");
+        let lifetime = if needs_lifetime { "<'a>" } else { "" };
+        let _ = writeln!(code, "impl{} Default for {} {{", lifetime, args);
+        code.push_str("  #[inline]\n");
+        code.push_str("  fn default() -> Self {\n");
+        let _ = writeln!(code, "    {}Args {{", name);
+        for member in &members {
+            if let FieldType::Union(target) = member.ty {
+                let _ = writeln!(
+                    code,
+                    "      {}_type: {}::NONE,",
+                    member.name,
+                    self.type_path(target, namespace)
+                );
+            }
+            let value = match (&member.default, member.ty.is_scalar()) {
+                (Some(default), true) => default.clone(),
+                (None, true) => "None".to_string(),
+                _ if member.required => "None, // required field".to_string(),
+                _ => "None".to_string(),
+            };
+            let _ = writeln!(code, "      {}: {},", member.name, value);
+        }
+        code.push_str("    }\n");
+        code.push_str("  }\n");
+        code.push_str("}\n\n");
+
+        if self.options.serialize {
+            code.push_str(&self.table_serialize(&name, namespace, &members)?);
+        }
+
+        // Builder
+        code.push_str(&fill(
+            r#"pub struct {{NAME}}Builder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {{NAME}}Builder<'a, 'b, A> {
+"#,
+            &[("NAME", &name)],
+        ));
+        for member in &members {
+            code.push_str(&self.builder_add(&name, namespace, member));
+        }
+        code.push_str(&fill(
+            r#"  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> {{NAME}}Builder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    {{NAME}}Builder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<{{NAME}}<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+"#,
+            &[("NAME", &name)],
+        ));
+        for member in members.iter().filter(|member| member.required) {
+            let _ = writeln!(
+                code,
+                "    self.fbb_.required(o, {}::{},\"{}\");",
+                name, member.vtable, member.name
+            );
+        }
+        code.push_str("    flatbuffers::WIPOffset::new(o.value())\n");
+        code.push_str("  }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&self.table_debug(&name, namespace, &members)?);
+
+        if self.options.object_api {
+            code.push_str(&self.table_object(declaration, &members)?);
+        }
+
+        if self.options.decrypt_helpers {
+            code.push_str(&self.decrypt_helpers(declaration));
+        }
+
+        if let Some(root_type) = &self.schema.root_type
+            && self
+                .schema
+                .resolve(root_type, "")
+                .is_some_and(|root| std::ptr::eq(root, declaration))
+        {
+            code.push_str(&self.root_functions(&name));
+        }
+
+        Ok(code)
+    }
+
+    /// `decrypt` for Excel rows and `load_decrypted` for Excel tables
+    fn decrypt_helpers(&self, declaration: &Declaration) -> String {
+        let namespace = &declaration.namespace;
+        let module = format!(
+            "{}table_encryption",
+            "super::".repeat(Self::namespace_modules(namespace).len() + 1)
+        );
+        let mut code = String::new();
+
+        if let Some(excel) = self
+            .excel_tables
+            .iter()
+            .find(|excel| std::ptr::eq(excel.row, declaration))
+        {
+            let statements: String = excel
+                .fields
+                .iter()
+                .map(|field| {
+                    let enumeration = field
+                        .enumeration
+                        .map(|target| self.type_path(target, namespace));
+                    decrypt::rust::decrypt_field(
+                        field,
+                        &Self::escape(&field.field.name),
+                        &module,
+                        enumeration.as_deref(),
+                    )
+                })
+                .collect();
+            code.push_str(&decrypt::rust::row_impl(
+                &self.object_name(&Self::escape(&declaration.name)),
+                excel.key_name(),
+                &module,
+                &statements,
+            ));
+        }
+
+        if let Some(excel) = self
+            .excel_tables
+            .iter()
+            .find(|excel| std::ptr::eq(excel.table, declaration))
+        {
+            let name = Self::escape(&declaration.name);
+            code.push_str(&decrypt::rust::table_impl(
+                &name,
+                &self.object_name(&name),
+                &Self::escape(&excel.list.name),
+                excel.list.has_attribute("required"),
+                &module,
+            ));
+        }
+
+        code
+    }
+
+    fn create_add(&self, member: &TableMember) -> String {
+        let optional = match member.ty {
+            FieldType::Scalar(_) | FieldType::Enum(_, _) => member.default.is_none(),
+            _ => true,
+        };
+        if optional {
+            format!(
+                "    if let Some(x) = args.{} {{ builder.add_{}(x); }}",
+                member.name, member.name
+            )
+        } else {
+            format!("    builder.add_{}(args.{});", member.name, member.name)
+        }
+    }
+
+    fn args_type(&self, namespace: &str, member: &TableMember) -> String {
+        match &member.ty {
+            FieldType::Scalar(_) | FieldType::Enum(_, _) => {
+                let ty = self.reader_type(&member.ty, namespace);
+                if member.default.is_some() {
+                    ty
+                } else {
+                    format!("Option<{}>", ty)
+                }
+            }
+            FieldType::Struct(target) => {
+                format!("Option<&'a {}>", self.type_path(target, namespace))
+            }
+            other => format!("Option<{}>", self.offset_type(other, namespace, "'a")),
+        }
+    }
+
+    fn accessor(&self, table: &str, namespace: &str, member: &TableMember) -> String {
+        let mut code = String::new();
+        code.push_str(&Self::doc(&member.field.doc, "  "));
+
+        if let FieldType::Union(target) = member.ty {
+            let union_path = self.type_path(target, namespace);
+            code.push_str(&fill(
+                r#"  #[inline]
+  pub fn {{FIELD}}_type(&self) -> {{UNION}} {
+{{SAFETY}}    unsafe { self._tab.get::<{{UNION}}>({{TABLE}}::{{VT}}_TYPE, Some({{UNION}}::NONE)).unwrap()}
+  }
+"#,
+                &[
+                    ("FIELD", &member.name),
+                    ("UNION", &union_path),
+                    ("TABLE", table),
+                    ("VT", &member.vtable),
+                    ("SAFETY", SAFETY),
+                ],
+            ));
+        }
+
+        let reader = self.reader_type(&member.ty, namespace);
+        let follow = self.follow_type(&member.ty, namespace);
+        let (ty, default, unwrap) = match (&member.default, member.ty.is_scalar()) {
+            (Some(default), true) => (reader, format!("Some({})", default), ".unwrap()"),
+            (None, true) => (format!("Option<{}>", reader), "None".to_string(), ""),
+            _ if member.required => (reader, "None".to_string(), ".unwrap()"),
+            _ => (format!("Option<{}>", reader), "None".to_string(), ""),
+        };
+
+        code.push_str(&fill(
+            r#"  #[inline]
+  pub fn {{FIELD}}(&self) -> {{TYPE}} {
+{{SAFETY}}    unsafe { self._tab.get::<{{FOLLOW}}>({{TABLE}}::{{VT}}, {{DEFAULT}}){{UNWRAP}}}
+  }
+"#,
+            &[
+                ("FIELD", &member.name),
+                ("TYPE", &ty),
+                ("FOLLOW", &follow),
+                ("TABLE", table),
+                ("VT", &member.vtable),
+                ("DEFAULT", &default),
+                ("UNWRAP", unwrap),
+                ("SAFETY", SAFETY),
+            ],
+        ));
+
+        code
+    }
+
+    fn union_accessors(
+        &self,
+        namespace: &str,
+        member: &TableMember,
+        target: &Declaration,
+    ) -> Result<String> {
+        let Kind::Union(variants) = &target.kind else {
+            unreachable!("not a union");
+        };
+        let union_path = self.type_path(target, namespace);
+        let mut code = String::new();
+
+        for variant in variants {
+            let table_type = self
+                .schema
+                .resolve(&variant.ty, &target.namespace)
+                .ok_or_else(|| eyre!("Unknown type `{}`", variant.ty))?;
+            let get = if member.required {
+                r#"      let u = self.{{FIELD}}();
+      // Safety:
+      // Created from a valid Table for this object
+      // Which contains a valid union in this slot
+      Some(unsafe { {{TYPE}}::init_from_table(u) })"#
+            } else {
+                r#"      self.{{FIELD}}().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { {{TYPE}}::init_from_table(t) }
+     })"#
+            };
+            code.push_str(&fill(
+                &format!(
+                    r#"  #[inline]
+  #[allow(non_snake_case)]
+  pub fn {{{{FIELD}}}}_as_{{{{METHOD}}}}(&self) -> Option<{{{{TYPE}}}}<'a>> {{
+    if self.{{{{FIELD}}}}_type() == {{{{UNION}}}}::{{{{VARIANT}}}} {{
+{}
+    }} else {{
+      None
+    }}
+  }}
+
+"#,
+                    get
+                ),
+                &[
+                    ("FIELD", &member.name),
+                    ("METHOD", &to_snake_case(&variant.name)),
+                    ("TYPE", &self.type_path(table_type, namespace)),
+                    ("UNION", &union_path),
+                    ("VARIANT", &Self::escape(&variant.name)),
+                ],
+            ));
+        }
+
+        Ok(code)
+    }
+
+    fn key_compare(&self, table: &str, key: &TableMember) -> String {
+        let (value_type, compare) = match &key.ty {
+            FieldType::String => ("& str".to_string(), "key.cmp(val)"),
+            FieldType::Scalar(Scalar::Float | Scalar::Double) => (
+                self.reader_type(&key.ty, ""),
+                "key.partial_cmp(&val).unwrap_or(::core::cmp::Ordering::Equal)",
+            ),
+            other => (self.reader_type(other, ""), "key.cmp(&val)"),
+        };
+
+        fill(
+            r#"  #[inline]
+  pub fn key_compare_less_than(&self, o: &{{TABLE}}) -> bool {
+    self.{{FIELD}}() < o.{{FIELD}}()
+  }
+
+  #[inline]
+  pub fn key_compare_with_value(&self, val: {{TYPE}}) -> ::core::cmp::Ordering {
+    let key = self.{{FIELD}}();
+    {{COMPARE}}
+  }
+"#,
+            &[
+                ("TABLE", table),
+                ("FIELD", &key.name),
+                ("TYPE", &value_type),
+                ("COMPARE", compare),
+            ],
+        )
+    }
+
+    fn builder_add(&self, table: &str, namespace: &str, member: &TableMember) -> String {
+        let mut code = String::new();
+
+        if let FieldType::Union(target) = member.ty {
+            let union_path = self.type_path(target, namespace);
+            let _ = writeln!(code, "  #[inline]");
+            let _ = writeln!(
+                code,
+                "  pub fn add_{}_type(&mut self, {}_type: {}) {{",
+                member.name, member.name, union_path
+            );
+            let _ = writeln!(
+                code,
+                "    self.fbb_.push_slot::<{}>({}::{}_TYPE, {}_type, {}::NONE);",
+                union_path, table, member.vtable, member.name, union_path
+            );
+            let _ = writeln!(code, "  }}");
+        }
+
+        let (parameter, push) = match &member.ty {
+            FieldType::Scalar(_) | FieldType::Enum(_, _) => {
+                let ty = self.reader_type(&member.ty, namespace);
+                let push = match &member.default {
+                    Some(default) => format!(
+                        "self.fbb_.push_slot::<{}>({}::{}, {}, {});",
+                        ty, table, member.vtable, member.local, default
+                    ),
+                    None => format!(
+                        "self.fbb_.push_slot_always::<{}>({}::{}, {});",
+                        ty, table, member.vtable, member.local
+                    ),
+                };
+                (ty, push)
+            }
+            FieldType::Struct(target) => {
+                let path = self.type_path(target, namespace);
+                (
+                    format!("&{}", path),
+                    format!(
+                        "self.fbb_.push_slot_always::<&{}>({}::{}, {});",
+                        path, table, member.vtable, member.local
+                    ),
+                )
+            }
+            other => (
+                self.offset_type(other, namespace, "'b"),
+                format!(
+                    "self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>({}::{}, {});",
+                    table, member.vtable, member.local
+                ),
+            ),
+        };
+
+        let _ = writeln!(code, "  #[inline]");
+        let _ = writeln!(
+            code,
+            "  pub fn add_{}(&mut self, {}: {}) {{",
+            member.name, member.local, parameter
+        );
+        let _ = writeln!(code, "    {}", push);
+        let _ = writeln!(code, "  }}");
+        code
+    }
+
+    fn table_debug(&self, table: &str, namespace: &str, members: &[TableMember]) -> Result<String> {
+        let mut code = String::new();
+        let _ = writeln!(code, "impl ::core::fmt::Debug for {}<'_> {{", table);
+        code.push_str(
+            "  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {\n",
+        );
+        let _ = writeln!(code, "    let mut ds = f.debug_struct(\"{}\");", table);
+
+        for member in members {
+            if let FieldType::Union(target) = member.ty {
+                let union_path = self.type_path(target, namespace);
+                let _ = writeln!(
+                    code,
+                    "      ds.field(\"{}_type\", &self.{}_type());",
+                    member.name, member.name
+                );
+                let _ = writeln!(code, "      match self.{}_type() {{", member.name);
+                let Kind::Union(variants) = &target.kind else {
+                    unreachable!("not a union");
+                };
+                for variant in variants {
+                    code.push_str(&fill(
+                        r#"        {{UNION}}::{{VARIANT}} => {
+          if let Some(x) = self.{{FIELD}}_as_{{METHOD}}() {
+            ds.field("{{FIELD}}", &x)
+          } else {
+            ds.field("{{FIELD}}", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+"#,
+                        &[
+                            ("UNION", &union_path),
+                            ("VARIANT", &Self::escape(&variant.name)),
+                            ("FIELD", &member.name),
+                            ("METHOD", &to_snake_case(&variant.name)),
+                        ],
+                    ));
+                }
+                code.push_str("        _ => {\n");
+                code.push_str("          let x: Option<()> = None;\n");
+                let _ = writeln!(code, "          ds.field(\"{}\", &x)", member.name);
+                code.push_str("        },\n");
+                code.push_str("      };\n");
+            } else {
+                let _ = writeln!(
+                    code,
+                    "      ds.field(\"{}\", &self.{}());",
+                    member.name, member.name
+                );
+            }
+        }
+
+        code.push_str("      ds.finish()\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+        Ok(code)
+    }
+
+    fn table_serialize(
+        &self,
+        table: &str,
+        namespace: &str,
+        members: &[TableMember],
+    ) -> Result<String> {
+        let count: usize = members
+            .iter()
+            .map(|member| {
+                if matches!(member.ty, FieldType::Union(_)) {
+                    2
+                } else {
+                    1
+                }
+            })
+            .sum();
+        let mut code = fill(
+            r#"impl Serialize for {{NAME}}<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("{{NAME}}", {{COUNT}})?;
+"#,
+            &[("NAME", table), ("COUNT", &count.to_string())],
+        );
+
+        for member in members {
+            let optional = match member.ty {
+                FieldType::Scalar(_) | FieldType::Enum(_, _) => member.default.is_none(),
+                FieldType::Union(_) => false,
+                _ => !member.required,
+            };
+
+            if let FieldType::Union(target) = member.ty {
+                let union_path = self.type_path(target, namespace);
+                let _ = writeln!(
+                    code,
+                    "      s.serialize_field(\"{}_type\", &self.{}_type())?;",
+                    member.name, member.name
+                );
+                let _ = writeln!(code, "      match self.{}_type() {{", member.name);
+                let _ = writeln!(code, "        {}::NONE => (),", union_path);
+                let Kind::Union(variants) = &target.kind else {
+                    unreachable!("not a union");
+                };
+                for variant in variants {
+                    code.push_str(&fill(
+                        r#"        {{UNION}}::{{VARIANT}} => {
+          let f = self.{{FIELD}}_as_{{METHOD}}()
+            .expect("Invalid union table, expected `{{UNION}}::{{VARIANT}}`.");
+          s.serialize_field("{{FIELD}}", &f)?;
+        }
+"#,
+                        &[
+                            ("UNION", &union_path),
+                            ("VARIANT", &Self::escape(&variant.name)),
+                            ("FIELD", &member.name),
+                            ("METHOD", &to_snake_case(&variant.name)),
+                        ],
+                    ));
+                }
+                code.push_str("        _ => unimplemented!(),\n");
+                code.push_str("      }\n");
+            } else if optional {
+                code.push_str(&fill(
+                    r#"      if let Some(f) = self.{{FIELD}}() {
+        s.serialize_field("{{FIELD}}", &f)?;
+      } else {
+        s.skip_field("{{FIELD}}")?;
+      }
+"#,
+                    &[("FIELD", &member.name)],
+                ));
+            } else {
+                let _ = writeln!(
+                    code,
+                    "      s.serialize_field(\"{}\", &self.{}())?;",
+                    member.name, member.name
+                );
+            }
+        }
+
+        code.push_str("    s.end()\n");
+        code.push_str("  }\n");
+        code.push_str("}\n\n");
+        Ok(code)
+    }
+
+    fn table_unpack(&self, declaration: &Declaration, members: &[TableMember]) -> String {
+        let namespace = &declaration.namespace;
+        let object = self.object_name(&declaration.name);
+        let mut code = String::new();
+        let _ = writeln!(code, "  pub fn unpack(&self) -> {} {{", object);
+
+        for member in members {
+            let field = &member.name;
+            let expression = match &member.ty {
+                FieldType::Scalar(_) | FieldType::Enum(_, _) => format!("self.{}()", field),
+                FieldType::Union(target) => {
+                    let union_path = self.type_path(target, namespace);
+                    let object_path = self.object_path(target, namespace);
+                    let mut expression = format!("match self.{}_type() {{\n", field);
+                    let _ = writeln!(
+                        expression,
+                        "      {}::NONE => {}::NONE,",
+                        union_path, object_path
+                    );
+                    if let Kind::Union(variants) = &target.kind {
+                        for variant in variants {
+                            let variant_name = Self::escape(&variant.name);
+                            let _ = writeln!(
+                                expression,
+                                "      {}::{} => {}::{}(Box::new(\n        self.{}_as_{}()\n            .expect(\"Invalid union table, expected `{}::{}`.\")\n            .unpack()\n      )),",
+                                union_path,
+                                variant_name,
+                                object_path,
+                                variant_name,
+                                field,
+                                to_snake_case(&variant.name),
+                                union_path,
+                                variant_name
+                            );
+                        }
+                    }
+                    let _ = writeln!(expression, "      _ => {}::NONE,", object_path);
+                    expression.push_str("    }");
+                    expression
+                }
+                other => {
+                    let convert = match other {
+                        FieldType::String => "x.to_string()".to_string(),
+                        FieldType::Struct(_) => "x.unpack()".to_string(),
+                        FieldType::Table(_) => "Box::new(x.unpack())".to_string(),
+                        FieldType::Vector(element) => match element.as_ref() {
+                            FieldType::String => "x.iter().map(|s| s.to_string()).collect()",
+                            FieldType::Struct(_) | FieldType::Table(_) => {
+                                "x.iter().map(|t| t.unpack()).collect()"
+                            }
+                            _ => "x.into_iter().collect()",
+                        }
+                        .to_string(),
+                        _ => unreachable!("handled above"),
+                    };
+                    if member.required {
+                        format!(
+                            "{{\n      let x = self.{}();\n      {}\n    }}",
+                            field, convert
+                        )
+                    } else {
+                        format!("self.{}().map(|x| {{\n      {}\n    }})", field, convert)
+                    }
+                }
+            };
+            let _ = writeln!(code, "    let {} = {};", member.local, expression);
+        }
+
+        let _ = writeln!(code, "    {} {{", object);
+        for member in members {
+            let _ = writeln!(
+                code,
+                "      {},",
+                Self::initializer(&member.name, &member.local)
+            );
+        }
+        code.push_str("    }\n");
+        code.push_str("  }\n\n");
+        code
+    }
+
+    fn table_object(&self, declaration: &Declaration, members: &[TableMember]) -> Result<String> {
+        let namespace = &declaration.namespace;
+        let name = Self::escape(&declaration.name);
+        let object = self.object_name(&declaration.name);
+        let mut code = String::new();
+
+        code.push_str("#[non_exhaustive]\n");
+        code.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+        let _ = writeln!(code, "pub struct {} {{", object);
+        for member in members {
+            let ty = self.object_type(&member.ty, namespace);
+            let optional = match member.ty {
+                FieldType::Scalar(_) | FieldType::Enum(_, _) => member.default.is_none(),
+                FieldType::Union(_) => false,
+                _ => !member.required,
+            };
+            if optional {
+                let _ = writeln!(code, "  pub {}: Option<{}>,", member.name, ty);
+            } else {
+                let _ = writeln!(code, "  pub {}: {},", member.name, ty);
+            }
+        }
+        code.push_str("}\n");
+
+        let _ = writeln!(code, "impl Default for {} {{", object);
+        code.push_str("  fn default() -> Self {\n");
+        code.push_str("    Self {\n");
+        for member in members {
+            let value = match (&member.ty, &member.default) {
+                (FieldType::Scalar(_) | FieldType::Enum(_, _), Some(default)) => default.clone(),
+                (FieldType::Scalar(_) | FieldType::Enum(_, _), None) => "None".to_string(),
+                (FieldType::Union(target), _) => {
+                    format!("{}::NONE", self.object_path(target, namespace))
+                }
+                (_, _) if !member.required => "None".to_string(),
+                (FieldType::String, _) => "\"\".to_string()".to_string(),
+                (FieldType::Vector(_), _) => "Default::default()".to_string(),
+                _ => "Default::default()".to_string(),
+            };
+            let _ = writeln!(code, "      {}: {},", member.name, value);
+        }
+        code.push_str("    }\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+
+        let _ = writeln!(code, "impl {} {{", object);
+        code.push_str("  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(\n");
+        code.push_str("    &self,\n");
+        code.push_str("    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>\n");
+        let _ = writeln!(code, "  ) -> flatbuffers::WIPOffset<{}<'b>> {{", name);
+
+        for member in members {
+            let field = &member.name;
+            let local = &member.local;
+            match &member.ty {
+                FieldType::Scalar(_) | FieldType::Enum(_, _) => {
+                    let _ = writeln!(code, "    let {} = self.{};", local, field);
+                }
+                FieldType::Union(target) => {
+                    let _ = writeln!(
+                        code,
+                        "    let {}_type = self.{}.{}_type();",
+                        field,
+                        field,
+                        to_snake_case(&target.name)
+                    );
+                    let _ = writeln!(code, "    let {} = self.{}.pack(_fbb);", local, field);
+                }
+                FieldType::Struct(_) => {
+                    if member.required {
+                        let _ =
+                            writeln!(code, "    let {}_tmp = Some(self.{}.pack());", field, field);
+                    } else {
+                        let _ = writeln!(
+                            code,
+                            "    let {}_tmp = self.{}.as_ref().map(|x| x.pack());",
+                            field, field
+                        );
+                    }
+                    let _ = writeln!(code, "    let {} = {}_tmp.as_ref();", local, field);
+                }
+                other => {
+                    let build = match other {
+                        FieldType::String => "_fbb.create_string(x)",
+                        FieldType::Table(_) => "x.pack(_fbb)",
+                        FieldType::Vector(element) => match element.as_ref() {
+                            FieldType::String => {
+                                "let w: Vec<_> = x.iter().map(|s| _fbb.create_string(s)).collect();_fbb.create_vector(&w)"
+                            }
+                            FieldType::Struct(_) => {
+                                "let w: Vec<_> = x.iter().map(|t| t.pack()).collect();_fbb.create_vector(&w)"
+                            }
+                            FieldType::Table(_) => {
+                                "let w: Vec<_> = x.iter().map(|t| t.pack(_fbb)).collect();_fbb.create_vector(&w)"
+                            }
+                            _ => "_fbb.create_vector(x)",
+                        },
+                        _ => unreachable!("handled above"),
+                    };
+                    if member.required {
+                        let _ = writeln!(code, "    let {} = Some({{", local);
+                        let _ = writeln!(code, "      let x = &self.{};", field);
+                        let _ = writeln!(code, "      {}", build);
+                        code.push_str("    });\n");
+                    } else {
+                        let _ = writeln!(
+                            code,
+                            "    let {} = self.{}.as_ref().map(|x|{{\n      {}\n    }});",
+                            local, field, build
+                        );
+                    }
+                }
+            }
+        }
+
+        let _ = writeln!(code, "    {}::create(_fbb, &{}Args{{", name, name);
+        for member in members {
+            if let FieldType::Union(_) = member.ty {
+                let _ = writeln!(code, "      {}_type,", member.name);
+            }
+            let _ = writeln!(
+                code,
+                "      {},",
+                Self::initializer(&member.name, &member.local)
+            );
+        }
+        code.push_str("    })\n");
+        code.push_str("  }\n");
+        code.push_str("}\n");
+
+        Ok(code)
+    }
+
+    fn root_functions(&self, table: &str) -> String {
+        let snake = to_snake_case(table);
+        let constant = to_screaming_snake_case(table);
+        let mut code = fill(
+            r#"#[inline]
+/// Verifies that a buffer of bytes contains a `{{NAME}}`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_{{SNAKE}}_unchecked`.
+pub fn root_as_{{SNAKE}}(buf: &[u8]) -> Result<{{NAME}}<'_>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<{{NAME}}>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `{{NAME}}` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_{{SNAKE}}_unchecked`.
+pub fn size_prefixed_root_as_{{SNAKE}}(buf: &[u8]) -> Result<{{NAME}}<'_>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<{{NAME}}>(buf)
+}
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `{{NAME}}` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_{{SNAKE}}_unchecked`.
+pub fn root_as_{{SNAKE}}_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<{{NAME}}<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<{{NAME}}<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `{{NAME}}` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_{{SNAKE}}_unchecked`.
+pub fn size_prefixed_root_as_{{SNAKE}}_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<{{NAME}}<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<{{NAME}}<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a {{NAME}} and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `{{NAME}}`.
+pub unsafe fn root_as_{{SNAKE}}_unchecked(buf: &[u8]) -> {{NAME}}<'_> {
+  unsafe { flatbuffers::root_unchecked::<{{NAME}}>(buf) }
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed {{NAME}} and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `{{NAME}}`.
+pub unsafe fn size_prefixed_root_as_{{SNAKE}}_unchecked(buf: &[u8]) -> {{NAME}}<'_> {
+  unsafe { flatbuffers::size_prefixed_root_unchecked::<{{NAME}}>(buf) }
+}
+"#,
+            &[("NAME", table), ("SNAKE", &snake)],
+        );
+
+        let identifier = match &self.schema.file_identifier {
+            Some(identifier) => {
+                code.push_str(&fill(
+                    r#"pub const {{CONSTANT}}_IDENTIFIER: &str = "{{IDENTIFIER}}";
+
+#[inline]
+pub fn {{SNAKE}}_buffer_has_identifier(buf: &[u8]) -> bool {
+  flatbuffers::buffer_has_identifier(buf, {{CONSTANT}}_IDENTIFIER, false)
+}
+
+#[inline]
+pub fn {{SNAKE}}_size_prefixed_buffer_has_identifier(buf: &[u8]) -> bool {
+  flatbuffers::buffer_has_identifier(buf, {{CONSTANT}}_IDENTIFIER, true)
+}
+
+"#,
+                    &[
+                        ("CONSTANT", &constant),
+                        ("IDENTIFIER", identifier),
+                        ("SNAKE", &snake),
+                    ],
+                ));
+                format!("Some({}_IDENTIFIER)", constant)
+            }
+            None => "None".to_string(),
+        };
+
+        if let Some(extension) = &self.schema.file_extension {
+            let _ = writeln!(
+                code,
+                "pub const {}_EXTENSION: &str = \"{}\";\n",
+                constant, extension
+            );
+        }
+
+        code.push_str(&fill(
+            r#"#[inline]
+pub fn finish_{{SNAKE}}_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+    root: flatbuffers::WIPOffset<{{NAME}}<'a>>) {
+  fbb.finish(root, {{IDENTIFIER}});
+}
+
+#[inline]
+pub fn finish_size_prefixed_{{SNAKE}}_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>, root: flatbuffers::WIPOffset<{{NAME}}<'a>>) {
+  fbb.finish_size_prefixed(root, {{IDENTIFIER}});
+}
+"#,
+            &[("NAME", table), ("SNAKE", &snake), ("IDENTIFIER", &identifier)],
+        ));
+
+        code
+    }
+}
+
+/// A table field that is not deprecated, with its resolved type and vtable slot
+struct TableMember<'a> {
+    field: &'a Field,
+    name: String,
+    /// Name of locals and parameters holding the field, which must not shadow a type
+    local: String,
+    ty: FieldType<'a>,
+    required: bool,
+    vtable: String,
+    slot: usize,
+    default: Option<String>,
+}
@@ -24,3 +24,19 @@ pub const FBS_DUMPER_BINARY: &str = "FbsDumper";
 pub const FBS_DUMPER_DIR: &str = "FbsDumper";
 pub const FLATC_BINARY: &str = "flatc";
 pub const FLATC_DIR: &str = "FlatC";
+
+pub const CONFIG_DIR: &str = "bafb";
+pub const CONFIG_FILE: &str = "config.toml";
+pub const PROJECT_CONFIG_FILE: &str = "bafb.toml";
+pub const CONFIG_ENV_PREFIX: &str = "BAFB_";
+pub const CONFIG_ENV_SEPARATOR: &str = "__";
+
+pub const DUMPS_DIR: &str = "dumps";
+pub const SCHEMA_FILE: &str = "BlueArchive.fbs";
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+pub const MODULE_ROOT_FILE: &str = "mod.rs";
+pub const STAGING_DIR: &str = ".bafb-staging";
+
+pub const FLATBUFFERS_VERSION: &str = "25.12.19";
+pub const SERDE_VERSION: &str = "1.0";
+pub const REGION_FEATURES: &[&str] = &["japan", "global"];
@@ -1,5 +1,7 @@
 mod cli;
+mod codegen;
 mod helpers;
+mod schema;
 mod tools;
 mod wrappers;
 
@@ -0,0 +1,242 @@
+use crate::helpers::config::SCHEMA_FILE;
+use crate::schema::Schema;
+use crate::schema::diff::{Change, diff};
+use crate::schema::history::Store;
+
+use eyre::{Result, eyre};
+use std::fmt::Write;
+use std::path::Path;
+
+/// The schema a changelog compares against and what to call it
+pub struct Previous {
+    pub schema: Schema,
+    pub name: String,
+}
+
+impl Previous {
+    /// A schema file, an output directory of an earlier dump, or a history store whose newest
+    /// version is taken
+    pub fn load(path: &Path) -> Result<Self> {
+        let store = Store::open(path);
+        let schema = match store {
+            Store::Directory(_) if path.is_file() => Some(path.to_path_buf()),
+            Store::Directory(_) if path.join(SCHEMA_FILE).is_file() => Some(path.join(SCHEMA_FILE)),
+            _ => None,
+        };
+        if let Some(schema) = schema {
+            return Ok(Self {
+                schema: Schema::load(&schema)?,
+                name: path.display().to_string(),
+            });
+        }
+
+        let newest = store
+            .load()?
+            .versions
+            .pop()
+            .ok_or_else(|| eyre!("{} holds no schemas", path.display()))?;
+        Ok(Self {
+            schema: newest.schema,
+            name: newest.version,
+        })
+    }
+}
+
+/// A group of changes, `count` is what the heading says and may count more than the items
+pub struct Section {
+    pub title: &'static str,
+    pub count: usize,
+    pub items: Vec<String>,
+}
+
+/// The changes between two schemas grouped the way patch notes read them
+pub struct Changelog {
+    pub sections: Vec<Section>,
+}
+
+impl Changelog {
+    pub fn new(old: &Schema, new: &Schema) -> Self {
+        let changes = diff(old, new);
+        let declarations: Vec<&Change> = changes
+            .iter()
+            .filter(|change| change.member.is_none())
+            .collect();
+        // Members of added or removed declarations are part of the declaration entry
+        let is_whole = |declaration: &str| {
+            declarations.iter().any(|change| {
+                change.declaration == declaration && (change.is_added() || change.is_removed())
+            })
+        };
+        let is_table = |change: &Change| {
+            change
+                .before
+                .as_ref()
+                .or(change.after.as_ref())
+                .is_some_and(|snapshot| snapshot.what == "table")
+        };
+        let members = |declaration: &str| {
+            changes
+                .iter()
+                .filter(|change| change.declaration == declaration && change.member.is_some())
+                .count()
+        };
+
+        let mut new_tables = Vec::new();
+        let mut removed_tables = Vec::new();
+        let mut new_types = Vec::new();
+        let mut removed_types = Vec::new();
+        let mut new_fields = Vec::new();
+        let mut removed_fields = Vec::new();
+        let mut enum_values: Vec<(&str, Vec<String>)> = Vec::new();
+        let mut type_changes = Vec::new();
+        let mut other = Vec::new();
+
+        for change in &changes {
+            let Some(member) = &change.member else {
+                let item = match (&change.before, &change.after) {
+                    (Some(_), Some(_)) => None,
+                    (Some(snapshot), None) | (None, Some(snapshot)) => {
+                        let members = count(members(&change.declaration), noun(snapshot.what));
+                        Some(if is_table(change) {
+                            format!("`{}` ({})", change.declaration, members)
+                        } else {
+                            format!("`{}` ({}, {})", change.declaration, snapshot.what, members)
+                        })
+                    }
+                    (None, None) => None,
+                };
+                match item {
+                    Some(item) if change.is_added() && is_table(change) => new_tables.push(item),
+                    Some(item) if change.is_removed() && is_table(change) => {
+                        removed_tables.push(item)
+                    }
+                    Some(item) if change.is_added() => new_types.push(item),
+                    Some(item) => removed_types.push(item),
+                    None if change.type_changed() => type_changes.push(transition(change)),
+                    None => other.push(transition(change)),
+                }
+                continue;
+            };
+            if is_whole(&change.declaration) {
+                continue;
+            }
+
+            match (&change.before, &change.after) {
+                (None, Some(after)) if after.what == "field" => {
+                    new_fields.push(format!("`{}`: `{}`", change.path(), after))
+                }
+                (None, Some(after)) if after.what == "value" => {
+                    let value = format!("`{} = {}`", member, after.ty);
+                    match enum_values
+                        .iter_mut()
+                        .find(|(declaration, _)| *declaration == change.declaration)
+                    {
+                        Some((_, values)) => values.push(value),
+                        None => enum_values.push((&change.declaration, vec![value])),
+                    }
+                }
+                (Some(before), None) if before.what == "field" => {
+                    removed_fields.push(format!("`{}`: `{}`", change.path(), before))
+                }
+                _ if change.type_changed() => type_changes.push(transition(change)),
+                _ => other.push(transition(change)),
+            }
+        }
+
+        let values = enum_values.iter().map(|(_, values)| values.len()).sum();
+        let enum_values = enum_values
+            .into_iter()
+            .map(|(declaration, values)| format!("`{}`: {}", declaration, values.join(", ")))
+            .collect();
+        let sections = [
+            ("New tables", new_tables),
+            ("Removed tables", removed_tables),
+            ("New fields", new_fields),
+            ("Removed fields", removed_fields),
+            ("Enum additions", enum_values),
+            ("Type changes", type_changes),
+            ("New types", new_types),
+            ("Removed types", removed_types),
+            ("Other changes", other),
+        ]
+        .into_iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(title, items)| Section {
+            title,
+            count: if title == "Enum additions" {
+                values
+            } else {
+                items.len()
+            },
+            items,
+        })
+        .collect();
+
+        Self { sections }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// `2 new tables, 1 new field`, or `no changes`
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no changes".to_string();
+        }
+        let parts: Vec<String> = self
+            .sections
+            .iter()
+            .map(|section| {
+                let title = section.title.to_lowercase();
+                match section.count {
+                    1 => format!("1 {}", title.trim_end_matches('s')),
+                    count => format!("{} {}", count, title),
+                }
+            })
+            .collect();
+        parts.join(", ")
+    }
+
+    /// `CHANGELOG.md` headed by the game version, compared with `previous`
+    pub fn markdown(&self, version: &str, previous: &str) -> String {
+        let mut output = format!(
+            "# {}\n\nSchema changes since {}: {}.\n",
+            version,
+            previous,
+            self.summary()
+        );
+        for section in &self.sections {
+            let _ = writeln!(output, "\n## {} ({})\n", section.title, section.count);
+            for item in &section.items {
+                let _ = writeln!(output, "- {}", item);
+            }
+        }
+        output
+    }
+}
+
+fn transition(change: &Change) -> String {
+    match (&change.before, &change.after) {
+        (Some(before), Some(after)) => format!("`{}`: `{}` → `{}`", change.path(), before, after),
+        (Some(snapshot), None) => format!("`{}`: removed `{}`", change.path(), snapshot),
+        (None, Some(snapshot)) => format!("`{}`: added `{}`", change.path(), snapshot),
+        (None, None) => format!("`{}`", change.path()),
+    }
+}
+
+fn noun(what: &str) -> &'static str {
+    match what {
+        "enum" => "value",
+        "union" => "variant",
+        _ => "field",
+    }
+}
+
+fn count(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
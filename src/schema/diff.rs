@@ -0,0 +1,251 @@
+use crate::schema::export::{sorted, target};
+use crate::schema::writer::metadata;
+use crate::schema::{Declaration, Kind, Schema, Type};
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a declaration or member looks like in one schema, two snapshots are equal when nothing
+/// but documentation changed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// `table`, `struct`, `enum` or `union` for declarations, `field`, `value` or `variant` for
+    /// members
+    pub what: &'static str,
+    /// Field type with named types qualified, enum value number, union variant table or the
+    /// underlying type of an enum
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub ty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub attributes: String,
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.what {
+            "table" | "struct" | "union" => write!(f, "{}", self.what)?,
+            "enum" => write!(f, "enum : {}", self.ty)?,
+            _ => write!(f, "{}", self.ty)?,
+        }
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+        if !self.attributes.is_empty() {
+            write!(f, " ({})", self.attributes)?;
+        }
+        Ok(())
+    }
+}
+
+/// A declaration or member that was added, removed or changed between two schemas
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    /// Qualified name of the declaration
+    pub declaration: String,
+    /// Field, enum value or union variant, `None` for the declaration itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    pub before: Option<Snapshot>,
+    pub after: Option<Snapshot>,
+}
+
+impl Change {
+    /// `Namespace.Declaration` or `Namespace.Declaration.member`
+    pub fn path(&self) -> String {
+        match &self.member {
+            Some(member) => format!("{}.{}", self.declaration, member),
+            None => self.declaration.clone(),
+        }
+    }
+
+    pub fn is_added(&self) -> bool {
+        self.before.is_none()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.after.is_none()
+    }
+
+    /// Whether the kind or type changed, rather than the default or the attributes
+    pub fn type_changed(&self) -> bool {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => before.what != after.what || before.ty != after.ty,
+            _ => false,
+        }
+    }
+
+    /// `added`, `removed` or `changed`
+    pub fn verb(&self) -> &'static str {
+        if self.is_added() {
+            "added"
+        } else if self.is_removed() {
+            "removed"
+        } else {
+            "changed"
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.verb(), self.path())?;
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => write!(f, ": {} -> {}", before, after),
+            (Some(snapshot), None) | (None, Some(snapshot)) => write!(f, ": {}", snapshot),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Everything added, removed or changed from `old` to `new`, by path with each declaration before
+/// its members. Members of added or removed declarations are listed too
+pub fn diff(old: &Schema, new: &Schema) -> Vec<Change> {
+    let old = snapshots(old);
+    let new = snapshots(new);
+
+    let mut changes = Vec::new();
+    let mut declarations: Vec<&String> = old.keys().chain(new.keys()).collect();
+    declarations.sort();
+    declarations.dedup();
+
+    for declaration in declarations {
+        let before = old.get(declaration);
+        let after = new.get(declaration);
+        let snapshot = |side: Option<&(Snapshot, Vec<(String, Snapshot)>)>| {
+            side.map(|(snapshot, _)| snapshot.clone())
+        };
+        if snapshot(before) != snapshot(after) {
+            changes.push(Change {
+                declaration: declaration.clone(),
+                member: None,
+                before: snapshot(before),
+                after: snapshot(after),
+            });
+        }
+
+        let empty = Vec::new();
+        let old_members = before.map_or(&empty, |(_, members)| members);
+        let new_members = after.map_or(&empty, |(_, members)| members);
+        let find = |members: &[(String, Snapshot)], name: &str| {
+            members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, snapshot)| snapshot.clone())
+        };
+
+        // Members in the order of the newer schema, then the ones it removed
+        let names = new_members
+            .iter()
+            .chain(
+                old_members
+                    .iter()
+                    .filter(|(name, _)| find(new_members, name).is_none()),
+            )
+            .map(|(name, _)| name);
+        for name in names {
+            let before = find(old_members, name);
+            let after = find(new_members, name);
+            if before != after {
+                changes.push(Change {
+                    declaration: declaration.clone(),
+                    member: Some(name.clone()),
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+    changes
+}
+
+type Snapshots = BTreeMap<String, (Snapshot, Vec<(String, Snapshot)>)>;
+
+fn snapshots(schema: &Schema) -> Snapshots {
+    sorted(schema)
+        .into_iter()
+        .map(|declaration| {
+            let ty = match &declaration.kind {
+                Kind::Enum(underlying, _) => underlying.name().to_string(),
+                _ => String::new(),
+            };
+            let snapshot = Snapshot {
+                what: declaration.kind.keyword(),
+                ty,
+                default: None,
+                attributes: attributes(&metadata(&declaration.attributes)),
+            };
+            (
+                declaration.qualified_name(),
+                (snapshot, members(schema, declaration)),
+            )
+        })
+        .collect()
+}
+
+fn members(schema: &Schema, declaration: &Declaration) -> Vec<(String, Snapshot)> {
+    match &declaration.kind {
+        Kind::Table(fields) | Kind::Struct(fields) => fields
+            .iter()
+            .map(|field| {
+                let snapshot = Snapshot {
+                    what: "field",
+                    ty: qualified(schema, declaration, &field.ty),
+                    default: field.default.clone(),
+                    attributes: attributes(&metadata(&field.attributes)),
+                };
+                (field.name.clone(), snapshot)
+            })
+            .collect(),
+        Kind::Enum(_, values) => values
+            .iter()
+            .map(|value| {
+                let snapshot = Snapshot {
+                    what: "value",
+                    ty: value.value.to_string(),
+                    default: None,
+                    attributes: String::new(),
+                };
+                (value.name.clone(), snapshot)
+            })
+            .collect(),
+        Kind::Union(variants) => variants
+            .iter()
+            .map(|variant| {
+                let ty = Type::Named(variant.ty.clone());
+                let snapshot = Snapshot {
+                    what: "variant",
+                    ty: qualified(schema, declaration, &ty),
+                    default: None,
+                    attributes: String::new(),
+                };
+                (variant.name.clone(), snapshot)
+            })
+            .collect(),
+    }
+}
+
+/// The type as written with named types replaced by their qualified name, so moving a reference
+/// between spellings of the same declaration is not a change
+fn qualified(schema: &Schema, owner: &Declaration, ty: &Type) -> String {
+    let name = match target(schema, owner, ty) {
+        Some(target) => target.qualified_name(),
+        None => return ty.to_string(),
+    };
+    match ty {
+        Type::Vector(_) => format!("[{}]", name),
+        Type::Array(_, length) => format!("[{}:{}]", name, length),
+        _ => name,
+    }
+}
+
+/// `metadata` without the surrounding ` (...)`
+fn attributes(metadata: &str) -> String {
+    metadata
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .to_string()
+}
@@ -0,0 +1,335 @@
+use crate::schema::Kind;
+use crate::schema::doc::{DocFile, Member, Page, Site, TypePart};
+
+use serde_json::{Value, json};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 0; color: #1f2328; }
+header { display: flex; gap: 1em; align-items: center; padding: 0.75em 2em; background: #f6f8fa; border-bottom: 1px solid #d0d7de; position: relative; }
+main { padding: 1em 2em; max-width: 80em; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+code, td.type { font-family: ui-monospace, monospace; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #d0d7de; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+tr.deprecated td { color: #8c959f; text-decoration: line-through; }
+tr:target { background: #fff8c5; }
+.meta { color: #59636e; }
+#search { margin-left: auto; width: 20em; padding: 0.3em; }
+#results { position: absolute; right: 2em; top: 2.5em; margin: 0; padding: 0; list-style: none; background: white; border: 1px solid #d0d7de; max-height: 30em; overflow-y: auto; z-index: 1; }
+#results:empty { display: none; }
+#results li a { display: block; padding: 0.2em 0.6em; }
+#results li span { color: #59636e; margin-left: 0.5em; }
+";
+
+const SEARCH: &str = "\
+const search = document.getElementById('search');
+const results = document.getElementById('results');
+search.addEventListener('input', () => {
+    const query = search.value.trim().toLowerCase();
+    results.replaceChildren();
+    if (!query) return;
+    const found = SEARCH_INDEX
+        .filter((entry) => entry.name.toLowerCase().includes(query))
+        .sort((a, b) => {
+            const rank = (entry) => entry.name.toLowerCase().split('.').pop().startsWith(query) ? 0 : 1;
+            return rank(a) - rank(b) || a.name.length - b.name.length;
+        })
+        .slice(0, 50);
+    for (const entry of found) {
+        const item = document.createElement('li');
+        const link = document.createElement('a');
+        link.href = entry.page;
+        link.textContent = entry.name;
+        const kind = document.createElement('span');
+        kind.textContent = entry.kind;
+        link.append(kind);
+        item.append(link);
+        results.append(item);
+    }
+});
+search.addEventListener('keydown', (event) => {
+    const first = results.querySelector('a');
+    if (event.key === 'Enter' && first) window.location.href = first.href;
+});
+";
+
+/// `index.html`, `used-by.html`, a page per declaration and the search index they share
+pub fn render(site: &Site) -> Vec<DocFile> {
+    let mut files = vec![
+        file("index.html", index(site)),
+        file("used-by.html", used_by(site)),
+        file("style.css", STYLE.to_string()),
+        file("search.js", SEARCH.to_string()),
+        file("search-index.js", search_index(site)),
+    ];
+    for page in &site.pages {
+        files.push(file(
+            &format!("{}.html", page.file),
+            declaration(site, page),
+        ));
+    }
+    files
+}
+
+fn file(path: &str, content: String) -> DocFile {
+    DocFile {
+        path: PathBuf::from(path),
+        content,
+    }
+}
+
+fn layout(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<link rel=\"stylesheet\" href=\"style.css\">
+</head>
+<body>
+<header>
+<a href=\"index.html\">Index</a>
+<a href=\"used-by.html\">Used by</a>
+<input id=\"search\" type=\"search\" placeholder=\"Search types and fields\" autocomplete=\"off\">
+<ul id=\"results\"></ul>
+</header>
+<main>
+{}</main>
+<script src=\"search-index.js\"></script>
+<script src=\"search.js\"></script>
+</body>
+</html>
+",
+        escape(title),
+        body
+    )
+}
+
+fn index(site: &Site) -> String {
+    let mut body = String::from("<h1>Schema</h1>\n");
+    for (title, pages) in site.kinds() {
+        if pages.is_empty() {
+            continue;
+        }
+        let _ = writeln!(body, "<h2>{} ({})</h2>\n<ul>", title, pages.len());
+        for page in pages {
+            let _ = write!(
+                body,
+                "<li>{}",
+                link(&page.declaration.qualified_name(), &page.file)
+            );
+            if let Some(line) = page.declaration.doc.first() {
+                let _ = write!(body, " <span class=\"meta\">{}</span>", escape(line.trim()));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+    }
+    layout("Schema", &body)
+}
+
+fn used_by(site: &Site) -> String {
+    let mut body =
+        String::from("<h1>Used by</h1>\n<table>\n<tr><th>Type</th><th>Used by</th></tr>\n");
+    for page in &site.pages {
+        let users: Vec<String> = page
+            .used_by
+            .iter()
+            .map(|(name, file)| link(name, file))
+            .collect();
+        let _ = writeln!(
+            body,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            link(&page.declaration.qualified_name(), &page.file),
+            if users.is_empty() {
+                "<span class=\"meta\">Nothing</span>".to_string()
+            } else {
+                users.join(", ")
+            }
+        );
+    }
+    body.push_str("</table>\n");
+    layout("Used by", &body)
+}
+
+fn search_index(site: &Site) -> String {
+    let mut entries: Vec<Value> = Vec::new();
+    for page in &site.pages {
+        let qualified = page.declaration.qualified_name();
+        entries.push(json!({
+            "name": qualified,
+            "kind": page.declaration.kind.keyword(),
+            "page": format!("{}.html", page.file),
+        }));
+        let kind = match page.declaration.kind {
+            Kind::Enum(_, _) => "value",
+            Kind::Union(_) => "variant",
+            _ => "field",
+        };
+        for member in &page.members {
+            entries.push(json!({
+                "name": format!("{}.{}", qualified, member.name),
+                "kind": kind,
+                "page": format!("{}.html#{}", page.file, member.name),
+            }));
+        }
+    }
+    format!("const SEARCH_INDEX = {};\n", Value::Array(entries))
+}
+
+fn declaration(site: &Site, page: &Page) -> String {
+    let declaration = page.declaration;
+    let mut body = format!(
+        "<h1>{} {}</h1>\n",
+        declaration.kind.keyword(),
+        escape(&declaration.name)
+    );
+
+    let mut meta = Vec::new();
+    if !declaration.namespace.is_empty() {
+        meta.push(format!(
+            "namespace <code>{}</code>",
+            escape(&declaration.namespace)
+        ));
+    }
+    if let Some(underlying) = page.underlying {
+        meta.push(format!("<code>{}</code>", underlying));
+    }
+    if !page.attributes.is_empty() {
+        meta.push(format!("<code>{}</code>", escape(&page.attributes)));
+    }
+    if page.is_root {
+        meta.push("root type".to_string());
+    }
+    if !site.regions.is_empty() {
+        meta.push(format!("regions: {}", regions(&page.regions)));
+    }
+    if let Some(since) = page.since {
+        meta.push(format!("since {}", escape(since)));
+    }
+    if !meta.is_empty() {
+        let _ = writeln!(body, "<p class=\"meta\">{}</p>", meta.join(" · "));
+    }
+    body.push_str(&doc(&declaration.doc));
+
+    let (title, columns): (&str, &[&str]) = match declaration.kind {
+        Kind::Table(_) | Kind::Struct(_) => (
+            "Fields",
+            &["Name", "Type", "Default", "Attributes", "Description"],
+        ),
+        Kind::Enum(_, _) => ("Values", &["Name", "Value", "Description"]),
+        Kind::Union(_) => ("Variants", &["Name", "Table", "Description"]),
+    };
+    let _ = writeln!(body, "<h2>{}</h2>\n<table>", title);
+    body.push_str("<tr>");
+    for column in columns {
+        let _ = write!(body, "<th>{}</th>", column);
+    }
+    if !site.regions.is_empty() {
+        body.push_str("<th>Regions</th>");
+    }
+    if site.versions {
+        body.push_str("<th>Since</th>");
+    }
+    body.push_str("</tr>\n");
+    for member in &page.members {
+        body.push_str(&row(site, member, columns.len() == 5));
+    }
+    body.push_str("</table>\n");
+
+    body.push_str("<h2>Used by</h2>\n");
+    if page.used_by.is_empty() {
+        body.push_str("<p class=\"meta\">Nothing in the schema uses it.</p>\n");
+    } else {
+        body.push_str("<ul>\n");
+        for (name, file) in &page.used_by {
+            let _ = writeln!(body, "<li>{}</li>", link(name, file));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    layout(&declaration.qualified_name(), &body)
+}
+
+fn row(site: &Site, member: &Member, is_field: bool) -> String {
+    let name = escape(member.name);
+    let mut row = format!(
+        "<tr id=\"{}\"{}><td><a href=\"#{}\">{}</a></td><td class=\"type\">{}</td>",
+        name,
+        if member.deprecated {
+            " class=\"deprecated\""
+        } else {
+            ""
+        },
+        name,
+        name,
+        type_html(&member.ty)
+    );
+    if is_field {
+        let _ = write!(
+            row,
+            "<td>{}</td><td>{}</td>",
+            code(member.default.unwrap_or_default()),
+            code(&member.attributes)
+        );
+    }
+    let lines: Vec<String> = member.doc.iter().map(|line| escape(line.trim())).collect();
+    let _ = write!(row, "<td>{}</td>", lines.join("<br>"));
+    if !site.regions.is_empty() {
+        let _ = write!(row, "<td>{}</td>", regions(&member.regions));
+    }
+    if site.versions {
+        let _ = write!(row, "<td>{}</td>", escape(member.since.unwrap_or_default()));
+    }
+    row.push_str("</tr>\n");
+    row
+}
+
+fn type_html(parts: &[TypePart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            TypePart::Text(text) => escape(text),
+            TypePart::Link(text, file) => link(text, file),
+        })
+        .collect()
+}
+
+fn doc(lines: &[String]) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = lines.iter().map(|line| escape(line.trim())).collect();
+    format!("<p>{}</p>\n", lines.join("<br>\n"))
+}
+
+fn code(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("<code>{}</code>", escape(text))
+    }
+}
+
+fn regions(regions: &[&str]) -> String {
+    if regions.is_empty() {
+        "none".to_string()
+    } else {
+        escape(&regions.join(", "))
+    }
+}
+
+fn link(text: &str, file: &str) -> String {
+    format!("<a href=\"{}.html\">{}</a>", escape(file), escape(text))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
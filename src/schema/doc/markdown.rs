@@ -0,0 +1,190 @@
+use crate::schema::Kind;
+use crate::schema::doc::{DocFile, Member, Page, Site, TypePart};
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// `README.md`, `used-by.md` and a page per declaration, there is no search
+pub fn render(site: &Site) -> Vec<DocFile> {
+    let mut files = vec![
+        file("README.md", index(site)),
+        file("used-by.md", used_by(site)),
+    ];
+    for page in &site.pages {
+        files.push(file(&format!("{}.md", page.file), declaration(site, page)));
+    }
+    files
+}
+
+fn file(path: &str, content: String) -> DocFile {
+    DocFile {
+        path: PathBuf::from(path),
+        content,
+    }
+}
+
+fn index(site: &Site) -> String {
+    let mut output = String::from("# Schema\n\nSee also [Used by](used-by.md).\n");
+    for (title, pages) in site.kinds() {
+        if pages.is_empty() {
+            continue;
+        }
+        let _ = writeln!(output, "\n## {} ({})\n", title, pages.len());
+        for page in pages {
+            let _ = write!(
+                output,
+                "- {}",
+                link(&page.declaration.qualified_name(), &page.file)
+            );
+            if let Some(line) = page.declaration.doc.first() {
+                let _ = write!(output, ": {}", line.trim());
+            }
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn used_by(site: &Site) -> String {
+    let mut output = String::from("# Used by\n\n| Type | Used by |\n|------|---------|\n");
+    for page in &site.pages {
+        let users: Vec<String> = page
+            .used_by
+            .iter()
+            .map(|(name, file)| link(name, file))
+            .collect();
+        let _ = writeln!(
+            output,
+            "| {} | {} |",
+            link(&page.declaration.qualified_name(), &page.file),
+            users.join(", ")
+        );
+    }
+    output
+}
+
+fn declaration(site: &Site, page: &Page) -> String {
+    let declaration = page.declaration;
+    let mut output = format!(
+        "# {} {}\n\n[Index](README.md)\n",
+        declaration.kind.keyword(),
+        declaration.name
+    );
+
+    let mut meta = Vec::new();
+    if !declaration.namespace.is_empty() {
+        meta.push(format!("namespace `{}`", declaration.namespace));
+    }
+    if let Some(underlying) = page.underlying {
+        meta.push(format!("`{}`", underlying));
+    }
+    if !page.attributes.is_empty() {
+        meta.push(format!("`{}`", page.attributes));
+    }
+    if page.is_root {
+        meta.push("root type".to_string());
+    }
+    if !site.regions.is_empty() {
+        meta.push(format!("regions: {}", regions(&page.regions)));
+    }
+    if let Some(since) = page.since {
+        meta.push(format!("since {}", since));
+    }
+    if !meta.is_empty() {
+        let _ = writeln!(output, "\n{}", meta.join(" · "));
+    }
+    if !declaration.doc.is_empty() {
+        let lines: Vec<&str> = declaration.doc.iter().map(|line| line.trim()).collect();
+        let _ = writeln!(output, "\n{}", lines.join("\n"));
+    }
+
+    let (title, mut columns): (&str, Vec<&str>) = match declaration.kind {
+        Kind::Table(_) | Kind::Struct(_) => (
+            "Fields",
+            vec!["Name", "Type", "Default", "Attributes", "Description"],
+        ),
+        Kind::Enum(_, _) => ("Values", vec!["Name", "Value", "Description"]),
+        Kind::Union(_) => ("Variants", vec!["Name", "Table", "Description"]),
+    };
+    let is_field = columns.len() == 5;
+    if !site.regions.is_empty() {
+        columns.push("Regions");
+    }
+    if site.versions {
+        columns.push("Since");
+    }
+    let _ = writeln!(
+        output,
+        "\n## {}\n\n| {} |\n|{}|",
+        title,
+        columns.join(" | "),
+        vec!["---"; columns.len()].join("|")
+    );
+    for member in &page.members {
+        output.push_str(&row(site, member, is_field));
+    }
+
+    output.push_str("\n## Used by\n\n");
+    if page.used_by.is_empty() {
+        output.push_str("Nothing in the schema uses it.\n");
+    } else {
+        for (name, file) in &page.used_by {
+            let _ = writeln!(output, "- {}", link(name, file));
+        }
+    }
+    output
+}
+
+fn row(site: &Site, member: &Member, is_field: bool) -> String {
+    let name = if member.deprecated {
+        format!("~~{}~~", member.name)
+    } else {
+        member.name.to_string()
+    };
+    let mut cells = vec![name, type_markdown(&member.ty)];
+    if is_field {
+        cells.push(code(member.default.unwrap_or_default()));
+        cells.push(code(&member.attributes));
+    }
+    let lines: Vec<&str> = member.doc.iter().map(|line| line.trim()).collect();
+    cells.push(lines.join("<br>"));
+    if !site.regions.is_empty() {
+        cells.push(regions(&member.regions));
+    }
+    if site.versions {
+        cells.push(member.since.unwrap_or_default().to_string());
+    }
+
+    let cells: Vec<String> = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn type_markdown(parts: &[TypePart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            TypePart::Text(text) => text.replace('[', "\\[").replace(']', "\\]"),
+            TypePart::Link(text, file) => link(text, file),
+        })
+        .collect()
+}
+
+fn code(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+fn regions(regions: &[&str]) -> String {
+    if regions.is_empty() {
+        "none".to_string()
+    } else {
+        regions.join(", ")
+    }
+}
+
+fn link(text: &str, file: &str) -> String {
+    format!("[{}]({}.md)", text, file)
+}
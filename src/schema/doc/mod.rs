@@ -0,0 +1,309 @@
+pub mod html;
+pub mod markdown;
+
+use crate::schema::export::{sorted, target};
+use crate::schema::history::History;
+use crate::schema::writer::metadata;
+use crate::schema::{Declaration, Kind, Schema, Type};
+
+use clap::ValueEnum;
+use eyre::Result;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Page formats `bafb schema doc` writes
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq)]
+pub enum DocFormat {
+    /// HTML pages with a search box
+    #[default]
+    Html,
+    /// Markdown pages, e.g. for a repository wiki
+    Markdown,
+}
+
+impl fmt::Display for DocFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// A file of the site, relative to the output directory
+pub struct DocFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Other schemas the pages are annotated from, the regions that have each field and the version
+/// it first appeared in
+#[derive(Default)]
+pub struct Sources {
+    pub regions: Vec<(String, Schema)>,
+    pub history: Option<History>,
+}
+
+impl Sources {
+    fn regions(&self, qualified: &str, member: Option<&str>) -> Vec<&str> {
+        self.regions
+            .iter()
+            .filter(|(_, schema)| has(schema, qualified, member))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    fn since(&self, qualified: &str, member: Option<&str>) -> Option<&str> {
+        self.history
+            .as_ref()?
+            .first(|schema| has(schema, qualified, member))
+    }
+}
+
+/// Whether `schema` declares `qualified` with a field, enum value or union variant named `member`
+fn has(schema: &Schema, qualified: &str, member: Option<&str>) -> bool {
+    let Some(declaration) = schema.resolve(qualified, "") else {
+        return false;
+    };
+    let Some(member) = member else {
+        return true;
+    };
+    match &declaration.kind {
+        Kind::Table(fields) | Kind::Struct(fields) => {
+            fields.iter().any(|field| field.name == member)
+        }
+        Kind::Enum(_, values) => values.iter().any(|value| value.name == member),
+        Kind::Union(variants) => variants.iter().any(|variant| variant.name == member),
+    }
+}
+
+/// Part of a type as written on a page, declared types link to their page
+pub enum TypePart {
+    Text(String),
+    Link(String, String),
+}
+
+/// A field, enum value or union variant
+pub struct Member<'a> {
+    pub name: &'a str,
+    /// The type of a field or variant, the number of an enum value
+    pub ty: Vec<TypePart>,
+    pub default: Option<&'a str>,
+    pub attributes: String,
+    pub doc: &'a [String],
+    pub deprecated: bool,
+    pub regions: Vec<&'a str>,
+    pub since: Option<&'a str>,
+}
+
+/// The page of a declaration
+pub struct Page<'a> {
+    pub declaration: &'a Declaration,
+    pub file: String,
+    pub attributes: String,
+    /// The underlying type of an enum
+    pub underlying: Option<&'static str>,
+    pub is_root: bool,
+    pub members: Vec<Member<'a>>,
+    pub used_by: Vec<(String, String)>,
+    pub regions: Vec<&'a str>,
+    pub since: Option<&'a str>,
+}
+
+/// Every page of a schema, sorted by qualified name
+pub struct Site<'a> {
+    pub pages: Vec<Page<'a>>,
+    /// Names of the regions pages say have each declaration and member
+    pub regions: Vec<&'a str>,
+    /// Whether pages say the version each declaration and member first appeared in
+    pub versions: bool,
+}
+
+impl<'a> Site<'a> {
+    pub fn new(schema: &'a Schema, sources: &'a Sources) -> Result<Self> {
+        let mut used_by: HashMap<String, Vec<&Declaration>> = HashMap::new();
+        for declaration in sorted(schema) {
+            let mut targets = schema.references(declaration)?;
+            targets.sort_by_key(|target| target.qualified_name());
+            targets.dedup_by_key(|target| target.qualified_name());
+            for target in targets {
+                used_by
+                    .entry(target.qualified_name())
+                    .or_default()
+                    .push(declaration);
+            }
+        }
+
+        let root = schema
+            .root_type
+            .as_deref()
+            .and_then(|root| schema.resolve(root, ""))
+            .map(|root| root.qualified_name());
+
+        let pages = sorted(schema)
+            .into_iter()
+            .map(|declaration| {
+                let qualified = declaration.qualified_name();
+                let used_by = used_by
+                    .get(&qualified)
+                    .map(|users| {
+                        users
+                            .iter()
+                            .map(|user| (user.qualified_name(), file_of(user)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                Page {
+                    declaration,
+                    file: file_of(declaration),
+                    attributes: metadata(&declaration.attributes).trim().to_string(),
+                    underlying: match &declaration.kind {
+                        Kind::Enum(underlying, _) => Some(underlying.name()),
+                        _ => None,
+                    },
+                    is_root: root.as_ref() == Some(&qualified),
+                    members: members(schema, sources, declaration),
+                    used_by,
+                    regions: sources.regions(&qualified, None),
+                    since: sources.since(&qualified, None),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            pages,
+            regions: sources
+                .regions
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect(),
+            versions: sources.history.is_some(),
+        })
+    }
+
+    /// Writes the site in `format`
+    pub fn render(&self, format: DocFormat) -> Vec<DocFile> {
+        match format {
+            DocFormat::Html => html::render(self),
+            DocFormat::Markdown => markdown::render(self),
+        }
+    }
+
+    /// Pages of one kind of declaration, in the order the index lists them
+    pub fn kinds(&self) -> Vec<(&'static str, Vec<&Page<'a>>)> {
+        ["Tables", "Structs", "Enums", "Unions"]
+            .into_iter()
+            .map(|title| {
+                let pages = self
+                    .pages
+                    .iter()
+                    .filter(|page| {
+                        matches!(
+                            (title, &page.declaration.kind),
+                            ("Tables", Kind::Table(_))
+                                | ("Structs", Kind::Struct(_))
+                                | ("Enums", Kind::Enum(_, _))
+                                | ("Unions", Kind::Union(_))
+                        )
+                    })
+                    .collect();
+                (title, pages)
+            })
+            .collect()
+    }
+}
+
+/// `<qualified name>` without an extension, the renderers add theirs
+fn file_of(declaration: &Declaration) -> String {
+    declaration.qualified_name()
+}
+
+fn members<'a>(
+    schema: &'a Schema,
+    sources: &'a Sources,
+    declaration: &'a Declaration,
+) -> Vec<Member<'a>> {
+    let qualified = declaration.qualified_name();
+    let annotate = |name: &str| {
+        (
+            sources.regions(&qualified, Some(name)),
+            sources.since(&qualified, Some(name)),
+        )
+    };
+
+    match &declaration.kind {
+        Kind::Table(fields) | Kind::Struct(fields) => fields
+            .iter()
+            .map(|field| {
+                let (regions, since) = annotate(&field.name);
+                Member {
+                    name: &field.name,
+                    ty: type_parts(schema, declaration, &field.ty),
+                    default: field.default.as_deref(),
+                    attributes: metadata(&field.attributes).trim().to_string(),
+                    doc: &field.doc,
+                    deprecated: field.has_attribute("deprecated"),
+                    regions,
+                    since,
+                }
+            })
+            .collect(),
+        Kind::Enum(_, values) => values
+            .iter()
+            .map(|value| {
+                let (regions, since) = annotate(&value.name);
+                Member {
+                    name: &value.name,
+                    ty: vec![TypePart::Text(value.value.to_string())],
+                    default: None,
+                    attributes: String::new(),
+                    doc: &value.doc,
+                    deprecated: false,
+                    regions,
+                    since,
+                }
+            })
+            .collect(),
+        Kind::Union(variants) => variants
+            .iter()
+            .map(|variant| {
+                let (regions, since) = annotate(&variant.name);
+                let ty = Type::Named(variant.ty.clone());
+                Member {
+                    name: &variant.name,
+                    ty: type_parts(schema, declaration, &ty),
+                    default: None,
+                    attributes: String::new(),
+                    doc: &variant.doc,
+                    deprecated: false,
+                    regions,
+                    since,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn type_parts(schema: &Schema, owner: &Declaration, ty: &Type) -> Vec<TypePart> {
+    match ty {
+        Type::Vector(element) => {
+            let mut parts = vec![TypePart::Text("[".to_string())];
+            parts.extend(type_parts(schema, owner, element));
+            parts.push(TypePart::Text("]".to_string()));
+            parts
+        }
+        Type::Array(element, length) => {
+            let mut parts = vec![TypePart::Text("[".to_string())];
+            parts.extend(type_parts(schema, owner, element));
+            parts.push(TypePart::Text(format!(":{}]", length)));
+            parts
+        }
+        Type::Named(name) => match target(schema, owner, ty) {
+            Some(target) => vec![TypePart::Link(name.clone(), file_of(target))],
+            None => vec![TypePart::Text(name.clone())],
+        },
+        other => vec![TypePart::Text(other.to_string())],
+    }
+}
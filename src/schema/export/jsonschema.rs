@@ -0,0 +1,192 @@
+use crate::schema::export::{sorted, target};
+use crate::schema::parser::parse_integer;
+use crate::schema::{Declaration, Field, Kind, Scalar, Schema, Type};
+
+use eyre::Result;
+use serde_json::{Map, Value, json};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema of the JSON the serde support of the generated code writes, field names as in the
+/// schema, enums and union types by name, optional fields left out when they are not set
+pub fn export(schema: &Schema) -> Result<String> {
+    let mut definitions = Map::new();
+    for declaration in sorted(schema) {
+        definitions.insert(
+            declaration.qualified_name(),
+            definition(schema, declaration),
+        );
+    }
+
+    let mut document = Map::new();
+    document.insert("$schema".to_string(), json!(DRAFT));
+    if let Some(root) = schema
+        .root_type
+        .as_deref()
+        .and_then(|root| schema.resolve(root, ""))
+    {
+        document.insert(
+            "$ref".to_string(),
+            json!(format!("#/$defs/{}", root.qualified_name())),
+        );
+    }
+    document.insert("$defs".to_string(), Value::Object(definitions));
+
+    Ok(format!(
+        "{}\n",
+        serde_json::to_string_pretty(&Value::Object(document))?
+    ))
+}
+
+fn reference(declaration: &Declaration) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", declaration.qualified_name()) })
+}
+
+fn describe(mut value: Value, doc: &[String]) -> Value {
+    if !doc.is_empty()
+        && let Value::Object(object) = &mut value
+    {
+        let lines: Vec<&str> = doc.iter().map(|line| line.trim()).collect();
+        object.insert("description".to_string(), json!(lines.join("\n")));
+    }
+    value
+}
+
+fn definition(schema: &Schema, declaration: &Declaration) -> Value {
+    let value = match &declaration.kind {
+        // bit_flags values are combined, so they stay numbers
+        Kind::Enum(underlying, _) if declaration.has_attribute("bit_flags") => scalar(*underlying),
+        Kind::Enum(_, values) => json!({
+            "type": "string",
+            "enum": values.iter().map(|value| value.name.as_str()).collect::<Vec<_>>(),
+        }),
+        // The `<field>_type` companion of a union field
+        Kind::Union(variants) => {
+            let mut names = vec!["NONE"];
+            names.extend(variants.iter().map(|variant| variant.name.as_str()));
+            json!({ "type": "string", "enum": names })
+        }
+        Kind::Struct(fields) => object(schema, declaration, fields, true),
+        Kind::Table(fields) => object(schema, declaration, fields, false),
+    };
+    describe(value, &declaration.doc)
+}
+
+fn object(schema: &Schema, declaration: &Declaration, fields: &[Field], is_struct: bool) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in fields
+        .iter()
+        .filter(|field| !field.has_attribute("deprecated"))
+    {
+        let target = target(schema, declaration, &field.ty);
+
+        if let Some(union) = target.filter(|target| matches!(target.kind, Kind::Union(_))) {
+            let Kind::Union(variants) = &union.kind else {
+                unreachable!("not a union");
+            };
+            let type_field = format!("{}_type", field.name);
+            properties.insert(type_field.clone(), reference(union));
+            required.push(type_field);
+
+            let tables: Vec<Value> = variants
+                .iter()
+                .filter_map(|variant| schema.resolve(&variant.ty, &union.namespace))
+                .map(reference)
+                .collect();
+            properties.insert(
+                field.name.clone(),
+                describe(json!({ "anyOf": tables }), &field.doc),
+            );
+            continue;
+        }
+
+        let mut property = field_type(&field.ty, target);
+        if let (Some(default), Value::Object(object)) = (default(field, target), &mut property) {
+            object.insert("default".to_string(), default);
+        }
+        properties.insert(field.name.clone(), describe(property, &field.doc));
+
+        // Scalars are always written unless they are optional, anything else only when set
+        let is_scalar = match field.ty {
+            Type::Scalar(_) => true,
+            Type::Named(_) => target.is_some_and(|target| matches!(target.kind, Kind::Enum(_, _))),
+            _ => false,
+        };
+        let optional = field.default.as_deref() == Some("null");
+        if is_struct || (is_scalar && !optional) || field.has_attribute("required") {
+            required.push(field.name.clone());
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn field_type(ty: &Type, target: Option<&Declaration>) -> Value {
+    match ty {
+        Type::Scalar(scalar) => self::scalar(*scalar),
+        Type::String => json!({ "type": "string" }),
+        Type::Named(_) => target.map_or(json!({}), reference),
+        Type::Vector(element) => json!({ "type": "array", "items": field_type(element, target) }),
+        Type::Array(element, length) => json!({
+            "type": "array",
+            "items": field_type(element, target),
+            "minItems": length,
+            "maxItems": length,
+        }),
+    }
+}
+
+fn scalar(scalar: Scalar) -> Value {
+    match scalar {
+        Scalar::Bool => json!({ "type": "boolean" }),
+        Scalar::Float | Scalar::Double => json!({ "type": "number" }),
+        Scalar::ULong => json!({ "type": "integer", "minimum": 0, "maximum": u64::MAX }),
+        integer => {
+            let (minimum, maximum) = integer.range().unwrap_or_default();
+            json!({
+                "type": "integer",
+                "minimum": minimum as i64,
+                "maximum": maximum as i64,
+            })
+        }
+    }
+}
+
+/// The written default of a scalar or enum field, as it appears in the JSON
+fn default(field: &Field, target: Option<&Declaration>) -> Option<Value> {
+    let raw = field.default.as_deref().filter(|raw| *raw != "null")?;
+
+    match (&field.ty, target.map(|target| &target.kind)) {
+        (Type::Scalar(Scalar::Bool), _) => Some(json!(matches!(raw, "true" | "1"))),
+        (Type::Scalar(Scalar::Float | Scalar::Double), _) => raw
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(|value| json!(value)),
+        (Type::Scalar(Scalar::ULong), _) => parse_integer(raw).map(|value| json!(value as u64)),
+        (Type::Scalar(_), _) => parse_integer(raw).map(|value| json!(value)),
+        (Type::Named(_), Some(Kind::Enum(_, values))) => {
+            let found = match parse_integer(raw) {
+                Some(number) => values.iter().find(|value| value.value == number),
+                None => {
+                    let name = raw.rsplit('.').next().unwrap_or(raw);
+                    values.iter().find(|value| value.name == name)
+                }
+            };
+            let target = target?;
+            if target.has_attribute("bit_flags") {
+                found.map(|value| json!(value.value))
+            } else {
+                found.map(|value| json!(value.name))
+            }
+        }
+        _ => None,
+    }
+}
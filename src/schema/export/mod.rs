@@ -0,0 +1,173 @@
+pub mod jsonschema;
+pub mod proto;
+pub mod sql;
+
+use crate::schema::{Declaration, Field, Kind, Schema, Type};
+use crate::tools::serve;
+
+use clap::ValueEnum;
+use eyre::{Result, eyre};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Formats `bafb schema export` writes a schema as
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum ExportFormat {
+    /// JSON Schema (draft 2020-12) describing the decoded JSON of every type
+    #[value(name = "jsonschema")]
+    JsonSchema,
+    /// proto3 messages, enums and `oneof` unions with field numbers from the FlatBuffers ids
+    Proto,
+    /// SQLite tables for the Excel row types with lookup tables for enums and unions
+    Sqlite,
+    /// PostgreSQL tables for the Excel row types with lookup tables for enums and unions
+    Postgres,
+    /// GraphQL SDL of the types and queries `bafb serve` answers
+    Graphql,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::JsonSchema => "schema.json",
+            ExportFormat::Proto => "proto",
+            ExportFormat::Sqlite => "sqlite.sql",
+            ExportFormat::Postgres => "postgres.sql",
+            ExportFormat::Graphql => "graphql",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Writes a checked `schema` in `format`
+pub fn export(schema: &Schema, format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::JsonSchema => jsonschema::export(schema),
+        ExportFormat::Proto => proto::export(schema),
+        ExportFormat::Sqlite => sql::export(schema, sql::Dialect::Sqlite),
+        ExportFormat::Postgres => sql::export(schema, sql::Dialect::Postgres),
+        ExportFormat::Graphql => serve::graphql::sdl(schema),
+    }
+}
+
+/// `<schema>.<extension>` next to `fbs`
+pub fn output_path(fbs: &Path, format: ExportFormat) -> PathBuf {
+    let stem = fbs
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    fbs.with_file_name(format!("{}.{}", stem, format.extension()))
+}
+
+/// Declarations sorted by qualified name, so exports only change when the schema does
+pub fn sorted(schema: &Schema) -> Vec<&Declaration> {
+    let mut declarations: Vec<&Declaration> = schema.declarations.iter().collect();
+    declarations.sort_by_key(|declaration| declaration.qualified_name());
+    declarations
+}
+
+/// The declaration a named field type refers to, `schema` is expected to be checked
+pub fn target<'a>(schema: &'a Schema, owner: &Declaration, ty: &Type) -> Option<&'a Declaration> {
+    match ty.element() {
+        Type::Named(name) => schema.resolve(name, &owner.namespace),
+        _ => None,
+    }
+}
+
+/// Excel row types, the tables named `*Excel`, sorted by qualified name
+pub fn rows(schema: &Schema) -> Vec<&Declaration> {
+    sorted(schema)
+        .into_iter()
+        .filter(|declaration| {
+            matches!(declaration.kind, Kind::Table(_)) && declaration.name.ends_with("Excel")
+        })
+        .collect()
+}
+
+/// Declaration names for languages with a single scope, the namespace every declaration shares is
+/// the package and the rest of a namespace is kept as a `_` separated prefix
+pub struct Names {
+    pub package: String,
+    names: HashMap<String, String>,
+}
+
+impl Names {
+    pub fn new(schema: &Schema) -> Result<Self> {
+        let package = Self::package(schema);
+        let depth = package.split('.').filter(|part| !part.is_empty()).count();
+
+        let mut names = HashMap::new();
+        let mut taken: HashMap<String, String> = HashMap::new();
+        for declaration in &schema.declarations {
+            let mut parts: Vec<&str> = declaration
+                .namespace
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .skip(depth)
+                .collect();
+            parts.push(&declaration.name);
+            let name = parts.join("_");
+
+            let qualified = declaration.qualified_name();
+            if let Some(other) = taken.insert(name.clone(), qualified.clone()) {
+                return Err(eyre!(
+                    "{} and {} would both be named {}",
+                    other,
+                    qualified,
+                    name
+                ));
+            }
+            names.insert(qualified, name);
+        }
+
+        Ok(Self { package, names })
+    }
+
+    /// The longest namespace all declarations are in
+    fn package(schema: &Schema) -> String {
+        let mut common: Option<Vec<&str>> = None;
+        for declaration in &schema.declarations {
+            let parts: Vec<&str> = declaration
+                .namespace
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .collect();
+            common = Some(match common {
+                None => parts,
+                Some(common) => common
+                    .into_iter()
+                    .zip(parts)
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+        common.unwrap_or_default().join(".")
+    }
+
+    pub fn get(&self, declaration: &Declaration) -> &str {
+        &self.names[&declaration.qualified_name()]
+    }
+}
+
+/// The field rows are looked up by, the `key` attribute or a field named `Id`
+pub fn key(declaration: &Declaration) -> Option<&Field> {
+    let fields = declaration
+        .fields()
+        .iter()
+        .filter(|field| !field.has_attribute("deprecated"));
+    fields
+        .clone()
+        .find(|field| field.has_attribute("key"))
+        .or_else(|| fields.clone().find(|field| field.name == "Id"))
+        .filter(|field| matches!(field.ty, Type::Scalar(_) | Type::String | Type::Named(_)))
+}
@@ -0,0 +1,279 @@
+use crate::codegen::to_screaming_snake_case;
+use crate::schema::export::{Names, sorted, target};
+use crate::schema::{Declaration, Field, Kind, Scalar, Schema, Type};
+
+use eyre::{Result, eyre};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const HEADER: &str = "// automatically generated by bafb, do not modify\n";
+
+/// proto3 definitions of `schema`, one message per table, struct and union and one enum per enum,
+/// in a single package named after the namespace every declaration shares
+pub fn export(schema: &Schema) -> Result<String> {
+    let exporter = ProtoExporter::new(schema)?;
+
+    let mut output = String::from(HEADER);
+    output.push_str("syntax = \"proto3\";\n");
+    if !exporter.package.is_empty() {
+        let _ = writeln!(output, "\npackage {};", exporter.package);
+    }
+
+    for declaration in sorted(schema) {
+        let code = match &declaration.kind {
+            Kind::Enum(_, _) if !exporter.is_enum(declaration) => continue,
+            Kind::Enum(_, _) => exporter.enumeration(declaration)?,
+            Kind::Union(_) => exporter.union(declaration),
+            Kind::Table(fields) | Kind::Struct(fields) => exporter.message(declaration, fields)?,
+        };
+        output.push('\n');
+        output.push_str(&code);
+    }
+
+    Ok(output)
+}
+
+struct ProtoExporter<'a> {
+    schema: &'a Schema,
+    package: String,
+    names: Names,
+}
+
+impl<'a> ProtoExporter<'a> {
+    fn new(schema: &'a Schema) -> Result<Self> {
+        let names = Names::new(schema)?;
+        Ok(Self {
+            schema,
+            package: names.package.clone(),
+            names,
+        })
+    }
+
+    fn name(&self, declaration: &Declaration) -> &str {
+        self.names.get(declaration)
+    }
+
+    /// proto enums are 32 bit and cannot combine values, other enums are written as numbers
+    fn is_enum(&self, declaration: &Declaration) -> bool {
+        let Kind::Enum(underlying, values) = &declaration.kind else {
+            return false;
+        };
+        !declaration.has_attribute("bit_flags")
+            && values.iter().all(|value| {
+                let number = match underlying {
+                    Scalar::ULong => i128::from(value.value as u64),
+                    _ => i128::from(value.value),
+                };
+                i32::try_from(number).is_ok()
+            })
+    }
+
+    fn doc(output: &mut String, doc: &[String], indent: &str) {
+        for line in doc {
+            let _ = writeln!(output, "{}//{}", indent, line);
+        }
+    }
+
+    /// Values are prefixed with the enum name since they share the package scope, the zero value
+    /// comes first as proto3 requires and is added when the enum has none
+    fn enumeration(&self, declaration: &Declaration) -> Result<String> {
+        let Kind::Enum(_, values) = &declaration.kind else {
+            unreachable!("not an enum");
+        };
+        let name = self.name(declaration);
+        let prefix = to_screaming_snake_case(name);
+
+        let mut output = String::new();
+        Self::doc(&mut output, &declaration.doc, "");
+        let _ = writeln!(output, "enum {} {{", name);
+
+        let mut numbers = HashSet::new();
+        if values.iter().any(|value| !numbers.insert(value.value)) {
+            output.push_str("  option allow_alias = true;\n");
+        }
+
+        let mut ordered: Vec<_> = values.iter().collect();
+        ordered.sort_by_key(|value| value.value != 0);
+        let mut written = HashSet::new();
+        if !numbers.contains(&0) {
+            let _ = writeln!(output, "  {}_UNSPECIFIED = 0;", prefix);
+            written.insert(format!("{}_UNSPECIFIED", prefix));
+        }
+        for value in ordered {
+            let member = format!("{}_{}", prefix, to_screaming_snake_case(&value.name));
+            if !written.insert(member.clone()) {
+                return Err(eyre!(
+                    "{}.{} would be written as {} twice",
+                    declaration.qualified_name(),
+                    value.name,
+                    member
+                ));
+            }
+            Self::doc(&mut output, &value.doc, "  ");
+            let _ = writeln!(output, "  {} = {};", member, value.value);
+        }
+
+        output.push_str("}\n");
+        Ok(output)
+    }
+
+    /// A message around a `oneof`, numbered by the union discriminants
+    fn union(&self, declaration: &Declaration) -> String {
+        let Kind::Union(variants) = &declaration.kind else {
+            unreachable!("not a union");
+        };
+
+        let mut output = String::new();
+        Self::doc(&mut output, &declaration.doc, "");
+        let _ = writeln!(output, "message {} {{", self.name(declaration));
+        output.push_str("  oneof value {\n");
+        for variant in variants {
+            let table = self
+                .schema
+                .resolve(&variant.ty, &declaration.namespace)
+                .map_or(variant.ty.as_str(), |table| self.name(table));
+            Self::doc(&mut output, &variant.doc, "    ");
+            let _ = writeln!(
+                output,
+                "    {} {} = {};",
+                table, variant.name, variant.value
+            );
+        }
+        output.push_str("  }\n}\n");
+        output
+    }
+
+    /// FlatBuffers field ids, explicit `id` attributes or the field order with unions taking two
+    /// slots, their type comes first
+    fn ids(&self, declaration: &Declaration, fields: &[Field]) -> Result<Vec<u32>> {
+        if fields.iter().any(|field| field.has_attribute("id")) {
+            return fields
+                .iter()
+                .map(|field| {
+                    field
+                        .attribute("id")
+                        .and_then(|id| id.value.as_deref())
+                        .and_then(|id| id.parse().ok())
+                        .ok_or_else(|| {
+                            eyre!(
+                                "{}.{} has no valid id while other fields do",
+                                declaration.qualified_name(),
+                                field.name
+                            )
+                        })
+                })
+                .collect();
+        }
+
+        let mut next = 0;
+        Ok(fields
+            .iter()
+            .map(|field| {
+                if self.is_union(declaration, &field.ty) {
+                    next += 1;
+                }
+                next += 1;
+                next - 1
+            })
+            .collect())
+    }
+
+    /// Field numbers are the FlatBuffers ids plus one, so they only change when the table does
+    fn message(&self, declaration: &Declaration, fields: &[Field]) -> Result<String> {
+        let ids = self.ids(declaration, fields)?;
+
+        let mut output = String::new();
+        let mut wrappers = String::new();
+        let mut reserved = Vec::new();
+        for (field, id) in fields.iter().zip(ids) {
+            let number = id + 1;
+            if field.has_attribute("deprecated") {
+                reserved.push((number, &field.name));
+                continue;
+            }
+
+            let (label, ty) = match &field.ty {
+                Type::Vector(element) | Type::Array(element, _) => {
+                    match element.as_ref() {
+                        // Lists of lists need a message around the inner list
+                        Type::Vector(inner) | Type::Array(inner, _) => {
+                            let wrapper = format!("{}Entry", field.name);
+                            let _ = writeln!(
+                                wrappers,
+                                "  message {} {{\n    repeated {} values = 1;\n  }}",
+                                wrapper,
+                                self.element_type(declaration, inner)
+                            );
+                            ("repeated ", wrapper)
+                        }
+                        _ => ("repeated ", self.element_type(declaration, element)),
+                    }
+                }
+                ty => {
+                    let optional = field.default.as_deref() == Some("null");
+                    (
+                        if optional { "optional " } else { "" },
+                        self.element_type(declaration, ty),
+                    )
+                }
+            };
+
+            Self::doc(&mut output, &field.doc, "  ");
+            let _ = writeln!(output, "  {}{} {} = {};", label, ty, field.name, number);
+        }
+
+        let mut message = String::new();
+        Self::doc(&mut message, &declaration.doc, "");
+        let _ = writeln!(message, "message {} {{", self.name(declaration));
+        message.push_str(&wrappers);
+        if !reserved.is_empty() {
+            let numbers: Vec<String> = reserved.iter().map(|(n, _)| n.to_string()).collect();
+            let names: Vec<String> = reserved
+                .iter()
+                .map(|(_, name)| format!("\"{}\"", name))
+                .collect();
+            let _ = writeln!(message, "  reserved {};", numbers.join(", "));
+            let _ = writeln!(message, "  reserved {};", names.join(", "));
+        }
+        message.push_str(&output);
+        message.push_str("}\n");
+        Ok(message)
+    }
+
+    fn is_union(&self, declaration: &Declaration, ty: &Type) -> bool {
+        matches!(ty, Type::Named(_))
+            && target(self.schema, declaration, ty)
+                .is_some_and(|target| matches!(target.kind, Kind::Union(_)))
+    }
+
+    fn element_type(&self, declaration: &Declaration, ty: &Type) -> String {
+        match ty {
+            Type::Scalar(scalar) => Self::scalar(*scalar).to_string(),
+            Type::String => "string".to_string(),
+            Type::Named(_) => match target(self.schema, declaration, ty) {
+                Some(target) => match &target.kind {
+                    Kind::Enum(underlying, _) if !self.is_enum(target) => {
+                        Self::scalar(*underlying).to_string()
+                    }
+                    _ => self.name(target).to_string(),
+                },
+                None => ty.to_string(),
+            },
+            Type::Vector(element) | Type::Array(element, _) => {
+                self.element_type(declaration, element)
+            }
+        }
+    }
+
+    fn scalar(scalar: Scalar) -> &'static str {
+        match scalar {
+            Scalar::Bool => "bool",
+            Scalar::Byte | Scalar::Short | Scalar::Int => "int32",
+            Scalar::UByte | Scalar::UShort | Scalar::UInt => "uint32",
+            Scalar::Long => "int64",
+            Scalar::ULong => "uint64",
+            Scalar::Float => "float",
+            Scalar::Double => "double",
+        }
+    }
+}
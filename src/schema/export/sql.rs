@@ -0,0 +1,542 @@
+use crate::schema::export::{Names, rows, sorted, target};
+use crate::schema::{Declaration, Field, Kind, Scalar, Schema, Type};
+
+use clap::ValueEnum;
+use eyre::{Result, eyre};
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+const HEADER: &str = "-- automatically generated by bafb, do not modify\n";
+
+/// Column of the row id, and of the parent row id in child tables
+pub const ROW: &str = "_row";
+/// Column of the element position in child tables
+pub const INDEX: &str = "_index";
+
+/// SQL dialects tables are written for
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// `CREATE TABLE` statements for the Excel row types of `schema`, their lookup tables first
+pub fn export(schema: &Schema, dialect: Dialect) -> Result<String> {
+    Ok(Database::new(schema)?.ddl(dialect))
+}
+
+/// What a column holds and how it is read from the decoded JSON
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnKind {
+    /// The row id, or the parent row id in a child table
+    Row,
+    /// Position of the element in a child table
+    Index,
+    Scalar(Scalar),
+    String,
+    /// Name of an enum value or union type, checked against the named lookup table
+    Name(String),
+    /// Tables, unions and nested lists, as JSON text
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    /// Keys leading to the value, from the row or from the element in a child table
+    pub path: Vec<String>,
+    pub kind: ColumnKind,
+    pub not_null: bool,
+}
+
+/// A table of Excel rows, or a child table with the elements of a list field of them
+#[derive(Debug, Clone)]
+pub struct Relation {
+    pub name: String,
+    /// Qualified name of the row type
+    pub row: String,
+    /// The list field the elements come from, for child tables
+    pub field: Option<String>,
+    pub columns: Vec<Column>,
+    pub doc: Vec<String>,
+}
+
+/// Names and values of an enum or union
+#[derive(Debug, Clone)]
+pub struct Lookup {
+    pub name: String,
+    pub underlying: Scalar,
+    pub values: Vec<(String, i64)>,
+    pub doc: Vec<String>,
+}
+
+impl Lookup {
+    /// The name of `value`, if it is part of the lookup
+    pub fn name_of(&self, value: i64) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(_, number)| *number == value)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// The relational shape of the Excel row types of a schema
+#[derive(Debug, Clone)]
+pub struct Database {
+    pub lookups: Vec<Lookup>,
+    pub relations: Vec<Relation>,
+}
+
+impl Database {
+    pub fn new(schema: &Schema) -> Result<Self> {
+        let names = Names::new(schema)?;
+        let mut builder = Builder {
+            schema,
+            names: &names,
+            database: Database {
+                lookups: Vec::new(),
+                relations: Vec::new(),
+            },
+        };
+
+        for declaration in sorted(schema) {
+            builder.lookup(declaration);
+        }
+        for row in rows(schema) {
+            builder.row(row)?;
+        }
+
+        let database = builder.database;
+        database.check()?;
+        Ok(database)
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&Lookup> {
+        self.lookups.iter().find(|lookup| lookup.name == name)
+    }
+
+    /// SQLite compares table and column names case insensitively
+    fn check(&self) -> Result<()> {
+        let mut tables = HashMap::new();
+        let relations = self.relations.iter().map(|relation| &relation.name);
+        for name in self
+            .lookups
+            .iter()
+            .map(|lookup| &lookup.name)
+            .chain(relations)
+        {
+            if let Some(other) = tables.insert(name.to_lowercase(), name) {
+                return Err(eyre!("Tables {} and {} would clash", other, name));
+            }
+        }
+
+        for relation in &self.relations {
+            let mut columns = HashMap::new();
+            for column in &relation.columns {
+                if let Some(other) = columns.insert(column.name.to_lowercase(), &column.name) {
+                    return Err(eyre!(
+                        "Columns {} and {} of {} would clash",
+                        other,
+                        column.name,
+                        relation.name
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn ddl(&self, dialect: Dialect) -> String {
+        let mut output = String::from(HEADER);
+
+        for lookup in &self.lookups {
+            output.push('\n');
+            doc(&mut output, &lookup.doc);
+            let _ = writeln!(
+                output,
+                "CREATE TABLE {} (\n    \"name\" TEXT PRIMARY KEY,\n    \"value\" {} NOT NULL\n);",
+                quote(&lookup.name),
+                column_type(&ColumnKind::Scalar(lookup.underlying), dialect)
+            );
+            if lookup.values.is_empty() {
+                continue;
+            }
+
+            let values: Vec<String> = lookup
+                .values
+                .iter()
+                .map(|(name, value)| {
+                    let value = match lookup.underlying {
+                        Scalar::ULong => SqlValue::Unsigned(*value as u64),
+                        _ => SqlValue::Integer(*value),
+                    };
+                    format!(
+                        "    ({}, {})",
+                        literal(&name.clone().into(), dialect),
+                        literal(&value, dialect)
+                    )
+                })
+                .collect();
+            let _ = writeln!(
+                output,
+                "INSERT INTO {} (\"name\", \"value\") VALUES\n{};",
+                quote(&lookup.name),
+                values.join(",\n")
+            );
+        }
+
+        for relation in &self.relations {
+            output.push('\n');
+            doc(&mut output, &relation.doc);
+            let parent = self
+                .relations
+                .iter()
+                .find(|parent| parent.row == relation.row && parent.field.is_none());
+
+            let mut lines: Vec<String> = relation
+                .columns
+                .iter()
+                .map(|column| {
+                    let mut line = format!(
+                        "    {} {}",
+                        quote(&column.name),
+                        column_type(&column.kind, dialect)
+                    );
+                    match &column.kind {
+                        ColumnKind::Row if relation.field.is_none() => {
+                            line.push_str(" PRIMARY KEY");
+                            return line;
+                        }
+                        _ if column.not_null => line.push_str(" NOT NULL"),
+                        _ => {}
+                    }
+                    match (&column.kind, parent) {
+                        (ColumnKind::Row, Some(parent)) => {
+                            let _ = write!(
+                                line,
+                                " REFERENCES {} ({})",
+                                quote(&parent.name),
+                                quote(ROW)
+                            );
+                        }
+                        (ColumnKind::Name(lookup), _) => {
+                            let _ = write!(line, " REFERENCES {} (\"name\")", quote(lookup));
+                        }
+                        _ => {}
+                    }
+                    line
+                })
+                .collect();
+            if relation.field.is_some() {
+                lines.push(format!(
+                    "    PRIMARY KEY ({}, {})",
+                    quote(ROW),
+                    quote(INDEX)
+                ));
+            }
+
+            let _ = writeln!(
+                output,
+                "CREATE TABLE {} (\n{}\n);",
+                quote(&relation.name),
+                lines.join(",\n")
+            );
+        }
+
+        output
+    }
+}
+
+struct Builder<'a> {
+    schema: &'a Schema,
+    names: &'a Names,
+    database: Database,
+}
+
+impl Builder<'_> {
+    /// Enums and unions, whose columns hold value names, get a table of their values
+    fn lookup(&mut self, declaration: &Declaration) {
+        let (underlying, values) = match &declaration.kind {
+            Kind::Enum(underlying, values) => (
+                *underlying,
+                values
+                    .iter()
+                    .map(|value| (value.name.clone(), value.value))
+                    .collect(),
+            ),
+            Kind::Union(variants) => {
+                let mut values = vec![("NONE".to_string(), 0)];
+                values.extend(
+                    variants
+                        .iter()
+                        .map(|variant| (variant.name.clone(), variant.value)),
+                );
+                (Scalar::UByte, values)
+            }
+            _ => return,
+        };
+
+        self.database.lookups.push(Lookup {
+            name: self.names.get(declaration).to_string(),
+            underlying,
+            values,
+            doc: declaration.doc.clone(),
+        });
+    }
+
+    fn row(&mut self, row: &Declaration) -> Result<()> {
+        let name = self.names.get(row).to_string();
+        let mut columns = vec![Column {
+            name: ROW.to_string(),
+            path: Vec::new(),
+            kind: ColumnKind::Row,
+            not_null: true,
+        }];
+        let mut children = Vec::new();
+
+        for field in row
+            .fields()
+            .iter()
+            .filter(|field| !field.has_attribute("deprecated"))
+        {
+            let target = target(self.schema, row, &field.ty);
+            let not_null = field.has_attribute("required")
+                || (field.default.as_deref() != Some("null") && self.is_scalar(&field.ty, target));
+
+            match (&field.ty, target.map(|target| &target.kind)) {
+                (Type::Named(_), Some(Kind::Union(_))) => {
+                    let union = target.expect("union target");
+                    columns.push(Column {
+                        name: format!("{}_type", field.name),
+                        path: vec![format!("{}_type", field.name)],
+                        kind: ColumnKind::Name(self.names.get(union).to_string()),
+                        not_null: false,
+                    });
+                    columns.push(self.column(
+                        &field.name,
+                        vec![field.name.clone()],
+                        ColumnKind::Json,
+                        false,
+                    ));
+                }
+                (Type::Vector(element) | Type::Array(element, _), _)
+                    if self.is_element(element, target) =>
+                {
+                    let mut child = vec![
+                        Column {
+                            name: ROW.to_string(),
+                            path: Vec::new(),
+                            kind: ColumnKind::Row,
+                            not_null: true,
+                        },
+                        Column {
+                            name: INDEX.to_string(),
+                            path: Vec::new(),
+                            kind: ColumnKind::Index,
+                            not_null: true,
+                        },
+                    ];
+                    self.columns(&mut child, row, "value", Vec::new(), element, true);
+                    children.push(Relation {
+                        name: format!("{}_{}", name, field.name),
+                        row: row.qualified_name(),
+                        field: Some(field.name.clone()),
+                        columns: child,
+                        doc: field.doc.clone(),
+                    });
+                }
+                _ => self.columns(
+                    &mut columns,
+                    row,
+                    &field.name,
+                    vec![field.name.clone()],
+                    &field.ty,
+                    not_null,
+                ),
+            }
+        }
+
+        self.database.relations.push(Relation {
+            name,
+            row: row.qualified_name(),
+            field: None,
+            columns,
+            doc: row.doc.clone(),
+        });
+        self.database.relations.extend(children);
+        Ok(())
+    }
+
+    fn column(&self, name: &str, path: Vec<String>, kind: ColumnKind, not_null: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            path,
+            kind,
+            not_null,
+        }
+    }
+
+    /// Columns of a value, structs are flattened into a column per field named `<field>_<member>`
+    fn columns(
+        &self,
+        columns: &mut Vec<Column>,
+        owner: &Declaration,
+        name: &str,
+        path: Vec<String>,
+        ty: &Type,
+        not_null: bool,
+    ) {
+        let target = target(self.schema, owner, ty);
+        let kind = match (ty, target.map(|target| &target.kind)) {
+            (Type::Scalar(scalar), _) => ColumnKind::Scalar(*scalar),
+            (Type::String, _) => ColumnKind::String,
+            (Type::Named(_), Some(Kind::Enum(underlying, _))) => {
+                let target = target.expect("enum target");
+                if target.has_attribute("bit_flags") {
+                    ColumnKind::Scalar(*underlying)
+                } else {
+                    ColumnKind::Name(self.names.get(target).to_string())
+                }
+            }
+            (Type::Named(_), Some(Kind::Struct(fields))) => {
+                let target = target.expect("struct target");
+                for member in fields {
+                    let mut member_path = path.clone();
+                    member_path.push(member.name.clone());
+                    // Elements of child tables are structs themselves, their members need no prefix
+                    let member_name = if path.is_empty() {
+                        member.name.clone()
+                    } else {
+                        format!("{}_{}", name, member.name)
+                    };
+                    self.columns(
+                        columns,
+                        target,
+                        &member_name,
+                        member_path,
+                        &member.ty,
+                        not_null,
+                    );
+                }
+                return;
+            }
+            _ => ColumnKind::Json,
+        };
+        columns.push(self.column(name, path, kind, not_null));
+    }
+
+    fn is_scalar(&self, ty: &Type, target: Option<&Declaration>) -> bool {
+        match ty {
+            Type::Scalar(_) => true,
+            Type::Named(_) => target.is_some_and(|target| matches!(target.kind, Kind::Enum(_, _))),
+            _ => false,
+        }
+    }
+
+    /// Lists of scalars, strings, enums and structs get a child table, other lists are JSON
+    fn is_element(&self, element: &Type, target: Option<&Declaration>) -> bool {
+        match element {
+            Type::Scalar(_) | Type::String => true,
+            Type::Named(_) => target
+                .is_some_and(|target| matches!(target.kind, Kind::Enum(_, _) | Kind::Struct(_))),
+            _ => false,
+        }
+    }
+}
+
+/// A value of a column, as it is bound or written in a script
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Real(f64),
+    Text(String),
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        SqlValue::Text(value)
+    }
+}
+
+/// SQLite has no unsigned 64 bit integers, `ulong` columns are text there
+pub fn column_type(kind: &ColumnKind, dialect: Dialect) -> &'static str {
+    match (dialect, kind) {
+        (Dialect::Sqlite, ColumnKind::Row | ColumnKind::Index) => "INTEGER",
+        (Dialect::Sqlite, ColumnKind::Scalar(Scalar::ULong)) => "TEXT",
+        (Dialect::Sqlite, ColumnKind::Scalar(Scalar::Float | Scalar::Double)) => "REAL",
+        (Dialect::Sqlite, ColumnKind::Scalar(_)) => "INTEGER",
+        (Dialect::Sqlite, _) => "TEXT",
+        (Dialect::Postgres, ColumnKind::Row) => "BIGINT",
+        (Dialect::Postgres, ColumnKind::Index) => "INTEGER",
+        (Dialect::Postgres, ColumnKind::Scalar(scalar)) => match scalar {
+            Scalar::Bool => "BOOLEAN",
+            Scalar::Byte | Scalar::UByte | Scalar::Short => "SMALLINT",
+            Scalar::UShort | Scalar::Int => "INTEGER",
+            Scalar::UInt | Scalar::Long => "BIGINT",
+            Scalar::ULong => "NUMERIC(20)",
+            Scalar::Float => "REAL",
+            Scalar::Double => "DOUBLE PRECISION",
+        },
+        (Dialect::Postgres, ColumnKind::Json) => "JSONB",
+        (Dialect::Postgres, _) => "TEXT",
+    }
+}
+
+pub fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+pub fn literal(value: &SqlValue, dialect: Dialect) -> String {
+    match value {
+        SqlValue::Null => "NULL".to_string(),
+        SqlValue::Bool(value) => match dialect {
+            Dialect::Sqlite => (*value as u8).to_string(),
+            Dialect::Postgres => value.to_string().to_uppercase(),
+        },
+        SqlValue::Integer(value) => value.to_string(),
+        SqlValue::Unsigned(value) => match dialect {
+            Dialect::Sqlite => format!("'{}'", value),
+            Dialect::Postgres => value.to_string(),
+        },
+        SqlValue::Real(value) if value.is_finite() => format!("{:?}", value),
+        SqlValue::Real(_) => "NULL".to_string(),
+        SqlValue::Text(value) => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+fn doc(output: &mut String, doc: &[String]) {
+    for line in doc {
+        let _ = writeln!(output, "--{}", line);
+    }
+}
+
+/// Field names a decoded row of `row` may have, union fields come with a `<field>_type`
+pub fn keys(schema: &Schema, row: &Declaration) -> Vec<String> {
+    let mut keys = Vec::new();
+    for field in row.fields() {
+        if is_union(schema, row, field) {
+            keys.push(format!("{}_type", field.name));
+        }
+        keys.push(field.name.clone());
+    }
+    keys
+}
+
+fn is_union(schema: &Schema, row: &Declaration, field: &Field) -> bool {
+    matches!(field.ty, Type::Named(_))
+        && target(schema, row, &field.ty)
+            .is_some_and(|target| matches!(target.kind, Kind::Union(_)))
+}
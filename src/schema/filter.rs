@@ -0,0 +1,99 @@
+use crate::schema::{Declaration, Kind, Schema};
+
+use eyre::{Context, Result, eyre};
+use glob::Pattern;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Keeps the tables matching `--include`/`--exclude` and every type they reference
+#[derive(Debug, Clone, Default)]
+pub struct SchemaFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl SchemaFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<Pattern>> {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Pattern::new(pattern)
+                        .wrap_err_with(|| format!("Invalid table pattern `{}`", pattern))
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Patterns match either the bare or the namespace qualified table name
+    fn matches(patterns: &[Pattern], declaration: &Declaration) -> bool {
+        let qualified = declaration.qualified_name();
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches(&declaration.name) || pattern.matches(&qualified))
+    }
+
+    /// Tables the filtered schema is built around
+    pub fn roots<'a>(&self, schema: &'a Schema) -> Vec<&'a Declaration> {
+        schema
+            .declarations
+            .iter()
+            .filter(|declaration| matches!(declaration.kind, Kind::Table(_)))
+            .filter(|declaration| {
+                self.include.is_empty() || Self::matches(&self.include, declaration)
+            })
+            .filter(|declaration| !Self::matches(&self.exclude, declaration))
+            .collect()
+    }
+
+    /// The root tables and their transitive references in schema order, a referenced type is
+    /// kept even when it is excluded so the result stays valid
+    pub fn apply(&self, schema: &Schema) -> Result<Schema> {
+        let roots = self.roots(schema);
+        if roots.is_empty() {
+            return Err(eyre!("No table matches the include and exclude patterns"));
+        }
+
+        let mut kept: HashSet<String> = HashSet::new();
+        let mut pending = roots;
+        while let Some(declaration) = pending.pop() {
+            if !kept.insert(declaration.qualified_name()) {
+                continue;
+            }
+
+            pending.extend(schema.references(declaration)?);
+        }
+
+        let mut filtered = schema.clone();
+        filtered
+            .declarations
+            .retain(|declaration| kept.contains(&declaration.qualified_name()));
+        if let Some(root_type) = &schema.root_type
+            && !schema
+                .resolve(root_type, "")
+                .is_some_and(|root| kept.contains(&root.qualified_name()))
+        {
+            filtered.root_type = None;
+        }
+
+        Ok(filtered)
+    }
+
+    /// Where the filtered copy of `fbs` is written, next to it
+    pub fn output_path(fbs: &Path) -> PathBuf {
+        let stem = fbs
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        fbs.with_file_name(format!("{}.filtered.fbs", stem))
+    }
+}
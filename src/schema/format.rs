@@ -0,0 +1,115 @@
+use crate::schema::{Declaration, Kind, Schema, Type};
+
+use clap::ValueEnum;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// How `bafb schema fmt` orders the declarations of a schema
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Default)]
+pub enum Order {
+    /// By namespace, then by name
+    #[default]
+    Alphabetical,
+    /// Referenced types before the types using them, alphabetical otherwise
+    Dependency,
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Order::Alphabetical => write!(f, "alphabetical"),
+            Order::Dependency => write!(f, "dependency"),
+        }
+    }
+}
+
+/// Canonical form of `schema`, fields, enum values and union variants keep their order since it
+/// is wire significant, references outside `schema` are left for the including file to resolve
+pub fn format(schema: &Schema, order: Order) -> String {
+    let mut formatted = schema.clone();
+    formatted.declarations = match order {
+        Order::Alphabetical => alphabetical(&schema.declarations),
+        Order::Dependency => dependency(schema),
+    };
+    formatted.to_string()
+}
+
+fn key(declaration: &Declaration) -> (String, String) {
+    (declaration.namespace.clone(), declaration.name.clone())
+}
+
+fn alphabetical(declarations: &[Declaration]) -> Vec<Declaration> {
+    let mut sorted = declarations.to_vec();
+    sorted.sort_by_key(key);
+    sorted
+}
+
+/// Kahn's algorithm with the alphabetical order as tie break, a cycle through tables is broken
+/// at its alphabetically first declaration
+fn dependency(schema: &Schema) -> Vec<Declaration> {
+    let index: HashMap<String, usize> = schema
+        .declarations
+        .iter()
+        .enumerate()
+        .map(|(i, declaration)| (declaration.qualified_name(), i))
+        .collect();
+
+    let mut dependencies: Vec<BTreeSet<usize>> = Vec::new();
+    for declaration in &schema.declarations {
+        let mut targets = BTreeSet::new();
+        for name in names(declaration) {
+            // Types declared in an include are already ordered before this file
+            if let Some(target) = schema.resolve(name, &declaration.namespace)
+                && let Some(&i) = index.get(&target.qualified_name())
+            {
+                targets.insert(i);
+            }
+        }
+        dependencies.push(targets);
+    }
+
+    let mut remaining: BTreeSet<((String, String), usize)> = schema
+        .declarations
+        .iter()
+        .enumerate()
+        .map(|(i, declaration)| (key(declaration), i))
+        .collect();
+    let mut emitted = vec![false; schema.declarations.len()];
+    let mut sorted = Vec::with_capacity(schema.declarations.len());
+
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .find(|(_, i)| {
+                dependencies[*i]
+                    .iter()
+                    .all(|target| emitted[*target] || target == i)
+            })
+            .or_else(|| remaining.first())
+            .cloned();
+        let Some(entry) = ready else {
+            break;
+        };
+
+        remaining.remove(&entry);
+        emitted[entry.1] = true;
+        sorted.push(schema.declarations[entry.1].clone());
+    }
+
+    sorted
+}
+
+/// Type names `declaration` refers to, without resolving them
+fn names(declaration: &Declaration) -> Vec<&str> {
+    match &declaration.kind {
+        Kind::Table(fields) | Kind::Struct(fields) => fields
+            .iter()
+            .filter_map(|field| match field.ty.element() {
+                Type::Named(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect(),
+        Kind::Union(variants) => variants.iter().map(|variant| variant.ty.as_str()).collect(),
+        Kind::Enum(_, _) => Vec::new(),
+    }
+}
@@ -0,0 +1,379 @@
+use crate::schema::diff::{Change, Snapshot, diff};
+use crate::schema::{Declaration, Schema};
+
+use eyre::{Context, Result, eyre};
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Index of a directory store, the dates of its schemas
+const INDEX: &str = "history.json";
+
+/// A schema as it was in one game version
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub version: String,
+    /// Day the schema was dumped, `YYYY-MM-DD`
+    pub date: Option<String>,
+    pub schema: Schema,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexEntry {
+    version: String,
+    date: Option<String>,
+}
+
+/// Where `bafb history add` keeps schemas, a directory of `<version>.fbs` files or a SQLite
+/// database when the path ends in `.db`, `.sqlite` or `.sqlite3`
+#[derive(Debug, Clone)]
+pub enum Store {
+    Directory(PathBuf),
+    Sqlite(PathBuf),
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Self {
+        let is_sqlite = path.extension().is_some_and(|extension| {
+            extension == "db" || extension == "sqlite" || extension == "sqlite3"
+        });
+        if is_sqlite {
+            Store::Sqlite(path.to_path_buf())
+        } else {
+            Store::Directory(path.to_path_buf())
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Store::Directory(path) | Store::Sqlite(path) => path,
+        }
+    }
+
+    /// Every schema in the store, oldest version first
+    pub fn load(&self) -> Result<History> {
+        let mut versions = match self {
+            Store::Directory(dir) => Self::load_directory(dir)?,
+            Store::Sqlite(path) => Self::load_sqlite(path)?,
+        };
+
+        if versions.is_empty() {
+            return Err(eyre!("{} holds no schemas", self.path().display()));
+        }
+        versions.sort_by(|a, b| compare_versions(&a.version, &b.version));
+        Ok(History { versions })
+    }
+
+    /// Stores `version`, an existing schema of the same game version is only overwritten with
+    /// `replace`
+    pub fn add(&self, version: &Version, replace: bool) -> Result<()> {
+        if version.version.is_empty()
+            || version
+                .version
+                .contains(|c: char| c == '/' || c == '\\' || c.is_whitespace())
+        {
+            return Err(eyre!("Invalid game version `{}`", version.version));
+        }
+
+        match self {
+            Store::Directory(dir) => Self::add_directory(dir, version, replace),
+            Store::Sqlite(path) => Self::add_sqlite(path, version, replace),
+        }
+    }
+
+    fn load_directory(dir: &Path) -> Result<Vec<Version>> {
+        if !dir.is_dir() {
+            return Err(eyre!("{} is not a directory", dir.display()));
+        }
+        let index = Self::read_index(dir)?;
+
+        let mut versions = Vec::new();
+        let entries =
+            fs::read_dir(dir).wrap_err_with(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "fbs") {
+                continue;
+            }
+            let version = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let date = index
+                .iter()
+                .find(|entry| entry.version == version)
+                .and_then(|entry| entry.date.clone());
+            let schema = Schema::load(&path)?;
+            versions.push(Version {
+                version,
+                date,
+                schema,
+            });
+        }
+        Ok(versions)
+    }
+
+    /// The index of a directory store, empty for a plain directory of schemas
+    fn read_index(dir: &Path) -> Result<Vec<IndexEntry>> {
+        let path = dir.join(INDEX);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let source = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&source)
+            .wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn add_directory(dir: &Path, version: &Version, replace: bool) -> Result<()> {
+        let path = dir.join(format!("{}.fbs", version.version));
+        if path.exists() && !replace {
+            return Err(eyre!(
+                "{} already holds {}, pass --replace to overwrite it",
+                dir.display(),
+                version.version
+            ));
+        }
+        fs::create_dir_all(dir).wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+        fs::write(&path, version.schema.to_string())
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+
+        let mut index = Self::read_index(dir)?;
+        index.retain(|entry| entry.version != version.version);
+        index.push(IndexEntry {
+            version: version.version.clone(),
+            date: version.date.clone(),
+        });
+        index.sort_by(|a, b| compare_versions(&a.version, &b.version));
+
+        let path = dir.join(INDEX);
+        fs::write(&path, serde_json::to_string_pretty(&index)? + "\n")
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn connect(path: &Path) -> Result<Connection> {
+        let connection = Connection::open(path)
+            .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schemas (
+    version TEXT PRIMARY KEY,
+    date TEXT,
+    schema TEXT NOT NULL
+);",
+        )?;
+        Ok(connection)
+    }
+
+    fn load_sqlite(path: &Path) -> Result<Vec<Version>> {
+        if !path.is_file() {
+            return Err(eyre!("{} does not exist", path.display()));
+        }
+        let connection = Self::connect(path)?;
+        let mut statement = connection.prepare("SELECT version, date, schema FROM schemas")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut versions = Vec::new();
+        for row in rows {
+            let (version, date, source) = row?;
+            let schema = Schema::parse(&source)
+                .wrap_err_with(|| format!("Failed to parse {} in {}", version, path.display()))?;
+            versions.push(Version {
+                version,
+                date,
+                schema,
+            });
+        }
+        Ok(versions)
+    }
+
+    fn add_sqlite(path: &Path, version: &Version, replace: bool) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let connection = Self::connect(path)?;
+        let exists: bool = connection.query_row(
+            "SELECT EXISTS (SELECT 1 FROM schemas WHERE version = ?1)",
+            params![version.version],
+            |row| row.get(0),
+        )?;
+        if exists && !replace {
+            return Err(eyre!(
+                "{} already holds {}, pass --replace to overwrite it",
+                path.display(),
+                version.version
+            ));
+        }
+
+        connection.execute(
+            "INSERT OR REPLACE INTO schemas (version, date, schema) VALUES (?1, ?2, ?3)",
+            params![version.version, version.date, version.schema.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+/// A change and the version that made it
+#[derive(Debug, Serialize)]
+pub struct Entry<'a> {
+    pub version: &'a str,
+    pub date: Option<&'a str>,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// The version that last added or changed a declaration or member
+#[derive(Debug, Serialize)]
+pub struct Blame<'a> {
+    /// Member name, or the qualified name when blaming declarations
+    pub name: String,
+    pub version: &'a str,
+    pub date: Option<&'a str>,
+    /// What it looks like in the newest version
+    pub current: Snapshot,
+}
+
+/// Schemas of several game versions, oldest first
+#[derive(Debug, Default)]
+pub struct History {
+    pub versions: Vec<Version>,
+}
+
+impl History {
+    /// Loads a store, a directory of schemas named after their game version, e.g.
+    /// `1.45.256.fbs`, or a SQLite database
+    pub fn load(path: &Path) -> Result<Self> {
+        Store::open(path).load()
+    }
+
+    /// The oldest version whose schema `has` something
+    pub fn first(&self, has: impl Fn(&Schema) -> bool) -> Option<&str> {
+        self.versions
+            .iter()
+            .find(|version| has(&version.schema))
+            .map(|version| version.version.as_str())
+    }
+
+    /// Every change in the order it was made, the oldest version adds everything it declares
+    pub fn changes(&self) -> Vec<Entry<'_>> {
+        let empty = Schema::default();
+        let mut entries = Vec::new();
+        let mut previous = &empty;
+        for version in &self.versions {
+            for change in diff(previous, &version.schema) {
+                entries.push(Entry {
+                    version: &version.version,
+                    date: version.date.as_deref(),
+                    change,
+                });
+            }
+            previous = &version.schema;
+        }
+        entries
+    }
+
+    /// Splits `Type[.member]` into the qualified name of a declaration any version has and the
+    /// member, `Type` may leave its namespace out
+    pub fn resolve(&self, target: &str) -> Result<(String, Option<String>)> {
+        if let Some(qualified) = self.declaration(target)? {
+            return Ok((qualified, None));
+        }
+        if let Some((declaration, member)) = target.rsplit_once('.')
+            && let Some(qualified) = self.declaration(declaration)?
+        {
+            return Ok((qualified, Some(member.to_string())));
+        }
+        Err(eyre!("No version declares {}", target))
+    }
+
+    fn declaration(&self, name: &str) -> Result<Option<String>> {
+        let mut found: Vec<String> = self
+            .versions
+            .iter()
+            .flat_map(|version| &version.schema.declarations)
+            .filter(|declaration| declaration.name == name || declaration.qualified_name() == name)
+            .map(Declaration::qualified_name)
+            .collect();
+        found.sort();
+        found.dedup();
+
+        if let Some(exact) = found.iter().find(|qualified| *qualified == name) {
+            return Ok(Some(exact.clone()));
+        }
+        match found.len() {
+            0 => Ok(None),
+            1 => Ok(found.pop()),
+            _ => Err(eyre!(
+                "{} is ambiguous, use one of {}",
+                name,
+                found.join(", ")
+            )),
+        }
+    }
+
+    /// Changes to a declaration and its members, or to one member
+    pub fn log(&self, declaration: &str, member: Option<&str>) -> Vec<Entry<'_>> {
+        self.changes()
+            .into_iter()
+            .filter(|entry| {
+                entry.change.declaration == declaration
+                    && (member.is_none() || entry.change.member.as_deref() == member)
+            })
+            .collect()
+    }
+
+    /// The members of `declaration` in the newest version with the version that last added or
+    /// changed each, or every declaration with the last version to touch it or its members
+    pub fn blame(&self, declaration: Option<&str>) -> Vec<Blame<'_>> {
+        let Some(newest) = self.versions.last() else {
+            return Vec::new();
+        };
+        let changes = self.changes();
+
+        // Everything the newest version declares, as if it was all added at once
+        diff(&Schema::default(), &newest.schema)
+            .into_iter()
+            .filter(|current| match declaration {
+                Some(declaration) => current.declaration == declaration && current.member.is_some(),
+                None => current.member.is_none(),
+            })
+            .filter_map(|current| {
+                let last = changes.iter().rev().find(|entry| {
+                    entry.change.declaration == current.declaration
+                        && (declaration.is_none() || entry.change.member == current.member)
+                })?;
+                Some(Blame {
+                    name: match declaration {
+                        Some(_) => current.member.clone().unwrap_or_default(),
+                        None => current.declaration.clone(),
+                    },
+                    version: last.version,
+                    date: last.date,
+                    current: current.after?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Orders game versions by their numbers, `1.9.0` before `1.10.0`, anything else by text
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn parts(version: &str) -> Vec<Result<u64, &str>> {
+        version
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<u64>().map_err(|_| part))
+            .collect()
+    }
+
+    // Numbers sort before text, `Ok` before `Err`
+    parts(a).cmp(&parts(b)).then_with(|| a.cmp(b))
+}
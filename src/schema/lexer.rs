@@ -0,0 +1,196 @@
+use eyre::{Result, eyre};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Number(String),
+    String(String),
+    Doc(String),
+    Punct(char),
+    Eof,
+}
+
+impl Token {
+    pub fn describe(&self) -> String {
+        match self {
+            Token::Ident(ident) => format!("`{}`", ident),
+            Token::Number(number) => format!("`{}`", number),
+            Token::String(string) => format!("\"{}\"", string),
+            Token::Doc(_) => "doc comment".to_string(),
+            Token::Punct(c) => format!("`{}`", c),
+            Token::Eof => "end of file".to_string(),
+        }
+    }
+}
+
+/// A token and the line and column it starts at
+#[derive(Debug, Clone)]
+pub struct Spanned {
+    pub token: Token,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Spanned>> {
+        let mut tokens = Vec::new();
+
+        loop {
+            let spanned = self.next_token()?;
+            let eof = spanned.token == Token::Eof;
+            tokens.push(spanned);
+            if eof {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> eyre::Report {
+        eyre!("{}:{}: {}", self.line, self.column, message)
+    }
+
+    fn next_token(&mut self) -> Result<Spanned> {
+        loop {
+            while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+                self.bump();
+            }
+
+            let (line, column) = (self.line, self.column);
+            let spanned = |token| Spanned {
+                token,
+                line,
+                column,
+            };
+
+            let Some(c) = self.bump() else {
+                return Ok(spanned(Token::Eof));
+            };
+
+            match c {
+                '/' if self.chars.peek() == Some(&'/') => {
+                    self.bump();
+                    let doc = self.chars.peek() == Some(&'/');
+                    if doc {
+                        self.bump();
+                    }
+
+                    let mut text = String::new();
+                    while let Some(&c) = self.chars.peek()
+                        && c != '\n'
+                    {
+                        text.push(c);
+                        self.bump();
+                    }
+
+                    if doc {
+                        return Ok(spanned(Token::Doc(text.trim_end().to_string())));
+                    }
+                }
+                '/' if self.chars.peek() == Some(&'*') => {
+                    self.bump();
+                    let mut previous = '\0';
+                    loop {
+                        let Some(c) = self.bump() else {
+                            return Err(self.error("unterminated block comment"));
+                        };
+                        if previous == '*' && c == '/' {
+                            break;
+                        }
+                        previous = c;
+                    }
+                }
+                '"' => {
+                    let mut text = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('"') => break,
+                            Some('\\') => match self.bump() {
+                                Some('n') => text.push('\n'),
+                                Some('t') => text.push('\t'),
+                                Some(c) => text.push(c),
+                                None => return Err(self.error("unterminated string")),
+                            },
+                            Some('\n') | None => return Err(self.error("unterminated string")),
+                            Some(c) => text.push(c),
+                        }
+                    }
+                    return Ok(spanned(Token::String(text)));
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut ident = c.to_string();
+                    while let Some(&c) = self.chars.peek()
+                        && (c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        ident.push(c);
+                        self.bump();
+                    }
+                    return Ok(spanned(Token::Ident(ident)));
+                }
+                c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                    let mut number = c.to_string();
+                    while let Some(&c) = self.chars.peek()
+                        && (c.is_ascii_alphanumeric() || c == '.' || c == '_')
+                    {
+                        number.push(c);
+                        self.bump();
+                        // Exponent signs, e.g. 1e-5
+                        if matches!(c, 'e' | 'E')
+                            && !number.starts_with("0x")
+                            && !number.starts_with("-0x")
+                            && let Some(&sign) = self.chars.peek()
+                            && matches!(sign, '-' | '+')
+                        {
+                            number.push(sign);
+                            self.bump();
+                        }
+                    }
+                    if number == "-" || number == "+" {
+                        return Err(self.error(&format!("unexpected `{}`", c)));
+                    }
+                    return Ok(spanned(Token::Number(number)));
+                }
+                '.' if self.chars.peek().is_some_and(char::is_ascii_digit) => {
+                    let mut number = "0.".to_string();
+                    while let Some(&c) = self.chars.peek()
+                        && (c.is_ascii_alphanumeric() || c == '.')
+                    {
+                        number.push(c);
+                        self.bump();
+                    }
+                    return Ok(spanned(Token::Number(number)));
+                }
+                '{' | '}' | '(' | ')' | '[' | ']' | ':' | ';' | ',' | '=' | '.' => {
+                    return Ok(spanned(Token::Punct(c)));
+                }
+                c => {
+                    return Err(eyre!("{}:{}: unexpected character `{}`", line, column, c));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,908 @@
+use crate::codegen::rust::KEYWORDS as RUST_KEYWORDS;
+use crate::codegen::to_snake_case;
+use crate::schema::{Declaration, Kind, Schema, Type};
+use crate::wrappers::flatc::Language;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Tables without any field
+    EmptyTable,
+    /// Enums, structs and unions no field or union uses
+    UnreferencedType,
+    /// The same name declared in more than one namespace
+    DuplicateName,
+    /// Enum values that skip numbers, bit_flags enums excepted
+    EnumGap,
+    /// Enum values sharing a number
+    DuplicateEnumValue,
+    /// Field and union variant types that are not declared
+    UnresolvedType,
+    /// Identifiers that are keywords of a target language
+    ReservedKeyword,
+    /// Names that become the same once converted to snake_case
+    SnakeCaseCollision,
+}
+
+impl Rule {
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::DuplicateEnumValue | Rule::UnresolvedType | Rule::SnakeCaseCollision => {
+                Severity::Error
+            }
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LintOptions {
+    /// Rules that are not checked
+    pub allow: Vec<Rule>,
+    /// Rules reported as errors
+    pub deny: Vec<Rule>,
+    /// Languages whose keywords are reserved, every language with a keyword list when empty
+    pub languages: Vec<Language>,
+}
+
+impl LintOptions {
+    pub fn severity(&self, rule: Rule) -> Option<Severity> {
+        if self.deny.contains(&rule) {
+            Some(Severity::Error)
+        } else if self.allow.contains(&rule) {
+            None
+        } else {
+            Some(rule.default_severity())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// Qualified declaration name, followed by the member for field and value findings
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.rule, self.location, self.message
+        )
+    }
+}
+
+pub struct Linter<'a> {
+    schema: &'a Schema,
+    options: LintOptions,
+    findings: Vec<Finding>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(schema: &'a Schema, options: LintOptions) -> Self {
+        Self {
+            schema,
+            options,
+            findings: Vec::new(),
+        }
+    }
+
+    /// Every finding of the enabled rules, by location
+    pub fn run(mut self) -> Vec<Finding> {
+        self.empty_tables();
+        self.unreferenced_types();
+        self.duplicate_names();
+        self.enum_values();
+        self.unresolved_types();
+        self.reserved_keywords();
+        self.snake_case_collisions();
+
+        self.findings
+            .sort_by(|a, b| (&a.location, a.rule).cmp(&(&b.location, b.rule)));
+        self.findings
+    }
+
+    fn report(&mut self, rule: Rule, location: String, message: String) {
+        if let Some(severity) = self.options.severity(rule) {
+            self.findings.push(Finding {
+                rule,
+                severity,
+                location,
+                message,
+            });
+        }
+    }
+
+    fn member(declaration: &Declaration, member: &str) -> String {
+        format!("{}.{}", declaration.qualified_name(), member)
+    }
+
+    fn empty_tables(&mut self) {
+        for declaration in &self.schema.declarations {
+            if let Kind::Table(fields) = &declaration.kind
+                && fields.is_empty()
+            {
+                self.report(
+                    Rule::EmptyTable,
+                    declaration.qualified_name(),
+                    "table has no fields".to_string(),
+                );
+            }
+        }
+    }
+
+    /// Type names `declaration` refers to, with the field or variant using them
+    fn names(declaration: &Declaration) -> Vec<(&str, &str)> {
+        match &declaration.kind {
+            Kind::Table(fields) | Kind::Struct(fields) => fields
+                .iter()
+                .filter_map(|field| match field.ty.element() {
+                    Type::Named(name) => Some((field.name.as_str(), name.as_str())),
+                    _ => None,
+                })
+                .collect(),
+            Kind::Union(variants) => variants
+                .iter()
+                .map(|variant| (variant.name.as_str(), variant.ty.as_str()))
+                .collect(),
+            Kind::Enum(_, _) => Vec::new(),
+        }
+    }
+
+    /// Tables are left out since the Excel and DB schema tables are roots nothing refers to
+    fn unreferenced_types(&mut self) {
+        let mut used = HashSet::new();
+        for declaration in &self.schema.declarations {
+            for (_, name) in Self::names(declaration) {
+                if let Some(target) = self.schema.resolve(name, &declaration.namespace) {
+                    used.insert(target.qualified_name());
+                }
+            }
+        }
+
+        for declaration in &self.schema.declarations {
+            let name = declaration.qualified_name();
+            if !matches!(declaration.kind, Kind::Table(_)) && !used.contains(&name) {
+                self.report(
+                    Rule::UnreferencedType,
+                    name,
+                    format!("{} is never used", declaration.kind.keyword()),
+                );
+            }
+        }
+    }
+
+    fn duplicate_names(&mut self) {
+        let mut namespaces: BTreeMap<&str, Vec<&Declaration>> = BTreeMap::new();
+        for declaration in &self.schema.declarations {
+            namespaces
+                .entry(&declaration.name)
+                .or_default()
+                .push(declaration);
+        }
+
+        for declarations in namespaces.values().filter(|list| list.len() > 1) {
+            for declaration in declarations {
+                let others: Vec<String> = declarations
+                    .iter()
+                    .filter(|other| other.namespace != declaration.namespace)
+                    .map(|other| other.qualified_name())
+                    .collect();
+                if !others.is_empty() {
+                    self.report(
+                        Rule::DuplicateName,
+                        declaration.qualified_name(),
+                        format!("also declared as {}", others.join(", ")),
+                    );
+                }
+            }
+        }
+    }
+
+    fn enum_values(&mut self) {
+        for declaration in &self.schema.declarations {
+            let Kind::Enum(_, values) = &declaration.kind else {
+                continue;
+            };
+
+            let mut numbers: BTreeMap<i64, Vec<&str>> = BTreeMap::new();
+            for value in values {
+                numbers.entry(value.value).or_default().push(&value.name);
+            }
+
+            for (number, names) in numbers.iter().filter(|(_, names)| names.len() > 1) {
+                self.report(
+                    Rule::DuplicateEnumValue,
+                    Self::member(declaration, names[1]),
+                    format!("shares {} with {}", number, names[0]),
+                );
+            }
+
+            if declaration.has_attribute("bit_flags") {
+                continue;
+            }
+            let numbers: Vec<i64> = numbers.keys().copied().collect();
+            for pair in numbers.windows(2) {
+                if pair[1] - pair[0] > 1 {
+                    let missing = if pair[1] - pair[0] == 2 {
+                        (pair[0] + 1).to_string()
+                    } else {
+                        format!("{} to {}", pair[0] + 1, pair[1] - 1)
+                    };
+                    self.report(
+                        Rule::EnumGap,
+                        declaration.qualified_name(),
+                        format!("no value for {}", missing),
+                    );
+                }
+            }
+        }
+    }
+
+    fn unresolved_types(&mut self) {
+        for declaration in &self.schema.declarations {
+            for (member, name) in Self::names(declaration) {
+                if self.schema.resolve(name, &declaration.namespace).is_none() {
+                    self.report(
+                        Rule::UnresolvedType,
+                        Self::member(declaration, member),
+                        format!("`{}` is not declared", name),
+                    );
+                }
+            }
+        }
+    }
+
+    fn reserved_keywords(&mut self) {
+        let languages: Vec<Language> = if self.options.languages.is_empty() {
+            Language::value_variants()
+                .iter()
+                .copied()
+                .filter(|language| !keywords(*language).is_empty())
+                .collect()
+        } else {
+            self.options.languages.clone()
+        };
+
+        for declaration in &self.schema.declarations {
+            let members: Vec<&str> = match &declaration.kind {
+                Kind::Table(fields) | Kind::Struct(fields) => {
+                    fields.iter().map(|field| field.name.as_str()).collect()
+                }
+                Kind::Enum(_, values) => values.iter().map(|value| value.name.as_str()).collect(),
+                Kind::Union(variants) => variants
+                    .iter()
+                    .map(|variant| variant.name.as_str())
+                    .collect(),
+            };
+            let is_object = matches!(declaration.kind, Kind::Table(_) | Kind::Struct(_));
+
+            let mut identifiers = vec![(declaration.qualified_name(), declaration.name.clone())];
+            for member in members {
+                identifiers.push((Self::member(declaration, member), member.to_string()));
+                // Rust accessors are the snake_case field names
+                if is_object && languages.contains(&Language::Rust) {
+                    identifiers.push((Self::member(declaration, member), to_snake_case(member)));
+                }
+            }
+
+            let mut reported = HashSet::new();
+            for (location, identifier) in identifiers {
+                let clashing: Vec<String> = languages
+                    .iter()
+                    .filter(|language| keywords(**language).contains(&identifier.as_str()))
+                    .map(ToString::to_string)
+                    .collect();
+                if !clashing.is_empty() && reported.insert(location.clone()) {
+                    self.report(
+                        Rule::ReservedKeyword,
+                        location,
+                        format!("`{}` is a keyword in {}", identifier, clashing.join(", ")),
+                    );
+                }
+            }
+        }
+    }
+
+    /// FbsDumper's `force_snake_case` and flatc's file names both lowercase names
+    fn snake_case_collisions(&mut self) {
+        let mut files: BTreeMap<(String, String), Vec<&Declaration>> = BTreeMap::new();
+        for declaration in &self.schema.declarations {
+            files
+                .entry((
+                    declaration.namespace.clone(),
+                    to_snake_case(&declaration.name),
+                ))
+                .or_default()
+                .push(declaration);
+        }
+        for declarations in files.values().filter(|list| list.len() > 1) {
+            for declaration in &declarations[1..] {
+                self.report(
+                    Rule::SnakeCaseCollision,
+                    declaration.qualified_name(),
+                    format!(
+                        "same snake_case name as {}",
+                        declarations[0].qualified_name()
+                    ),
+                );
+            }
+        }
+
+        for declaration in &self.schema.declarations {
+            let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+            for field in declaration.fields() {
+                match seen.get(&to_snake_case(&field.name)) {
+                    Some(first) => {
+                        let message = format!("same snake_case name as {}", first);
+                        self.report(
+                            Rule::SnakeCaseCollision,
+                            Self::member(declaration, &field.name),
+                            message,
+                        );
+                    }
+                    None => {
+                        seen.insert(to_snake_case(&field.name), &field.name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reserved words of the languages flatc generates, as far as they are valid FlatBuffers names
+pub fn keywords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => RUST_KEYWORDS,
+        Language::CSharp => &[
+            "abstract",
+            "as",
+            "base",
+            "bool",
+            "break",
+            "byte",
+            "case",
+            "catch",
+            "char",
+            "checked",
+            "class",
+            "const",
+            "continue",
+            "decimal",
+            "default",
+            "delegate",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "event",
+            "explicit",
+            "extern",
+            "false",
+            "finally",
+            "fixed",
+            "float",
+            "for",
+            "foreach",
+            "goto",
+            "if",
+            "implicit",
+            "in",
+            "int",
+            "interface",
+            "internal",
+            "is",
+            "lock",
+            "long",
+            "namespace",
+            "new",
+            "null",
+            "object",
+            "operator",
+            "out",
+            "override",
+            "params",
+            "private",
+            "protected",
+            "public",
+            "readonly",
+            "ref",
+            "return",
+            "sbyte",
+            "sealed",
+            "short",
+            "sizeof",
+            "stackalloc",
+            "static",
+            "string",
+            "struct",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "uint",
+            "ulong",
+            "unchecked",
+            "unsafe",
+            "ushort",
+            "using",
+            "virtual",
+            "void",
+            "volatile",
+            "while",
+        ],
+        Language::Python => &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+        Language::TypeScript | Language::JavaScript => &[
+            "arguments",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "implements",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "let",
+            "new",
+            "null",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "static",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "yield",
+        ],
+        Language::Go => &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "fallthrough",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "type",
+            "var",
+        ],
+        Language::Java => &[
+            "abstract",
+            "assert",
+            "boolean",
+            "break",
+            "byte",
+            "case",
+            "catch",
+            "char",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "extends",
+            "false",
+            "final",
+            "finally",
+            "float",
+            "for",
+            "goto",
+            "if",
+            "implements",
+            "import",
+            "instanceof",
+            "int",
+            "interface",
+            "long",
+            "native",
+            "new",
+            "null",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "short",
+            "static",
+            "strictfp",
+            "super",
+            "switch",
+            "synchronized",
+            "this",
+            "throw",
+            "throws",
+            "transient",
+            "true",
+            "try",
+            "void",
+            "volatile",
+            "while",
+        ],
+        Language::Kotlin | Language::KotlinKmp => &[
+            "as",
+            "break",
+            "class",
+            "continue",
+            "do",
+            "else",
+            "false",
+            "for",
+            "fun",
+            "if",
+            "in",
+            "interface",
+            "is",
+            "null",
+            "object",
+            "package",
+            "return",
+            "super",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typealias",
+            "typeof",
+            "val",
+            "var",
+            "when",
+            "while",
+        ],
+        Language::Cpp => &[
+            "alignas",
+            "alignof",
+            "and",
+            "asm",
+            "auto",
+            "bool",
+            "break",
+            "case",
+            "catch",
+            "char",
+            "class",
+            "const",
+            "constexpr",
+            "continue",
+            "decltype",
+            "default",
+            "delete",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "explicit",
+            "export",
+            "extern",
+            "false",
+            "float",
+            "for",
+            "friend",
+            "goto",
+            "if",
+            "inline",
+            "int",
+            "long",
+            "mutable",
+            "namespace",
+            "new",
+            "noexcept",
+            "not",
+            "nullptr",
+            "operator",
+            "or",
+            "private",
+            "protected",
+            "public",
+            "register",
+            "return",
+            "short",
+            "signed",
+            "sizeof",
+            "static",
+            "struct",
+            "switch",
+            "template",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typedef",
+            "typename",
+            "union",
+            "unsigned",
+            "using",
+            "virtual",
+            "void",
+            "volatile",
+            "while",
+            "xor",
+        ],
+        Language::Swift => &[
+            "Any",
+            "Self",
+            "as",
+            "associatedtype",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "continue",
+            "default",
+            "defer",
+            "deinit",
+            "do",
+            "else",
+            "enum",
+            "extension",
+            "fallthrough",
+            "false",
+            "fileprivate",
+            "for",
+            "func",
+            "guard",
+            "if",
+            "import",
+            "in",
+            "init",
+            "inout",
+            "internal",
+            "is",
+            "let",
+            "nil",
+            "open",
+            "operator",
+            "private",
+            "protocol",
+            "public",
+            "repeat",
+            "rethrows",
+            "return",
+            "self",
+            "static",
+            "struct",
+            "subscript",
+            "super",
+            "switch",
+            "throw",
+            "throws",
+            "true",
+            "try",
+            "typealias",
+            "var",
+            "where",
+            "while",
+        ],
+        Language::Dart => &[
+            "assert", "break", "case", "catch", "class", "const", "continue", "default", "do",
+            "else", "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new",
+            "null", "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var",
+            "void", "while", "with",
+        ],
+        Language::Php => &[
+            "abstract",
+            "and",
+            "array",
+            "as",
+            "break",
+            "callable",
+            "case",
+            "catch",
+            "class",
+            "clone",
+            "const",
+            "continue",
+            "declare",
+            "default",
+            "do",
+            "echo",
+            "else",
+            "elseif",
+            "empty",
+            "enddeclare",
+            "endfor",
+            "endforeach",
+            "endif",
+            "endswitch",
+            "endwhile",
+            "extends",
+            "final",
+            "finally",
+            "fn",
+            "for",
+            "foreach",
+            "function",
+            "global",
+            "goto",
+            "if",
+            "implements",
+            "include",
+            "instanceof",
+            "insteadof",
+            "interface",
+            "isset",
+            "list",
+            "match",
+            "namespace",
+            "new",
+            "or",
+            "print",
+            "private",
+            "protected",
+            "public",
+            "readonly",
+            "require",
+            "return",
+            "static",
+            "switch",
+            "throw",
+            "trait",
+            "try",
+            "unset",
+            "use",
+            "var",
+            "while",
+            "xor",
+            "yield",
+        ],
+        Language::Lua => &[
+            "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto",
+            "if", "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until",
+            "while",
+        ],
+        Language::Nim => &[
+            "addr",
+            "and",
+            "as",
+            "asm",
+            "bind",
+            "block",
+            "break",
+            "case",
+            "cast",
+            "concept",
+            "const",
+            "continue",
+            "converter",
+            "defer",
+            "discard",
+            "distinct",
+            "div",
+            "do",
+            "elif",
+            "else",
+            "end",
+            "enum",
+            "except",
+            "export",
+            "finally",
+            "for",
+            "from",
+            "func",
+            "if",
+            "import",
+            "in",
+            "include",
+            "interface",
+            "is",
+            "isnot",
+            "iterator",
+            "let",
+            "macro",
+            "method",
+            "mixin",
+            "mod",
+            "nil",
+            "not",
+            "notin",
+            "object",
+            "of",
+            "or",
+            "out",
+            "proc",
+            "ptr",
+            "raise",
+            "ref",
+            "return",
+            "shl",
+            "shr",
+            "static",
+            "template",
+            "try",
+            "tuple",
+            "type",
+            "using",
+            "var",
+            "when",
+            "while",
+            "xor",
+            "yield",
+        ],
+        Language::Lobster => &[],
+    }
+}
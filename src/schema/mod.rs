@@ -0,0 +1,461 @@
+pub mod changelog;
+pub mod diff;
+pub mod doc;
+pub mod export;
+pub mod filter;
+pub mod format;
+pub mod history;
+pub mod lexer;
+pub mod lint;
+pub mod parser;
+pub mod split;
+pub mod writer;
+
+use crate::schema::parser::Parser;
+
+use eyre::{Context, Result, eyre};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scalar {
+    Bool,
+    Byte,
+    UByte,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+}
+
+impl Scalar {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Scalar::Bool,
+            "byte" | "int8" => Scalar::Byte,
+            "ubyte" | "uint8" => Scalar::UByte,
+            "short" | "int16" => Scalar::Short,
+            "ushort" | "uint16" => Scalar::UShort,
+            "int" | "int32" => Scalar::Int,
+            "uint" | "uint32" => Scalar::UInt,
+            "long" | "int64" => Scalar::Long,
+            "ulong" | "uint64" => Scalar::ULong,
+            "float" | "float32" => Scalar::Float,
+            "double" | "float64" => Scalar::Double,
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scalar::Bool => "bool",
+            Scalar::Byte => "byte",
+            Scalar::UByte => "ubyte",
+            Scalar::Short => "short",
+            Scalar::UShort => "ushort",
+            Scalar::Int => "int",
+            Scalar::UInt => "uint",
+            Scalar::Long => "long",
+            Scalar::ULong => "ulong",
+            Scalar::Float => "float",
+            Scalar::Double => "double",
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Scalar::Bool | Scalar::Byte | Scalar::UByte => 1,
+            Scalar::Short | Scalar::UShort => 2,
+            Scalar::Int | Scalar::UInt | Scalar::Float => 4,
+            Scalar::Long | Scalar::ULong | Scalar::Double => 8,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, Scalar::Bool | Scalar::Float | Scalar::Double)
+    }
+
+    /// Inclusive value range of an integer type
+    pub fn range(&self) -> Option<(i128, i128)> {
+        Some(match self {
+            Scalar::Byte => (i8::MIN.into(), i8::MAX.into()),
+            Scalar::UByte => (0, u8::MAX.into()),
+            Scalar::Short => (i16::MIN.into(), i16::MAX.into()),
+            Scalar::UShort => (0, u16::MAX.into()),
+            Scalar::Int => (i32::MIN.into(), i32::MAX.into()),
+            Scalar::UInt => (0, u32::MAX.into()),
+            Scalar::Long => (i64::MIN.into(), i64::MAX.into()),
+            Scalar::ULong => (0, u64::MAX.into()),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    Scalar(Scalar),
+    String,
+    Vector(Box<Type>),
+    /// Fixed length array, only valid in structs
+    Array(Box<Type>, usize),
+    /// Reference to an enum, union, struct or table
+    Named(String),
+}
+
+impl Type {
+    /// The type itself or the element type of a vector or array
+    pub fn element(&self) -> &Type {
+        match self {
+            Type::Vector(element) | Type::Array(element, _) => element,
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Scalar(scalar) => write!(f, "{}", scalar.name()),
+            Type::String => write!(f, "string"),
+            Type::Vector(element) => write!(f, "[{}]", element),
+            Type::Array(element, length) => write!(f, "[{}:{}]", element, length),
+            Type::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+    /// Default value as written in the schema, e.g. `0`, `-1.5`, `true` or an enum value name
+    pub default: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub doc: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    pub value: i64,
+    /// Whether the value was written out or implied by the previous one
+    pub explicit: bool,
+    pub doc: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionVariant {
+    pub name: String,
+    /// Referenced table, the same as `name` unless the variant is aliased
+    pub ty: String,
+    /// Discriminant, `NONE` takes 0
+    pub value: i64,
+    pub explicit: bool,
+    pub doc: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Table(Vec<Field>),
+    Struct(Vec<Field>),
+    Enum(Scalar, Vec<EnumValue>),
+    Union(Vec<UnionVariant>),
+}
+
+impl Kind {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Kind::Table(_) => "table",
+            Kind::Struct(_) => "struct",
+            Kind::Enum(_, _) => "enum",
+            Kind::Union(_) => "union",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    /// Dotted namespace, empty for the root namespace
+    pub namespace: String,
+    pub name: String,
+    pub kind: Kind,
+    pub attributes: Vec<Attribute>,
+    pub doc: Vec<String>,
+}
+
+impl Declaration {
+    pub fn qualified_name(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.namespace, self.name)
+        }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        match &self.kind {
+            Kind::Table(fields) | Kind::Struct(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| attribute.name == name)
+    }
+}
+
+impl Field {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attribute(name).is_some()
+    }
+}
+
+/// A parsed FlatBuffers schema with the declarations of all its includes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    pub includes: Vec<String>,
+    /// Custom attributes declared with `attribute "name";`
+    pub attributes: Vec<String>,
+    pub root_type: Option<String>,
+    pub file_identifier: Option<String>,
+    pub file_extension: Option<String>,
+    pub declarations: Vec<Declaration>,
+}
+
+impl Schema {
+    pub fn parse(source: &str) -> Result<Self> {
+        Parser::new(source)?.parse()
+    }
+
+    /// Parses `path` and every file it includes, relative to the including file
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut loaded = HashSet::new();
+        let mut schema = Self::load_file(path, &mut loaded)?;
+        schema.includes.clear();
+        Ok(schema)
+    }
+
+    /// Parses `path` without following its includes
+    pub fn load_single(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&source).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn load_file(path: &Path, loaded: &mut HashSet<PathBuf>) -> Result<Self> {
+        let canonical = fs::canonicalize(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        loaded.insert(canonical);
+
+        let mut schema = Self::load_single(path)?;
+        let base = path.parent().unwrap_or(Path::new(""));
+
+        let mut declarations = Vec::new();
+        for include in &schema.includes {
+            let included = base.join(include);
+            let canonical = fs::canonicalize(&included).wrap_err_with(|| {
+                format!("Failed to find {} included by {}", include, path.display())
+            })?;
+            if loaded.contains(&canonical) {
+                continue;
+            }
+
+            let child = Self::load_file(&included, loaded)?;
+            for attribute in child.attributes {
+                if !schema.attributes.contains(&attribute) {
+                    schema.attributes.push(attribute);
+                }
+            }
+            declarations.extend(child.declarations);
+        }
+
+        declarations.append(&mut schema.declarations);
+        schema.declarations = declarations;
+        Ok(schema)
+    }
+
+    /// Looks `name` up the way flatc does, from the innermost namespace of `namespace` outwards
+    pub fn resolve(&self, name: &str, namespace: &str) -> Option<&Declaration> {
+        let mut scope: Vec<&str> = if namespace.is_empty() {
+            Vec::new()
+        } else {
+            namespace.split('.').collect()
+        };
+
+        loop {
+            let candidate = if scope.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", scope.join("."), name)
+            };
+
+            if let Some(declaration) = self
+                .declarations
+                .iter()
+                .find(|declaration| declaration.qualified_name() == candidate)
+            {
+                return Some(declaration);
+            }
+
+            scope.pop()?;
+        }
+    }
+
+    /// Every declaration `declaration` refers to by name, in field or variant order
+    pub fn references(&self, declaration: &Declaration) -> Result<Vec<&Declaration>> {
+        let names: Vec<&str> = match &declaration.kind {
+            Kind::Table(fields) | Kind::Struct(fields) => fields
+                .iter()
+                .filter_map(|field| match field.ty.element() {
+                    Type::Named(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            Kind::Union(variants) => variants.iter().map(|variant| variant.ty.as_str()).collect(),
+            Kind::Enum(_, _) => Vec::new(),
+        };
+
+        names
+            .into_iter()
+            .map(|name| {
+                self.resolve(name, &declaration.namespace).ok_or_else(|| {
+                    eyre!(
+                        "Unknown type `{}` in {}",
+                        name,
+                        declaration.qualified_name()
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that every referenced type exists and is used where flatc would accept it
+    pub fn check(&self) -> Result<()> {
+        let mut seen = HashSet::new();
+        for declaration in &self.declarations {
+            if !seen.insert(declaration.qualified_name()) {
+                return Err(eyre!(
+                    "`{}` is declared twice",
+                    declaration.qualified_name()
+                ));
+            }
+        }
+
+        for declaration in &self.declarations {
+            let owner = declaration.qualified_name();
+
+            match &declaration.kind {
+                Kind::Table(fields) | Kind::Struct(fields) => {
+                    let is_struct = matches!(declaration.kind, Kind::Struct(_));
+
+                    for field in fields {
+                        let ty = field.ty.element();
+                        let target = match ty {
+                            Type::Named(name) => {
+                                Some(self.resolve(name, &declaration.namespace).ok_or_else(
+                                    || eyre!("Unknown type `{}` in {}.{}", name, owner, field.name),
+                                )?)
+                            }
+                            _ => None,
+                        };
+
+                        if is_struct {
+                            let valid = match (&field.ty, target) {
+                                (Type::Scalar(_), _) | (Type::Array(_, _), None) => {
+                                    !matches!(ty, Type::String)
+                                }
+                                (Type::Named(_) | Type::Array(_, _), Some(target)) => {
+                                    matches!(target.kind, Kind::Struct(_) | Kind::Enum(_, _))
+                                }
+                                _ => false,
+                            };
+                            if !valid {
+                                return Err(eyre!(
+                                    "Struct field {}.{} must be a scalar, enum, struct or array",
+                                    owner,
+                                    field.name
+                                ));
+                            }
+                        } else if matches!(field.ty, Type::Array(_, _)) {
+                            return Err(eyre!(
+                                "Fixed length arrays are only allowed in structs ({}.{})",
+                                owner,
+                                field.name
+                            ));
+                        }
+
+                        if let (Type::Vector(_), Some(target)) = (&field.ty, target)
+                            && matches!(target.kind, Kind::Union(_))
+                        {
+                            return Err(eyre!(
+                                "Vectors of unions are not supported ({}.{})",
+                                owner,
+                                field.name
+                            ));
+                        }
+                    }
+                }
+                Kind::Enum(underlying, values) => {
+                    if !underlying.is_integer() {
+                        return Err(eyre!("Enum {} must have an integer type", owner));
+                    }
+                    let (min, max) = underlying.range().unwrap_or_default();
+                    for value in values {
+                        let number = match underlying {
+                            Scalar::ULong => i128::from(value.value as u64),
+                            _ => i128::from(value.value),
+                        };
+                        if !(min..=max).contains(&number) {
+                            return Err(eyre!(
+                                "{}.{} = {} does not fit in {}",
+                                owner,
+                                value.name,
+                                value.value,
+                                underlying.name()
+                            ));
+                        }
+                    }
+                }
+                Kind::Union(variants) => {
+                    for variant in variants {
+                        let target = self
+                            .resolve(&variant.ty, &declaration.namespace)
+                            .ok_or_else(|| {
+                                eyre!("Unknown type `{}` in union {}", variant.ty, owner)
+                            })?;
+                        if !matches!(target.kind, Kind::Table(_)) {
+                            return Err(eyre!(
+                                "Union {} can only hold tables, `{}` is a {}",
+                                owner,
+                                variant.ty,
+                                target.kind.keyword()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
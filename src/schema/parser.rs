@@ -0,0 +1,425 @@
+use crate::schema::lexer::{Lexer, Spanned, Token};
+use crate::schema::{
+    Attribute, Declaration, EnumValue, Field, Kind, Scalar, Schema, Type, UnionVariant,
+};
+
+use eyre::{Result, eyre};
+
+pub struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    namespace: String,
+}
+
+impl Parser {
+    pub fn new(source: &str) -> Result<Self> {
+        Ok(Self {
+            tokens: Lexer::new(source).tokenize()?,
+            position: 0,
+            namespace: String::new(),
+        })
+    }
+
+    pub fn parse(mut self) -> Result<Schema> {
+        let mut schema = Schema::default();
+
+        loop {
+            let doc = self.doc();
+            let token = self.peek().clone();
+
+            match token {
+                Token::Eof => return Ok(schema),
+                Token::Ident(keyword) => {
+                    self.advance();
+                    match keyword.as_str() {
+                        "include" => {
+                            schema.includes.push(self.string()?);
+                            self.expect(';')?;
+                        }
+                        "native_include" => {
+                            self.string()?;
+                            self.expect(';')?;
+                        }
+                        "namespace" => {
+                            self.namespace = if self.eat(';') {
+                                String::new()
+                            } else {
+                                let namespace = self.qualified_ident()?;
+                                self.expect(';')?;
+                                namespace
+                            };
+                        }
+                        "attribute" => {
+                            let name = match self.advance() {
+                                Token::String(name) | Token::Ident(name) => name,
+                                other => return Err(self.unexpected(&other, "an attribute name")),
+                            };
+                            schema.attributes.push(name);
+                            self.expect(';')?;
+                        }
+                        "root_type" => {
+                            schema.root_type = Some(self.qualified_ident()?);
+                            self.expect(';')?;
+                        }
+                        "file_identifier" => {
+                            schema.file_identifier = Some(self.string()?);
+                            self.expect(';')?;
+                        }
+                        "file_extension" => {
+                            schema.file_extension = Some(self.string()?);
+                            self.expect(';')?;
+                        }
+                        "table" | "struct" => {
+                            let declaration = self.object(keyword == "struct", doc)?;
+                            schema.declarations.push(declaration);
+                        }
+                        "enum" => schema.declarations.push(self.enumeration(doc)?),
+                        "union" => schema.declarations.push(self.union(doc)?),
+                        "rpc_service" => {
+                            return Err(self.error_before("rpc_service is not supported"));
+                        }
+                        _ => return Err(self.error_before(&format!("unexpected `{}`", keyword))),
+                    }
+                }
+                other => {
+                    self.advance();
+                    return Err(self.error_before(&format!(
+                        "expected a declaration, found {}",
+                        other.describe()
+                    )));
+                }
+            }
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].token
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].token.clone();
+        if token != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn location(&self, position: usize) -> (usize, usize) {
+        let spanned = &self.tokens[position];
+        (spanned.line, spanned.column)
+    }
+
+    /// Error located at the token that was just consumed
+    fn error_before(&self, message: &str) -> eyre::Report {
+        let (line, column) = self.location(self.position.saturating_sub(1));
+        eyre!("{}:{}: {}", line, column, message)
+    }
+
+    fn unexpected(&self, token: &Token, expected: &str) -> eyre::Report {
+        self.error_before(&format!(
+            "expected {}, found {}",
+            expected,
+            token.describe()
+        ))
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if *self.peek() == Token::Punct(c) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.advance() {
+            Token::Punct(found) if found == c => Ok(()),
+            other => Err(self.unexpected(&other, &format!("`{}`", c))),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.advance() {
+            Token::Ident(ident) => Ok(ident),
+            other => Err(self.unexpected(&other, "an identifier")),
+        }
+    }
+
+    fn qualified_ident(&mut self) -> Result<String> {
+        let mut ident = self.ident()?;
+        while self.eat('.') {
+            ident.push('.');
+            ident.push_str(&self.ident()?);
+        }
+        Ok(ident)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        match self.advance() {
+            Token::String(string) => Ok(string),
+            other => Err(self.unexpected(&other, "a string")),
+        }
+    }
+
+    fn doc(&mut self) -> Vec<String> {
+        let mut doc = Vec::new();
+        while let Token::Doc(line) = self.peek() {
+            doc.push(line.clone());
+            self.advance();
+        }
+        doc
+    }
+
+    fn declaration(
+        &self,
+        name: String,
+        kind: Kind,
+        attributes: Vec<Attribute>,
+        doc: Vec<String>,
+    ) -> Declaration {
+        Declaration {
+            namespace: self.namespace.clone(),
+            name,
+            kind,
+            attributes,
+            doc,
+        }
+    }
+
+    fn metadata(&mut self) -> Result<Vec<Attribute>> {
+        let mut attributes = Vec::new();
+        if !self.eat('(') {
+            return Ok(attributes);
+        }
+
+        loop {
+            let name = self.ident()?;
+            let value = if self.eat(':') {
+                Some(match self.advance() {
+                    Token::String(value) | Token::Number(value) | Token::Ident(value) => value,
+                    other => return Err(self.unexpected(&other, "an attribute value")),
+                })
+            } else {
+                None
+            };
+            attributes.push(Attribute { name, value });
+
+            if !self.eat(',') {
+                break;
+            }
+        }
+
+        self.expect(')')?;
+        Ok(attributes)
+    }
+
+    fn ty(&mut self) -> Result<Type> {
+        if self.eat('[') {
+            let element = self.ty()?;
+            let ty = if self.eat(':') {
+                let length = match self.advance() {
+                    Token::Number(length) => length
+                        .parse()
+                        .map_err(|_| self.error_before("invalid array length"))?,
+                    other => return Err(self.unexpected(&other, "an array length")),
+                };
+                Type::Array(Box::new(element), length)
+            } else {
+                Type::Vector(Box::new(element))
+            };
+            self.expect(']')?;
+
+            if matches!(ty.element(), Type::Vector(_) | Type::Array(_, _)) {
+                return Err(self.error_before("nested vectors are not allowed"));
+            }
+            return Ok(ty);
+        }
+
+        let name = self.qualified_ident()?;
+        Ok(match name.as_str() {
+            "string" => Type::String,
+            _ => match Scalar::from_name(&name) {
+                Some(scalar) => Type::Scalar(scalar),
+                None => Type::Named(name),
+            },
+        })
+    }
+
+    fn object(&mut self, is_struct: bool, doc: Vec<String>) -> Result<Declaration> {
+        let name = self.ident()?;
+        let attributes = self.metadata()?;
+        self.expect('{')?;
+
+        let mut fields = Vec::new();
+        loop {
+            let doc = self.doc();
+            if self.eat('}') {
+                break;
+            }
+
+            let name = self.ident()?;
+            self.expect(':')?;
+            let ty = self.ty()?;
+            let default = if self.eat('=') {
+                Some(match self.advance() {
+                    Token::Number(value) | Token::Ident(value) => value,
+                    other => return Err(self.unexpected(&other, "a default value")),
+                })
+            } else {
+                None
+            };
+            let attributes = self.metadata()?;
+            self.expect(';')?;
+
+            if fields.iter().any(|field: &Field| field.name == name) {
+                return Err(self.error_before(&format!("field `{}` is declared twice", name)));
+            }
+            fields.push(Field {
+                name,
+                ty,
+                default,
+                attributes,
+                doc,
+            });
+        }
+
+        let kind = if is_struct {
+            Kind::Struct(fields)
+        } else {
+            Kind::Table(fields)
+        };
+        Ok(self.declaration(name, kind, attributes, doc))
+    }
+
+    fn integer(&mut self) -> Result<i64> {
+        match self.advance() {
+            Token::Number(number) => parse_integer(&number)
+                .ok_or_else(|| self.error_before(&format!("invalid integer `{}`", number))),
+            other => Err(self.unexpected(&other, "an integer")),
+        }
+    }
+
+    fn enumeration(&mut self, doc: Vec<String>) -> Result<Declaration> {
+        let name = self.ident()?;
+        self.expect(':')?;
+        let underlying = match self.ty()? {
+            Type::Scalar(scalar) => scalar,
+            other => {
+                return Err(self.error_before(&format!(
+                    "enum {} needs an integer type, found `{}`",
+                    name, other
+                )));
+            }
+        };
+        let attributes = self.metadata()?;
+        let bit_flags = attributes
+            .iter()
+            .any(|attribute| attribute.name == "bit_flags");
+        self.expect('{')?;
+
+        let mut values: Vec<EnumValue> = Vec::new();
+        loop {
+            let doc = self.doc();
+            if self.eat('}') {
+                break;
+            }
+
+            let value_name = self.ident()?;
+            // bit_flags values are still bit positions here, so both count up from the last one
+            let (value, explicit) = if self.eat('=') {
+                (self.integer()?, true)
+            } else {
+                (values.last().map_or(0, |last| last.value + 1), false)
+            };
+            values.push(EnumValue {
+                name: value_name,
+                value,
+                explicit,
+                doc,
+            });
+
+            if !self.eat(',') {
+                self.doc();
+                self.expect('}')?;
+                break;
+            }
+        }
+
+        // bit_flags values are bit positions in the schema, bit 63 is the sign bit of a ulong mask
+        if bit_flags {
+            for value in &mut values {
+                value.value = 1i64
+                    .checked_shl(value.value as u32)
+                    .filter(|_| (0..64).contains(&value.value))
+                    .ok_or_else(|| eyre!("bit flag {}.{} is out of range", name, value.name))?;
+            }
+        }
+
+        Ok(self.declaration(name, Kind::Enum(underlying, values), attributes, doc))
+    }
+
+    fn union(&mut self, doc: Vec<String>) -> Result<Declaration> {
+        let name = self.ident()?;
+        let attributes = self.metadata()?;
+        self.expect('{')?;
+
+        let mut variants: Vec<UnionVariant> = Vec::new();
+        loop {
+            let doc = self.doc();
+            if self.eat('}') {
+                break;
+            }
+
+            let first = self.qualified_ident()?;
+            let (variant_name, ty) = if self.eat(':') {
+                (first, self.qualified_ident()?)
+            } else {
+                let short = first.rsplit('.').next().unwrap_or(&first).to_string();
+                (short, first)
+            };
+            let (value, explicit) = if self.eat('=') {
+                (self.integer()?, true)
+            } else {
+                (variants.last().map_or(1, |last| last.value + 1), false)
+            };
+            variants.push(UnionVariant {
+                name: variant_name,
+                ty,
+                value,
+                explicit,
+                doc,
+            });
+
+            if !self.eat(',') {
+                self.doc();
+                self.expect('}')?;
+                break;
+            }
+        }
+
+        Ok(self.declaration(name, Kind::Union(variants), attributes, doc))
+    }
+}
+
+pub fn parse_integer(number: &str) -> Option<i64> {
+    let (negative, digits) = match number.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, number.strip_prefix('+').unwrap_or(number)),
+    };
+
+    let magnitude = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => i128::from_str_radix(hex, 16).ok()?,
+        None => digits.parse::<i128>().ok()?,
+    };
+    let value = if negative { -magnitude } else { magnitude };
+
+    // ulong values above i64::MAX keep their bit pattern
+    if value > i128::from(i64::MAX) && value <= i128::from(u64::MAX) {
+        return Some(value as u64 as i64);
+    }
+    i64::try_from(value).ok()
+}
@@ -0,0 +1,190 @@
+use crate::schema::{Declaration, Kind, Schema};
+
+use eyre::{Result, eyre};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// Enums, structs and attribute declarations every other file can include
+pub const SHARED_FILE: &str = "shared.fbs";
+
+/// A file of a split schema, relative to the output directory
+pub struct SchemaFile {
+    pub path: PathBuf,
+    pub schema: Schema,
+}
+
+/// Partitions `schema` into one file per table and union plus a shared file with every enum and
+/// struct, the index file named `index` includes all of them and keeps the root type
+pub fn split(schema: &Schema, index: &str) -> Result<Vec<SchemaFile>> {
+    let mut files: Vec<SchemaFile> = Vec::new();
+    let shared = PathBuf::from(SHARED_FILE);
+
+    if !schema.attributes.is_empty() {
+        files.push(SchemaFile {
+            path: shared.clone(),
+            schema: Schema {
+                attributes: schema.attributes.clone(),
+                ..Schema::default()
+            },
+        });
+    }
+
+    for declaration in &schema.declarations {
+        let path = file_of(declaration);
+        match files.iter_mut().find(|file| file.path == path) {
+            Some(file) if path == shared => file.schema.declarations.push(declaration.clone()),
+            Some(_) => {
+                return Err(eyre!(
+                    "{} and another declaration would both be written to {}",
+                    declaration.qualified_name(),
+                    path.display()
+                ));
+            }
+            None => files.push(SchemaFile {
+                path,
+                schema: Schema {
+                    declarations: vec![declaration.clone()],
+                    ..Schema::default()
+                },
+            }),
+        }
+    }
+
+    for file in &mut files {
+        let mut includes = BTreeSet::new();
+        for declaration in &file.schema.declarations {
+            for target in schema.references(declaration)? {
+                includes.insert(file_of(target));
+            }
+            if uses_custom_attributes(schema, declaration) {
+                includes.insert(shared.clone());
+            }
+        }
+        includes.remove(&file.path);
+        file.schema.includes = includes
+            .iter()
+            .map(|include| relative(&file.path, include))
+            .collect();
+    }
+
+    let index = PathBuf::from(index);
+    if files.iter().any(|file| file.path == index) {
+        return Err(eyre!(
+            "The index {} clashes with a declaration",
+            index.display()
+        ));
+    }
+    files.push(SchemaFile {
+        schema: Schema {
+            includes: files
+                .iter()
+                .map(|file| relative(&index, &file.path))
+                .collect(),
+            root_type: schema.root_type.clone(),
+            file_identifier: schema.file_identifier.clone(),
+            file_extension: schema.file_extension.clone(),
+            ..Schema::default()
+        },
+        path: index,
+    });
+
+    Ok(files)
+}
+
+/// Checks that `actual` declares exactly what `expected` does, whatever the order
+pub fn verify(expected: &Schema, actual: &Schema) -> Result<()> {
+    let sorted = |schema: &Schema| {
+        let mut declarations: Vec<Declaration> = schema.declarations.clone();
+        declarations.sort_by_key(|declaration| declaration.qualified_name());
+        declarations
+    };
+    let (expected_declarations, actual_declarations) = (sorted(expected), sorted(actual));
+
+    for declaration in &expected_declarations {
+        let name = declaration.qualified_name();
+        match actual_declarations
+            .iter()
+            .find(|other| other.qualified_name() == name)
+        {
+            Some(other) if other == declaration => {}
+            Some(_) => return Err(eyre!("{} changed", name)),
+            None => return Err(eyre!("{} is missing", name)),
+        }
+    }
+    if let Some(extra) = actual_declarations.iter().find(|declaration| {
+        !expected_declarations
+            .iter()
+            .any(|other| other.qualified_name() == declaration.qualified_name())
+    }) {
+        return Err(eyre!(
+            "{} was not in the original schema",
+            extra.qualified_name()
+        ));
+    }
+
+    let attributes = |schema: &Schema| schema.attributes.iter().cloned().collect::<BTreeSet<_>>();
+    if attributes(expected) != attributes(actual) {
+        return Err(eyre!("The declared attributes changed"));
+    }
+    let root = |schema: &Schema| {
+        schema
+            .root_type
+            .as_deref()
+            .and_then(|root| schema.resolve(root, ""))
+            .map(Declaration::qualified_name)
+    };
+    if root(expected) != root(actual)
+        || expected.file_identifier != actual.file_identifier
+        || expected.file_extension != actual.file_extension
+    {
+        return Err(eyre!("The root type or file identification changed"));
+    }
+
+    Ok(())
+}
+
+/// Tables and unions get a file of their own below their namespace
+fn file_of(declaration: &Declaration) -> PathBuf {
+    match declaration.kind {
+        Kind::Enum(_, _) | Kind::Struct(_) => PathBuf::from(SHARED_FILE),
+        Kind::Table(_) | Kind::Union(_) => {
+            let mut path: PathBuf = declaration
+                .namespace
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .collect();
+            path.push(format!("{}.fbs", declaration.name));
+            path
+        }
+    }
+}
+
+fn uses_custom_attributes(schema: &Schema, declaration: &Declaration) -> bool {
+    declaration
+        .attributes
+        .iter()
+        .chain(
+            declaration
+                .fields()
+                .iter()
+                .flat_map(|field| &field.attributes),
+        )
+        .any(|attribute| schema.attributes.contains(&attribute.name))
+}
+
+/// Include path of `target` from the file `from`, both relative to the same directory
+fn relative(from: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from
+        .parent()
+        .map_or(Vec::new(), |parent| parent.components().collect());
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
@@ -0,0 +1,160 @@
+use crate::schema::parser::parse_integer;
+use crate::schema::{Attribute, Declaration, Field, Kind, Schema};
+
+use std::fmt::{self, Write};
+
+const INDENT: &str = "    ";
+
+impl fmt::Display for Schema {
+    /// Writes the schema back as FlatBuffers IDL in the layout FbsDumper uses
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+
+        for include in &self.includes {
+            let _ = writeln!(output, "include \"{}\";", include);
+        }
+        if !self.includes.is_empty() {
+            output.push('\n');
+        }
+
+        for attribute in &self.attributes {
+            let _ = writeln!(output, "attribute \"{}\";", attribute);
+        }
+        if !self.attributes.is_empty() {
+            output.push('\n');
+        }
+
+        let mut namespace = "";
+        for declaration in &self.declarations {
+            if declaration.namespace != namespace {
+                namespace = &declaration.namespace;
+                if namespace.is_empty() {
+                    output.push_str("namespace;\n\n");
+                } else {
+                    let _ = writeln!(output, "namespace {};\n", namespace);
+                }
+            }
+            write_declaration(&mut output, declaration);
+            output.push('\n');
+        }
+
+        if let Some(root_type) = &self.root_type {
+            let _ = writeln!(output, "root_type {};", root_type);
+        }
+        if let Some(identifier) = &self.file_identifier {
+            let _ = writeln!(output, "file_identifier \"{}\";", identifier);
+        }
+        if let Some(extension) = &self.file_extension {
+            let _ = writeln!(output, "file_extension \"{}\";", extension);
+        }
+
+        f.write_str(output.trim_end_matches('\n'))?;
+        f.write_str("\n")
+    }
+}
+
+fn write_doc(output: &mut String, doc: &[String], indent: &str) {
+    for line in doc {
+        let _ = writeln!(output, "{}///{}", indent, line);
+    }
+}
+
+/// ` (key, id: 1)`, values that are not numbers are quoted again
+pub fn metadata(attributes: &[Attribute]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+
+    let attributes: Vec<String> = attributes
+        .iter()
+        .map(|attribute| match &attribute.value {
+            Some(value) if value.parse::<f64>().is_ok() || parse_integer(value).is_some() => {
+                format!("{}: {}", attribute.name, value)
+            }
+            Some(value) => format!("{}: \"{}\"", attribute.name, value),
+            None => attribute.name.clone(),
+        })
+        .collect();
+    format!(" ({})", attributes.join(", "))
+}
+
+pub fn field(field: &Field) -> String {
+    let default = match &field.default {
+        Some(default) => format!(" = {}", default),
+        None => String::new(),
+    };
+    format!(
+        "{}: {}{}{};",
+        field.name,
+        field.ty,
+        default,
+        metadata(&field.attributes)
+    )
+}
+
+fn write_declaration(output: &mut String, declaration: &Declaration) {
+    write_doc(output, &declaration.doc, "");
+
+    match &declaration.kind {
+        Kind::Table(fields) | Kind::Struct(fields) => {
+            let _ = writeln!(
+                output,
+                "{} {}{} {{",
+                declaration.kind.keyword(),
+                declaration.name,
+                metadata(&declaration.attributes)
+            );
+            for member in fields {
+                write_doc(output, &member.doc, INDENT);
+                let _ = writeln!(output, "{}{}", INDENT, field(member));
+            }
+        }
+        Kind::Enum(underlying, values) => {
+            let _ = writeln!(
+                output,
+                "enum {} : {}{} {{",
+                declaration.name,
+                underlying.name(),
+                metadata(&declaration.attributes)
+            );
+            // bit_flags values are parsed into masks but written as bit positions
+            let bit_flags = declaration.has_attribute("bit_flags");
+            for value in values {
+                write_doc(output, &value.doc, INDENT);
+                if value.explicit {
+                    let number = if bit_flags {
+                        i64::from(value.value.trailing_zeros())
+                    } else {
+                        value.value
+                    };
+                    let _ = writeln!(output, "{}{} = {},", INDENT, value.name, number);
+                } else {
+                    let _ = writeln!(output, "{}{},", INDENT, value.name);
+                }
+            }
+        }
+        Kind::Union(variants) => {
+            let _ = writeln!(
+                output,
+                "union {}{} {{",
+                declaration.name,
+                metadata(&declaration.attributes)
+            );
+            for variant in variants {
+                write_doc(output, &variant.doc, INDENT);
+                let short = variant.ty.rsplit('.').next().unwrap_or(&variant.ty);
+                let mut line = if variant.name == short {
+                    variant.ty.clone()
+                } else {
+                    format!("{}: {}", variant.name, variant.ty)
+                };
+                if variant.explicit {
+                    let _ = write!(line, " = {}", variant.value);
+                }
+                let _ = writeln!(output, "{}{},", INDENT, line);
+            }
+        }
+    }
+
+    output.push_str("}\n");
+}
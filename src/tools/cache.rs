@@ -0,0 +1,102 @@
+use crate::helpers::config::{DUMPS_DIR, SCHEMA_FILE};
+
+use baad::utils::file;
+use eyre::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const FINGERPRINT_LENGTH: usize = 16;
+
+pub struct DumpCache {
+    root: PathBuf,
+}
+
+impl DumpCache {
+    pub fn new(region: &str) -> Result<Self> {
+        let root = file::get_data_path(&format!("{}/{}", DUMPS_DIR, region))?;
+        Ok(Self { root })
+    }
+
+    /// Identifies a game build by the extracted il2cpp binary and metadata
+    pub fn fingerprint(libil2cpp: &Path, metadata: &Path) -> Result<String> {
+        let mut hasher = Sha256::new();
+
+        for path in [libil2cpp, metadata] {
+            let mut reader = File::open(path)
+                .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+            io::copy(&mut reader, &mut hasher)
+                .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        }
+
+        let digest = format!("{:x}", hasher.finalize());
+        Ok(digest[..FINGERPRINT_LENGTH].to_string())
+    }
+
+    pub fn entry(&self, fingerprint: &str) -> PathBuf {
+        self.root.join(fingerprint)
+    }
+
+    pub fn schema(&self, fingerprint: &str) -> Option<PathBuf> {
+        let schema = self.entry(fingerprint).join(SCHEMA_FILE);
+        schema.exists().then_some(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bafb-cache-{}-{}", name, std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fingerprints_the_il2cpp_files() {
+        let dir = scratch("fingerprint");
+        let (libil2cpp, metadata) = (dir.join("libil2cpp.so"), dir.join("global-metadata.dat"));
+        fs::write(&libil2cpp, "library").unwrap();
+        fs::write(&metadata, "metadata").unwrap();
+
+        let fingerprint = DumpCache::fingerprint(&libil2cpp, &metadata).unwrap();
+        assert_eq!(fingerprint.len(), FINGERPRINT_LENGTH);
+        assert_eq!(
+            DumpCache::fingerprint(&libil2cpp, &metadata).unwrap(),
+            fingerprint
+        );
+
+        fs::write(&metadata, "updated metadata").unwrap();
+        assert_ne!(
+            DumpCache::fingerprint(&libil2cpp, &metadata).unwrap(),
+            fingerprint
+        );
+
+        fs::remove_file(&metadata).unwrap();
+        assert!(DumpCache::fingerprint(&libil2cpp, &metadata).is_err());
+    }
+
+    #[test]
+    fn picks_the_cached_dump_of_a_build() {
+        let cache = DumpCache {
+            root: scratch("entries"),
+        };
+        fs::create_dir_all(cache.entry("cached")).unwrap();
+        fs::write(cache.entry("cached").join(SCHEMA_FILE), "").unwrap();
+        // A dump that stopped before FbsDumper wrote the schema
+        fs::create_dir_all(cache.entry("partial")).unwrap();
+
+        assert_eq!(
+            cache.schema("cached"),
+            Some(cache.entry("cached").join(SCHEMA_FILE))
+        );
+        assert_eq!(cache.schema("partial"), None);
+        assert_eq!(cache.schema("missing"), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::schema::export::{rows, target};
+use crate::schema::{Declaration, Kind, Schema, Type};
+
+use eyre::{Context, Result, eyre};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rows of one Excel table, as the serde support of the generated code writes them
+#[derive(Debug)]
+pub struct DecodedTable {
+    /// Qualified name of the row type
+    pub row: String,
+    pub path: PathBuf,
+    pub rows: Vec<Map<String, Value>>,
+}
+
+/// The decoded Excel tables of a directory, a JSON file per table named after its row type or
+/// its `<Row>Table` wrapper, case insensitively
+#[derive(Debug, Default)]
+pub struct DecodedData {
+    pub tables: Vec<DecodedTable>,
+    /// JSON files that are not named after a row type
+    pub skipped: Vec<PathBuf>,
+}
+
+impl DecodedData {
+    pub fn load(schema: &Schema, dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(eyre!("{} is not a directory", dir.display()));
+        }
+
+        // Names shared by row types of different namespaces do not match any of them
+        let mut names: HashMap<String, Option<&Declaration>> = HashMap::new();
+        for row in rows(schema) {
+            for name in [row.name.clone(), format!("{}Table", row.name)] {
+                names
+                    .entry(name.to_lowercase())
+                    .and_modify(|found| *found = None)
+                    .or_insert(Some(row));
+            }
+        }
+
+        let mut files = Vec::new();
+        Self::collect(dir, &mut files)?;
+        files.sort();
+
+        let mut data = Self::default();
+        let mut loaded: HashMap<String, PathBuf> = HashMap::new();
+        for path in files {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let Some(Some(row)) = names.get(&stem) else {
+                data.skipped.push(path);
+                continue;
+            };
+
+            let qualified = row.qualified_name();
+            if let Some(other) = loaded.insert(qualified.clone(), path.clone()) {
+                return Err(eyre!(
+                    "{} and {} both hold {}",
+                    other.display(),
+                    path.display(),
+                    qualified
+                ));
+            }
+
+            let content = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+            let value: Value = serde_json::from_str(&content)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+            let rows = Self::rows(schema, row, value)
+                .wrap_err_with(|| format!("Invalid table {}", path.display()))?;
+
+            data.tables.push(DecodedTable {
+                row: qualified,
+                path,
+                rows,
+            });
+        }
+
+        Ok(data)
+    }
+
+    fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).wrap_err_with(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::collect(&path, files)?;
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// A list of rows, the `<Row>Table` wrapper around it or a single row
+    fn rows(schema: &Schema, row: &Declaration, value: Value) -> Result<Vec<Map<String, Value>>> {
+        let list = match value {
+            Value::Array(list) => list,
+            Value::Object(mut object) => {
+                match Self::list_field(schema, row).map(|field| object.remove(field)) {
+                    Some(Some(Value::Array(list))) => list,
+                    Some(Some(_)) => {
+                        return Err(eyre!("The list of {} is not an array", row.name));
+                    }
+                    // Empty lists are left out
+                    Some(None) if object.is_empty() => Vec::new(),
+                    _ => return Ok(vec![object]),
+                }
+            }
+            _ => return Err(eyre!("Expected a list of {} rows", row.name)),
+        };
+
+        list.into_iter()
+            .enumerate()
+            .map(|(index, value)| match value {
+                Value::Object(object) => Ok(object),
+                _ => Err(eyre!("Row {} is not an object", index)),
+            })
+            .collect()
+    }
+
+    /// The field of `<Row>Table` holding the rows, `DataList` in dumped schemas
+    fn list_field<'a>(schema: &'a Schema, row: &Declaration) -> Option<&'a str> {
+        let wrapper = schema.resolve(&format!("{}Table", row.name), &row.namespace)?;
+        let Kind::Table(fields) = &wrapper.kind else {
+            return None;
+        };
+        fields
+            .iter()
+            .find(|field| match &field.ty {
+                Type::Vector(element) => {
+                    matches!(element.as_ref(), Type::Named(_))
+                        && target(schema, wrapper, &field.ty)
+                            .is_some_and(|target| target.qualified_name() == row.qualified_name())
+                }
+                _ => false,
+            })
+            .map(|field| field.name.as_str())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::helpers::config::*;
 
-use eyre::Result;
+use eyre::{eyre, Result};
 use baad::utils::file;
 use baad::{info, warn};
 use std::io::{Read, Seek};
@@ -58,6 +58,11 @@ impl ToolsExtractor {
         Ok(())
     }
 
+    pub fn binary_path(&self, binary_name: &str, path: &str) -> Result<PathBuf> {
+        let target_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, path))?;
+        Ok(target_path.join(Self::get_binary_name(binary_name)))
+    }
+
     fn extract_tool(
         &self,
         binary_name: &str,
@@ -72,7 +77,7 @@ impl ToolsExtractor {
 
         let target_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, path))?;
         let binary_name_with_ext = Self::get_binary_name(binary_name);
-        let binary_path = target_path.join(&binary_name_with_ext);
+        let binary_path = self.binary_path(binary_name, path)?;
 
         if binary_path.exists() && !forced {
             warn!("{} already extracted, skipping...", tool_name);
@@ -82,6 +87,13 @@ impl ToolsExtractor {
         info!("Extracting {}...", tool_name);
 
         let zip_path = file::get_data_path(&format!("{}/{}", TOOLS_DIR, zip_file))?;
+        if !zip_path.exists() {
+            return Err(eyre!(
+                "{} archive not found at {}, download it first",
+                tool_name,
+                zip_path.display()
+            ));
+        }
 
         fs::create_dir_all(&target_path)?;
         let file = fs::File::open(zip_path)?;
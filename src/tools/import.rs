@@ -0,0 +1,243 @@
+use crate::schema::export::sql::{
+    self, Column, ColumnKind, Database, Dialect, Relation, SqlValue, literal, quote,
+};
+use crate::schema::{Scalar, Schema};
+use crate::tools::decoded::DecodedData;
+
+use eyre::{Context, Result, eyre};
+use rusqlite::types::Value as SqliteValue;
+use rusqlite::{Connection, params_from_iter};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+/// Loads decoded Excel tables into the tables `bafb schema export --format sqlite` creates
+pub struct Importer<'a> {
+    schema: &'a Schema,
+    database: Database,
+}
+
+impl<'a> Importer<'a> {
+    pub fn new(schema: &'a Schema) -> Result<Self> {
+        Ok(Self {
+            schema,
+            database: Database::new(schema)?,
+        })
+    }
+
+    /// Creates a SQLite database at `output`, replacing any existing one, and returns the number
+    /// of rows imported
+    pub fn sqlite(&self, data: &DecodedData, output: &Path) -> Result<usize> {
+        if output.exists() {
+            fs::remove_file(output)
+                .wrap_err_with(|| format!("Failed to remove {}", output.display()))?;
+        }
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let mut connection = Connection::open(output)
+            .wrap_err_with(|| format!("Failed to open {}", output.display()))?;
+        connection.execute_batch("PRAGMA foreign_keys = ON;")?;
+        connection
+            .execute_batch(&self.database.ddl(Dialect::Sqlite))
+            .wrap_err("Failed to create the tables")?;
+
+        let transaction = connection.transaction()?;
+        let rows = self.rows(data, |relation, values| {
+            let mut statement = transaction.prepare_cached(&Self::insert(relation, None))?;
+            statement.execute(params_from_iter(values.into_iter().map(Self::sqlite_value)))?;
+            Ok(())
+        })?;
+        transaction.commit()?;
+
+        Ok(rows)
+    }
+
+    /// A script creating the tables and inserting the rows in one transaction, with the number of
+    /// rows imported
+    pub fn script(&self, data: &DecodedData, dialect: Dialect) -> Result<(String, usize)> {
+        let mut output = self.database.ddl(dialect);
+        output.push_str("\nBEGIN;\n");
+        let rows = self.rows(data, |relation, values| {
+            let values: Vec<String> = values.iter().map(|value| literal(value, dialect)).collect();
+            output.push_str(&Self::insert(relation, Some(&values)));
+            output.push_str(";\n");
+            Ok(())
+        })?;
+        output.push_str("COMMIT;\n");
+
+        Ok((output, rows))
+    }
+
+    /// An insert of `values`, or of numbered parameters
+    fn insert(relation: &Relation, values: Option<&[String]>) -> String {
+        let columns: Vec<String> = relation
+            .columns
+            .iter()
+            .map(|column| quote(&column.name))
+            .collect();
+        let values = match values {
+            Some(values) => values.join(", "),
+            None => (1..=columns.len())
+                .map(|index| format!("?{}", index))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote(&relation.name),
+            columns.join(", "),
+            values
+        )
+    }
+
+    fn sqlite_value(value: SqlValue) -> SqliteValue {
+        match value {
+            SqlValue::Null => SqliteValue::Null,
+            SqlValue::Bool(value) => SqliteValue::Integer(value.into()),
+            SqlValue::Integer(value) => SqliteValue::Integer(value),
+            SqlValue::Unsigned(value) => SqliteValue::Text(value.to_string()),
+            SqlValue::Real(value) => SqliteValue::Real(value),
+            SqlValue::Text(value) => SqliteValue::Text(value),
+        }
+    }
+
+    /// Visits the rows of every table with the values of their columns, the elements of their
+    /// lists right after them, and returns the number of rows
+    fn rows(
+        &self,
+        data: &DecodedData,
+        mut visit: impl FnMut(&Relation, Vec<SqlValue>) -> Result<()>,
+    ) -> Result<usize> {
+        let mut count = 0;
+
+        for table in &data.tables {
+            let row = self
+                .schema
+                .resolve(&table.row, "")
+                .ok_or_else(|| eyre!("Unknown row type {}", table.row))?;
+            let keys = sql::keys(self.schema, row);
+            let relations: Vec<&Relation> = self
+                .database
+                .relations
+                .iter()
+                .filter(|relation| relation.row == table.row)
+                .collect();
+
+            for (index, object) in table.rows.iter().enumerate() {
+                let id = index as i64 + 1;
+                let result = (|| -> Result<()> {
+                    if let Some(key) = object.keys().find(|key| !keys.contains(key)) {
+                        return Err(eyre!("Unknown field {}", key));
+                    }
+
+                    for relation in &relations {
+                        let Some(field) = &relation.field else {
+                            let values =
+                                self.values(relation, id, None, |path| at(object, path))?;
+                            visit(relation, values)?;
+                            continue;
+                        };
+
+                        let elements = match object.get(field) {
+                            None | Some(Value::Null) => continue,
+                            Some(Value::Array(elements)) => elements,
+                            Some(_) => return Err(eyre!("{} is not a list", field)),
+                        };
+                        for (position, element) in elements.iter().enumerate() {
+                            let values = self.values(relation, id, Some(position), |path| {
+                                path.iter().try_fold(element, |value, key| value.get(key))
+                            })?;
+                            visit(relation, values)?;
+                        }
+                    }
+                    Ok(())
+                })();
+                result.wrap_err_with(|| {
+                    format!("Invalid row {} of {}", index, table.path.display())
+                })?;
+            }
+
+            count += table.rows.len();
+        }
+
+        Ok(count)
+    }
+
+    fn values<'v>(
+        &self,
+        relation: &Relation,
+        row: i64,
+        position: Option<usize>,
+        get: impl Fn(&[String]) -> Option<&'v Value>,
+    ) -> Result<Vec<SqlValue>> {
+        relation
+            .columns
+            .iter()
+            .map(|column| match column.kind {
+                ColumnKind::Row => Ok(SqlValue::Integer(row)),
+                ColumnKind::Index => Ok(SqlValue::Integer(position.unwrap_or_default() as i64)),
+                _ => self.value(column, get(&column.path)),
+            })
+            .collect()
+    }
+
+    fn value(&self, column: &Column, value: Option<&Value>) -> Result<SqlValue> {
+        let value = match value {
+            None | Some(Value::Null) if column.not_null => {
+                return Err(eyre!("{} is missing", column.name));
+            }
+            None | Some(Value::Null) => return Ok(SqlValue::Null),
+            Some(value) => value,
+        };
+
+        let converted = match (&column.kind, value) {
+            (ColumnKind::Scalar(Scalar::Bool), Value::Bool(value)) => Some(SqlValue::Bool(*value)),
+            (ColumnKind::Scalar(Scalar::Float | Scalar::Double), Value::Number(number)) => {
+                number.as_f64().map(SqlValue::Real)
+            }
+            (ColumnKind::Scalar(Scalar::ULong), Value::Number(number)) => {
+                number.as_u64().map(SqlValue::Unsigned)
+            }
+            (ColumnKind::Scalar(scalar), Value::Number(number)) => number
+                .as_i64()
+                .filter(|number| {
+                    scalar
+                        .range()
+                        .is_some_and(|(min, max)| (min..=max).contains(&i128::from(*number)))
+                })
+                .map(SqlValue::Integer),
+            (ColumnKind::String, Value::String(value)) => Some(SqlValue::Text(value.clone())),
+            // Values without a name are written as numbers
+            (ColumnKind::Name(lookup), value) => {
+                let lookup = self
+                    .database
+                    .lookup(lookup)
+                    .ok_or_else(|| eyre!("Unknown lookup table {}", lookup))?;
+                match value {
+                    Value::String(name) if lookup.values.iter().any(|(known, _)| known == name) => {
+                        Some(SqlValue::Text(name.clone()))
+                    }
+                    Value::Number(number) => number
+                        .as_i64()
+                        .and_then(|number| lookup.name_of(number))
+                        .map(|name| SqlValue::Text(name.to_string())),
+                    _ => None,
+                }
+            }
+            (ColumnKind::Json, value) => Some(SqlValue::Text(value.to_string())),
+            _ => None,
+        };
+
+        converted.ok_or_else(|| eyre!("{} cannot hold {}", column.name, value))
+    }
+}
+
+/// The value at `path` in a row
+fn at<'v>(object: &'v Map<String, Value>, path: &[String]) -> Option<&'v Value> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(object.get(first)?, |value, key| value.get(key))
+}
@@ -1,2 +1,9 @@
 pub mod fetch;
-pub mod extract;
\ No newline at end of file
+pub mod extract;
+pub mod preflight;
+pub mod cache;
+pub mod postprocess;
+pub mod package;
+pub mod decoded;
+pub mod import;
+pub mod serve;
@@ -0,0 +1,137 @@
+use crate::helpers::config::{MODULE_ROOT_FILE, REGION_FEATURES, SERDE_VERSION};
+use crate::tools::package::{GeneratedPackage, PackageMetadata};
+use crate::tools::postprocess::ModuleRoot;
+
+use eyre::{eyre, Context, Result};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LIB_FILE: &str = "lib.rs";
+const LIB_ATTRIBUTES: &str = "#![allow(unused_imports, dead_code, non_camel_case_types, non_snake_case, clippy::all)]";
+
+pub struct CargoPackage {
+    metadata: PackageMetadata,
+    serialize: bool,
+    module_root: String,
+}
+
+impl CargoPackage {
+    pub fn new(metadata: PackageMetadata, serialize: bool) -> Self {
+        Self {
+            metadata,
+            serialize,
+            module_root: MODULE_ROOT_FILE.to_string(),
+        }
+    }
+
+    pub fn module_root(mut self, module_root: &str) -> Self {
+        self.module_root = module_root.to_string();
+        self
+    }
+
+    fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn manifest(&self, features: &[String]) -> Result<String> {
+        let mut manifest = String::new();
+
+        writeln!(manifest, "[package]")?;
+        writeln!(manifest, "name = \"{}\"", self.metadata.name)?;
+        writeln!(manifest, "version = \"{}\"", self.metadata.version)?;
+        writeln!(manifest, "edition = \"2021\"")?;
+        writeln!(manifest)?;
+
+        writeln!(manifest, "[dependencies]")?;
+        if self.serialize {
+            writeln!(
+                manifest,
+                "flatbuffers = {{ version = \"{}\", features = [\"serialize\"] }}",
+                self.metadata.flatbuffers_version
+            )?;
+            writeln!(manifest, "serde = \"{}\"", SERDE_VERSION)?;
+        } else {
+            writeln!(manifest, "flatbuffers = \"{}\"", self.metadata.flatbuffers_version)?;
+        }
+
+        if !features.is_empty() {
+            writeln!(manifest)?;
+            writeln!(manifest, "[features]")?;
+            let quoted: Vec<String> = features.iter().map(|f| format!("\"{}\"", f)).collect();
+            writeln!(manifest, "default = [{}]", quoted.join(", "))?;
+            for feature in features {
+                writeln!(manifest, "{} = []", feature)?;
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl GeneratedPackage for CargoPackage {
+    fn source_dir(&self, root: &Path) -> PathBuf {
+        root.join("src")
+    }
+
+    fn write(&self, root: &Path) -> Result<()> {
+        if !Self::is_valid_name(&self.metadata.name) {
+            return Err(eyre!(
+                "Invalid crate name `{}`, use letters, digits, `-` and `_`",
+                self.metadata.name
+            ));
+        }
+
+        let source = self.source_dir(root);
+        let module_root = source.join(&self.module_root);
+        let lib = source.join(LIB_FILE);
+
+        // Earlier runs may have written other regions into lib.rs already
+        let mut modules = ModuleRoot::default();
+        modules.push_prelude(LIB_ATTRIBUTES);
+        if lib.exists() {
+            modules.merge(ModuleRoot::parse(&fs::read_to_string(&lib)?));
+        }
+        if module_root.exists() {
+            modules.merge(ModuleRoot::parse(&fs::read_to_string(&module_root)?));
+            fs::remove_file(&module_root)?;
+        } else if !lib.exists() {
+            return Err(eyre!(
+                "{} not found, generate with rust_module_root_file enabled",
+                module_root.display()
+            ));
+        }
+
+        let features: Vec<String> = modules
+            .names()
+            .filter(|name| REGION_FEATURES.contains(name))
+            .map(str::to_string)
+            .collect();
+        for feature in &features {
+            modules.annotate(feature, &format!("#[cfg(feature = \"{}\")]", feature));
+        }
+
+        fs::write(&lib, modules.render())
+            .wrap_err_with(|| format!("Failed to write {}", lib.display()))?;
+
+        let manifest = root.join("Cargo.toml");
+        fs::write(&manifest, self.manifest(&features)?)
+            .wrap_err_with(|| format!("Failed to write {}", manifest.display()))?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CargoPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cargo crate {} {}",
+            self.metadata.name, self.metadata.version
+        )
+    }
+}
@@ -0,0 +1,269 @@
+pub mod cargo;
+pub mod npm;
+pub mod nuget;
+pub mod python;
+
+use eyre::{Context, Result, eyre};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Name and versions shared by every package format
+#[derive(Debug, Clone)]
+pub struct PackageMetadata {
+    pub name: String,
+    pub version: String,
+    pub flatbuffers_version: String,
+}
+
+impl PackageMetadata {
+    pub fn new(name: &str, version: &str, flatbuffers_version: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            flatbuffers_version: flatbuffers_version.to_string(),
+        }
+    }
+
+    /// Date based version (`YEAR.MONTH.DAY`) of a `YYYY-MM-DD` dump date
+    pub fn date_version(date: &str) -> Result<String> {
+        let parts: Vec<&str> = date.split('-').collect();
+        let valid = parts.len() == 3
+            && parts.iter().zip([4, 2, 2]).all(|(part, length)| {
+                part.len() == length && part.chars().all(|c| c.is_ascii_digit())
+            });
+        if !valid {
+            return Err(eyre!("Expected a YYYY-MM-DD dump date, got {}", date));
+        }
+
+        // Leading zeros are not valid in semantic versions
+        let numbers: Vec<String> = parts
+            .iter()
+            .map(|part| part.parse::<u32>().unwrap_or_default().to_string())
+            .collect();
+        Ok(numbers.join("."))
+    }
+
+    /// Year, month and day of `time` in UTC
+    pub fn civil_date(time: SystemTime) -> (i64, i64, i64) {
+        let days = time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() / SECONDS_PER_DAY)
+            .unwrap_or_default() as i64;
+
+        // Civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        (year, month, day)
+    }
+}
+
+/// A package manifest written around the code flatc generated for one language
+pub trait GeneratedPackage: fmt::Display + Send {
+    /// Directory flatc should write the sources into
+    fn source_dir(&self, root: &Path) -> PathBuf {
+        root.to_path_buf()
+    }
+
+    fn write(&self, root: &Path) -> Result<()>;
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries =
+            fs::read_dir(&dir).wrap_err_with(|| format!("Failed to read {}", dir.display()))?;
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == extension) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::npm::NpmPackage;
+    use super::nuget::NugetPackage;
+    use super::python::PythonPackage;
+    use super::*;
+    use crate::schema::{Declaration, Schema};
+
+    fn metadata() -> PackageMetadata {
+        PackageMetadata::new("BaFlatData", "1.46.0", "25.9.23")
+    }
+
+    /// An empty file for every declaration of `tests/fixtures/excel.fbs`, at `path` of it as
+    /// flatc would write them
+    fn generated(name: &str, path: impl Fn(&Declaration) -> String) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("bafb-package-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("excel.fbs");
+        for declaration in &Schema::load(&fixture).unwrap().declarations {
+            let file = root.join(path(declaration));
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(&file, "").unwrap();
+        }
+        root
+    }
+
+    /// `FlatData.Nested` as `flat-data/nested`, the way flatc names TypeScript modules
+    fn kebab(name: &str) -> String {
+        let mut kebab = String::new();
+        for (i, c) in name.chars().enumerate() {
+            match c {
+                '.' => kebab.push('/'),
+                c if c.is_ascii_uppercase() => {
+                    if i > 0 && !kebab.ends_with('/') {
+                        kebab.push('-');
+                    }
+                    kebab.push(c.to_ascii_lowercase());
+                }
+                c => kebab.push(c),
+            }
+        }
+        kebab
+    }
+
+    #[test]
+    fn versions_by_dump_date() {
+        assert_eq!(
+            PackageMetadata::date_version("2025-06-17").unwrap(),
+            "2025.6.17"
+        );
+        for date in ["2025-6-17", "17-06-2025", "2025-06-17T00:00", "2025-o6-17"] {
+            let error = PackageMetadata::date_version(date).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("Expected a YYYY-MM-DD dump date")
+            );
+        }
+    }
+
+    #[test]
+    fn packages_python_namespaces() {
+        let root = generated("python", |declaration| {
+            format!(
+                "{}/{}.py",
+                declaration.namespace.replace('.', "/"),
+                declaration.name
+            )
+        });
+        PythonPackage::new(metadata(), Some("3.9".to_string()))
+            .write(&root)
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("pyproject.toml")).unwrap(),
+            "[build-system]
+requires = [\"setuptools>=61\"]
+build-backend = \"setuptools.build_meta\"
+
+[project]
+name = \"BaFlatData\"
+version = \"1.46.0\"
+requires-python = \">=3.9\"
+dependencies = [\"flatbuffers==25.9.23\"]
+
+[tool.setuptools.packages.find]
+include = [\"FlatData*\"]
+"
+        );
+        assert!(root.join("FlatData/__init__.py").is_file());
+        assert!(root.join("FlatData/Nested/__init__.py").is_file());
+        assert!(!root.join("__init__.py").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn packages_typescript_entry_points() {
+        let root = generated("npm", |declaration| {
+            format!(
+                "{}/{}.ts",
+                kebab(&declaration.namespace),
+                kebab(&declaration.name)
+            )
+        });
+        fs::write(root.join("flat-data.ts"), "").unwrap();
+        NpmPackage::new(metadata(), true).write(&root).unwrap();
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(root.join("package.json")).unwrap()).unwrap();
+        assert_eq!(manifest["name"], "baflatdata");
+        assert_eq!(manifest["version"], "1.46.0");
+        assert_eq!(manifest["dependencies"]["flatbuffers"], "^25.9.23");
+        assert_eq!(manifest["files"], serde_json::json!(["dist"]));
+        let entry = serde_json::json!({
+            "types": "./dist/flat-data.d.ts",
+            "default": "./dist/flat-data.js",
+        });
+        assert_eq!(manifest["exports"]["."], entry);
+        assert_eq!(manifest["exports"]["./flat-data"], entry);
+        assert_eq!(manifest["exports"].as_object().unwrap().len(), 2);
+        assert!(root.join("tsconfig.json").is_file());
+        fs::remove_dir_all(&root).unwrap();
+
+        // Without entry points there is nothing to export
+        let root = generated("npm-empty", |declaration| {
+            format!(
+                "{}/{}.ts",
+                kebab(&declaration.namespace),
+                kebab(&declaration.name)
+            )
+        });
+        let error = NpmPackage::new(metadata(), true).write(&root).unwrap_err();
+        assert!(error.to_string().contains("enable flatc.ts_entry_points"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn packages_csharp_projects() {
+        let root = generated("nuget", |declaration| {
+            format!(
+                "{}/{}.cs",
+                declaration.namespace.replace('.', "/"),
+                declaration.name
+            )
+        });
+        NugetPackage::new(metadata(), true).write(&root).unwrap();
+
+        let project = fs::read_to_string(root.join("BaFlatData.csproj")).unwrap();
+        assert!(project.contains("<TargetFramework>netstandard2.1</TargetFramework>"));
+        assert!(project.contains("<PackageId>BaFlatData</PackageId>"));
+        assert!(project.contains("<Version>1.46.0</Version>"));
+        assert!(
+            project.contains(
+                "<PackageReference Include=\"Google.FlatBuffers\" Version=\"25.9.23\" />"
+            )
+        );
+        assert!(project.contains("<PackageReference Include=\"Newtonsoft.Json\""));
+        assert!(root.join("FlatData/Nested/GroupExcel.cs").is_file());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
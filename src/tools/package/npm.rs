@@ -0,0 +1,138 @@
+use crate::tools::package::{GeneratedPackage, PackageMetadata};
+
+use eyre::{eyre, Context, Result};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const OUT_DIR: &str = "dist";
+const TYPESCRIPT_VERSION: &str = "^5.0.0";
+
+pub struct NpmPackage {
+    metadata: PackageMetadata,
+    typescript: bool,
+}
+
+impl NpmPackage {
+    pub fn new(metadata: PackageMetadata, typescript: bool) -> Self {
+        Self {
+            metadata,
+            typescript,
+        }
+    }
+
+    /// Top level modules flatc wrote, one per namespace with `ts_entry_points`
+    fn entry_points(&self, root: &Path) -> Result<Vec<String>> {
+        let extension = if self.typescript { "ts" } else { "js" };
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+                continue;
+            };
+
+            if path.is_file()
+                && path.extension().is_some_and(|ext| ext == extension)
+                && !name.ends_with(".d.ts")
+                && let Some(stem) = path.file_stem()
+            {
+                entries.push(stem.to_string_lossy().into_owned());
+            }
+        }
+
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn export(&self, entry: &str) -> Value {
+        if self.typescript {
+            json!({
+                "types": format!("./{}/{}.d.ts", OUT_DIR, entry),
+                "default": format!("./{}/{}.js", OUT_DIR, entry),
+            })
+        } else {
+            json!(format!("./{}.js", entry))
+        }
+    }
+
+    fn manifest(&self, entries: &[String]) -> Value {
+        let mut exports = Map::new();
+        if let [entry] = entries {
+            exports.insert(".".to_string(), self.export(entry));
+        }
+        for entry in entries {
+            exports.insert(format!("./{}", entry), self.export(entry));
+        }
+
+        let mut manifest = json!({
+            "name": self.metadata.name.to_lowercase(),
+            "version": self.metadata.version,
+            "type": "module",
+            "exports": exports,
+            "dependencies": {
+                "flatbuffers": format!("^{}", self.metadata.flatbuffers_version),
+            },
+        });
+
+        if self.typescript {
+            manifest["files"] = json!([OUT_DIR]);
+            manifest["scripts"] = json!({ "build": "tsc", "prepare": "tsc" });
+            manifest["devDependencies"] = json!({ "typescript": TYPESCRIPT_VERSION });
+        }
+
+        manifest
+    }
+
+    fn tsconfig() -> Value {
+        json!({
+            "compilerOptions": {
+                "target": "ES2020",
+                "module": "ES2020",
+                "moduleResolution": "bundler",
+                "declaration": true,
+                "skipLibCheck": true,
+                "rootDir": ".",
+                "outDir": OUT_DIR,
+            },
+            "include": ["**/*.ts"],
+            "exclude": [OUT_DIR, "node_modules"],
+        })
+    }
+}
+
+impl GeneratedPackage for NpmPackage {
+    fn write(&self, root: &Path) -> Result<()> {
+        let entries = self.entry_points(root)?;
+        if entries.is_empty() {
+            return Err(eyre!(
+                "No entry points found in {}, enable flatc.ts_entry_points",
+                root.display()
+            ));
+        }
+
+        let manifest = root.join("package.json");
+        fs::write(&manifest, serde_json::to_string_pretty(&self.manifest(&entries))?)
+            .wrap_err_with(|| format!("Failed to write {}", manifest.display()))?;
+
+        if self.typescript {
+            let tsconfig = root.join("tsconfig.json");
+            fs::write(&tsconfig, serde_json::to_string_pretty(&Self::tsconfig())?)
+                .wrap_err_with(|| format!("Failed to write {}", tsconfig.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for NpmPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "npm package {} {}",
+            self.metadata.name.to_lowercase(),
+            self.metadata.version
+        )
+    }
+}
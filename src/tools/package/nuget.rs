@@ -0,0 +1,76 @@
+use crate::tools::package::{GeneratedPackage, PackageMetadata};
+
+use eyre::{Context, Result};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::Path;
+
+const TARGET_FRAMEWORK: &str = "netstandard2.1";
+const NEWTONSOFT_JSON_VERSION: &str = "13.0.3";
+
+pub struct NugetPackage {
+    metadata: PackageMetadata,
+    json_serializer: bool,
+}
+
+impl NugetPackage {
+    pub fn new(metadata: PackageMetadata, json_serializer: bool) -> Self {
+        Self {
+            metadata,
+            json_serializer,
+        }
+    }
+
+    fn project(&self) -> Result<String> {
+        let mut project = String::new();
+
+        writeln!(project, "<Project Sdk=\"Microsoft.NET.Sdk\">")?;
+        writeln!(project)?;
+        writeln!(project, "  <PropertyGroup>")?;
+        writeln!(project, "    <TargetFramework>{}</TargetFramework>", TARGET_FRAMEWORK)?;
+        writeln!(project, "    <PackageId>{}</PackageId>", self.metadata.name)?;
+        writeln!(project, "    <Version>{}</Version>", self.metadata.version)?;
+        writeln!(project, "    <LangVersion>latest</LangVersion>")?;
+        writeln!(project, "  </PropertyGroup>")?;
+        writeln!(project)?;
+        writeln!(project, "  <ItemGroup>")?;
+        writeln!(
+            project,
+            "    <PackageReference Include=\"Google.FlatBuffers\" Version=\"{}\" />",
+            self.metadata.flatbuffers_version
+        )?;
+        if self.json_serializer {
+            // cs_gen_json_serializer emits Newtonsoft.Json attributes
+            writeln!(
+                project,
+                "    <PackageReference Include=\"Newtonsoft.Json\" Version=\"{}\" />",
+                NEWTONSOFT_JSON_VERSION
+            )?;
+        }
+        writeln!(project, "  </ItemGroup>")?;
+        writeln!(project)?;
+        writeln!(project, "</Project>")?;
+
+        Ok(project)
+    }
+}
+
+impl GeneratedPackage for NugetPackage {
+    fn write(&self, root: &Path) -> Result<()> {
+        let project = root.join(format!("{}.csproj", self.metadata.name));
+        fs::write(&project, self.project()?)
+            .wrap_err_with(|| format!("Failed to write {}", project.display()))?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for NugetPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "nuget package {} {}",
+            self.metadata.name, self.metadata.version
+        )
+    }
+}
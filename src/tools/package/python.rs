@@ -0,0 +1,104 @@
+use crate::tools::package::{files_with_extension, GeneratedPackage, PackageMetadata};
+
+use eyre::{eyre, Context, Result};
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::Path;
+
+const INIT_FILE: &str = "__init__.py";
+
+pub struct PythonPackage {
+    metadata: PackageMetadata,
+    python_version: Option<String>,
+}
+
+impl PythonPackage {
+    pub fn new(metadata: PackageMetadata, python_version: Option<String>) -> Self {
+        Self {
+            metadata,
+            python_version,
+        }
+    }
+
+    fn manifest(&self, packages: &BTreeSet<String>) -> Result<String> {
+        let mut manifest = String::new();
+
+        writeln!(manifest, "[build-system]")?;
+        writeln!(manifest, "requires = [\"setuptools>=61\"]")?;
+        writeln!(manifest, "build-backend = \"setuptools.build_meta\"")?;
+        writeln!(manifest)?;
+
+        writeln!(manifest, "[project]")?;
+        writeln!(manifest, "name = \"{}\"", self.metadata.name)?;
+        writeln!(manifest, "version = \"{}\"", self.metadata.version)?;
+        if let Some(python_version) = &self.python_version {
+            writeln!(manifest, "requires-python = \">={}\"", python_version)?;
+        }
+        writeln!(
+            manifest,
+            "dependencies = [\"flatbuffers=={}\"]",
+            self.metadata.flatbuffers_version
+        )?;
+        writeln!(manifest)?;
+
+        // flatc imports namespaces absolutely, so each one stays a top level package
+        let include: Vec<String> = packages
+            .iter()
+            .map(|package| format!("\"{}*\"", package))
+            .collect();
+        writeln!(manifest, "[tool.setuptools.packages.find]")?;
+        writeln!(manifest, "include = [{}]", include.join(", "))?;
+
+        Ok(manifest)
+    }
+}
+
+impl GeneratedPackage for PythonPackage {
+    fn write(&self, root: &Path) -> Result<()> {
+        let mut packages = BTreeSet::new();
+
+        for file in files_with_extension(root, "py")? {
+            let Ok(relative) = file.strip_prefix(root) else {
+                continue;
+            };
+
+            let mut components = relative.parent().into_iter().flat_map(Path::components);
+            let Some(top) = components.next() else {
+                continue;
+            };
+            packages.insert(top.as_os_str().to_string_lossy().into_owned());
+
+            for dir in file.parent().into_iter().flat_map(Path::ancestors) {
+                if dir == root {
+                    break;
+                }
+                let init = dir.join(INIT_FILE);
+                if !init.exists() {
+                    fs::write(&init, "")
+                        .wrap_err_with(|| format!("Failed to write {}", init.display()))?;
+                }
+            }
+        }
+
+        if packages.is_empty() {
+            return Err(eyre!("No Python namespaces found in {}", root.display()));
+        }
+
+        let manifest = root.join("pyproject.toml");
+        fs::write(&manifest, self.manifest(&packages)?)
+            .wrap_err_with(|| format!("Failed to write {}", manifest.display()))?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PythonPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "python package {} {}",
+            self.metadata.name, self.metadata.version
+        )
+    }
+}
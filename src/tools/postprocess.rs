@@ -0,0 +1,323 @@
+use crate::helpers::config::{MODULE_ROOT_FILE, STAGING_DIR};
+
+use eyre::{Context, Result, eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BANNERS: &[&str] = &["// Automatically generated", "// @generated"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostProcessOptions {
+    pub header: Option<String>,
+    pub prelude: Vec<String>,
+    pub strip_banners: bool,
+    pub flatten: bool,
+    pub module_root: Option<String>,
+    pub merge: Vec<PathBuf>,
+}
+
+impl PostProcessOptions {
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// Top level items of a flatc module root file, one `pub mod` block per namespace
+#[derive(Debug, Default)]
+pub struct ModuleRoot {
+    prelude: Vec<String>,
+    blocks: Vec<(String, String)>,
+}
+
+impl ModuleRoot {
+    pub fn parse(content: &str) -> Self {
+        let mut root = Self::default();
+        let mut attributes = String::new();
+        let mut block: Option<(String, String)> = None;
+        let mut depth = 0usize;
+
+        for line in content.lines() {
+            if let Some((_, body)) = &mut block {
+                body.push_str(line);
+                body.push('\n');
+            } else if line.starts_with("#[") {
+                attributes.push_str(line);
+                attributes.push('\n');
+                continue;
+            } else if line.contains('{') {
+                let name = line
+                    .trim_start_matches("pub ")
+                    .trim_start_matches("mod ")
+                    .trim_end_matches('{')
+                    .trim()
+                    .to_string();
+                block = Some((name, format!("{}{}\n", attributes, line)));
+                attributes.clear();
+            } else if !line.trim().is_empty() && !PostProcessor::is_banner(line) {
+                root.push_prelude(line);
+                continue;
+            } else {
+                continue;
+            }
+
+            depth += line.matches('{').count();
+            depth = depth.saturating_sub(line.matches('}').count());
+            if depth == 0
+                && let Some(finished) = block.take()
+            {
+                root.push_block(finished);
+            }
+        }
+
+        if let Some(unfinished) = block {
+            root.push_block(unfinished);
+        }
+
+        root
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.blocks.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn push_prelude(&mut self, line: &str) {
+        if !self.prelude.iter().any(|existing| existing == line) {
+            self.prelude.push(line.to_string());
+        }
+    }
+
+    /// Puts `attribute` in front of the block called `name` unless it is already there
+    pub fn annotate(&mut self, name: &str, attribute: &str) {
+        for (_, body) in self.blocks.iter_mut().filter(|(block, _)| block == name) {
+            if !body.lines().any(|line| line == attribute) {
+                *body = format!("{}\n{}", attribute, body);
+            }
+        }
+    }
+
+    fn push_block(&mut self, (name, body): (String, String)) {
+        match self.blocks.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(existing) => existing.1 = body,
+            None => self.blocks.push((name, body)),
+        }
+    }
+
+    pub fn merge(&mut self, other: ModuleRoot) {
+        for line in &other.prelude {
+            self.push_prelude(line);
+        }
+        for block in other.blocks {
+            self.push_block(block);
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        for line in &self.prelude {
+            output.push_str(line);
+            output.push('\n');
+        }
+
+        for (_, body) in &self.blocks {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(body);
+        }
+
+        output
+    }
+}
+
+pub struct PostProcessor {
+    options: PostProcessOptions,
+}
+
+impl PostProcessor {
+    pub fn new(options: PostProcessOptions) -> Self {
+        Self { options }
+    }
+
+    /// Directory flatc should write into before the output is processed
+    pub fn generated_dir(&self, output: &Path) -> PathBuf {
+        if self.options.flatten {
+            output.join(STAGING_DIR)
+        } else {
+            output.to_path_buf()
+        }
+    }
+
+    pub fn run(&self, output: &Path) -> Result<()> {
+        let generated = self.generated_dir(output);
+
+        if let Some(header) = &self.options.header {
+            for file in Self::rust_files(&generated)? {
+                if file.parent() == Some(generated.as_path())
+                    && file.file_name().is_some_and(|name| name == MODULE_ROOT_FILE)
+                {
+                    continue;
+                }
+
+                let content = fs::read_to_string(&file)
+                    .wrap_err_with(|| format!("Failed to read {}", file.display()))?;
+                fs::write(&file, format!("{}\n{}", header.trim_end(), content))
+                    .wrap_err_with(|| format!("Failed to write {}", file.display()))?;
+            }
+        }
+
+        let module_root = self.module_root(&generated)?;
+
+        if self.options.flatten {
+            Self::move_entries(&generated, output)?;
+        }
+
+        if let Some(content) = module_root {
+            let generated_root = output.join(MODULE_ROOT_FILE);
+            let target = output.join(
+                self.options
+                    .module_root
+                    .as_deref()
+                    .unwrap_or(MODULE_ROOT_FILE),
+            );
+
+            if target != generated_root && generated_root.exists() && !self.options.flatten {
+                fs::remove_file(&generated_root)?;
+            }
+            fs::write(&target, content)
+                .wrap_err_with(|| format!("Failed to write {}", target.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the module root from the prelude and the roots in `merge` alone, for namespaces
+    /// generated by earlier runs
+    pub fn merge_roots(&self, output: &Path) -> Result<PathBuf> {
+        if self.options.merge.is_empty() {
+            return Err(eyre!("postprocess.merge lists no module roots to merge"));
+        }
+
+        let target = output.join(
+            self.options
+                .module_root
+                .as_deref()
+                .unwrap_or(MODULE_ROOT_FILE),
+        );
+        let content = format!("{}{}", self.header(), self.merged_root()?.render());
+        fs::write(&target, content)
+            .wrap_err_with(|| format!("Failed to write {}", target.display()))?;
+
+        Ok(target)
+    }
+
+    fn is_banner(line: &str) -> bool {
+        BANNERS.iter().any(|banner| line.starts_with(banner))
+    }
+
+    fn module_root(&self, generated: &Path) -> Result<Option<String>> {
+        let path = generated.join(MODULE_ROOT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut root = self.merged_root()?;
+        let content = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+
+        let rendered = if self.options.strip_banners {
+            root.merge(ModuleRoot::parse(&content));
+            root.render()
+        } else {
+            // Keep flatc's banner at the top when it is not stripped
+            let banner: String = content
+                .lines()
+                .take_while(|line| Self::is_banner(line))
+                .map(|line| format!("{}\n", line))
+                .collect();
+            root.merge(ModuleRoot::parse(&content));
+            format!("{}{}", banner, root.render())
+        };
+
+        if self.options.flatten {
+            fs::remove_file(&path)?;
+        }
+
+        Ok(Some(format!("{}{}", self.header(), rendered)))
+    }
+
+    /// The prelude and every existing root in `merge`, later roots replace earlier namespaces
+    fn merged_root(&self) -> Result<ModuleRoot> {
+        let mut root = ModuleRoot::default();
+        for line in &self.options.prelude {
+            root.push_prelude(line);
+        }
+
+        for merge in &self.options.merge {
+            if !merge.exists() {
+                continue;
+            }
+            let content = fs::read_to_string(merge)
+                .wrap_err_with(|| format!("Failed to read {}", merge.display()))?;
+            root.merge(ModuleRoot::parse(&content));
+        }
+
+        Ok(root)
+    }
+
+    fn header(&self) -> String {
+        self.options
+            .header
+            .as_deref()
+            .map(|header| format!("{}\n", header.trim_end()))
+            .unwrap_or_default()
+    }
+
+    fn rust_files(dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            let entries = fs::read_dir(&dir)
+                .wrap_err_with(|| format!("Failed to read {}", dir.display()))?;
+
+            for entry in entries {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Moves every generated namespace into the output, replacing stale copies from earlier runs
+    fn move_entries(generated: &Path, output: &Path) -> Result<()> {
+        for entry in fs::read_dir(generated)? {
+            let source = entry?.path();
+            let Some(name) = source.file_name() else {
+                continue;
+            };
+            let target = output.join(name);
+
+            if target.is_dir() {
+                fs::remove_dir_all(&target)?;
+            } else if target.exists() {
+                fs::remove_file(&target)?;
+            }
+
+            fs::rename(&source, &target).wrap_err_with(|| {
+                format!("Failed to move {} to {}", source.display(), target.display())
+            })?;
+        }
+
+        fs::remove_dir_all(generated)?;
+        Ok(())
+    }
+}
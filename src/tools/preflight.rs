@@ -0,0 +1,412 @@
+use eyre::{eyre, Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const METADATA_MAGIC: u32 = 0xFAB1_1BAF;
+const METADATA_MIN_VERSION: i32 = 24;
+const METADATA_MAX_VERSION: i32 = 35;
+
+const ELF_MAGIC: [u8; 4] = [0x7F, b'E', b'L', b'F'];
+const ELF_CLASS_32: u8 = 1;
+const ELF_CLASS_64: u8 = 2;
+const ELF_DATA_LSB: u8 = 1;
+const ELF_TYPE_DYN: u16 = 3;
+const ELF_MACHINE_ARM: u16 = 0x28;
+const ELF_MACHINE_AARCH64: u16 = 0xB7;
+
+const SECTIONS_V24: &[&str] = &[
+    "stringLiteral",
+    "stringLiteralData",
+    "string",
+    "events",
+    "properties",
+    "methods",
+    "parameterDefaultValues",
+    "fieldDefaultValues",
+    "fieldAndParameterDefaultValueData",
+    "fieldMarshaledSizes",
+    "parameters",
+    "fields",
+    "genericParameters",
+    "genericParameterConstraints",
+    "genericContainers",
+    "nestedTypes",
+    "interfaces",
+    "vtableMethods",
+    "interfaceOffsets",
+    "typeDefinitions",
+];
+
+const SECTIONS_V29: &[&str] = &[
+    "images",
+    "assemblies",
+    "fieldRefs",
+    "referencedAssemblies",
+    "attributeData",
+    "attributeDataRange",
+    "unresolvedVirtualCallParameterTypes",
+    "unresolvedVirtualCallParameterRanges",
+    "windowsRuntimeTypeNames",
+    "windowsRuntimeStrings",
+    "exportedTypeDefinitions",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfArch {
+    Arm64,
+    Armv7,
+}
+
+impl fmt::Display for ElfArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElfArch::Arm64 => write!(f, "arm64-v8a"),
+            ElfArch::Armv7 => write!(f, "armeabi-v7a"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MetadataSection {
+    pub name: &'static str,
+    pub offset: u32,
+    pub size: u32,
+    pub count: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct MetadataInfo {
+    pub path: PathBuf,
+    pub file_size: u64,
+    pub version: i32,
+    pub sections: Vec<MetadataSection>,
+}
+
+#[derive(Debug)]
+pub struct ElfInfo {
+    pub path: PathBuf,
+    pub file_size: u64,
+    pub arch: ElfArch,
+    pub section_headers: u16,
+}
+
+#[derive(Debug)]
+pub struct PreflightReport {
+    pub metadata: MetadataInfo,
+    pub libil2cpp: ElfInfo,
+}
+
+pub struct Preflight {
+    libil2cpp: PathBuf,
+    metadata: PathBuf,
+}
+
+impl Preflight {
+    pub fn new(libil2cpp: PathBuf, metadata: PathBuf) -> Self {
+        Self { libil2cpp, metadata }
+    }
+
+    pub fn run(&self) -> Result<PreflightReport> {
+        let metadata = Self::inspect_metadata(&self.metadata)?;
+        let libil2cpp = Self::inspect_elf(&self.libil2cpp)?;
+
+        Ok(PreflightReport { metadata, libil2cpp })
+    }
+
+    fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+        data.get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn record_size(name: &str, version: i32) -> Option<u32> {
+        match (name, version) {
+            ("stringLiteral", _) => Some(8),
+            ("fields", _) => Some(12),
+            ("images", 24..) => Some(40),
+            ("typeDefinitions", 27..=31) => Some(88),
+            _ => None,
+        }
+    }
+
+    pub fn inspect_metadata(path: &Path) -> Result<MetadataInfo> {
+        let data = fs::read(path)
+            .wrap_err_with(|| format!("Failed to read metadata at {}", path.display()))?;
+
+        let magic = Self::read_u32(&data, 0)
+            .ok_or_else(|| eyre!("{} is too small to be il2cpp metadata", path.display()))?;
+        if magic != METADATA_MAGIC {
+            return Err(eyre!(
+                "{} has magic 0x{:08X}, expected 0x{:08X}; the file is likely encrypted or corrupt",
+                path.display(),
+                magic,
+                METADATA_MAGIC
+            ));
+        }
+
+        let version = Self::read_u32(&data, 4)
+            .ok_or_else(|| eyre!("{} has a truncated header", path.display()))?
+            as i32;
+        if !(METADATA_MIN_VERSION..=METADATA_MAX_VERSION).contains(&version) {
+            return Err(eyre!(
+                "{} reports metadata version {}, supported versions are {} to {}",
+                path.display(),
+                version,
+                METADATA_MIN_VERSION,
+                METADATA_MAX_VERSION
+            ));
+        }
+
+        let names = SECTIONS_V24
+            .iter()
+            .chain(if version >= 29 { SECTIONS_V29 } else { &[] });
+
+        let mut sections = Vec::new();
+        for (index, name) in names.enumerate() {
+            let header = 8 + index * 8;
+            let (Some(offset), Some(size)) =
+                (Self::read_u32(&data, header), Self::read_u32(&data, header + 4))
+            else {
+                return Err(eyre!("{} has a truncated header", path.display()));
+            };
+
+            if offset as u64 + size as u64 > data.len() as u64 {
+                return Err(eyre!(
+                    "Section {} (offset {}, size {}) extends past the end of {} ({} bytes)",
+                    name,
+                    offset,
+                    size,
+                    path.display(),
+                    data.len()
+                ));
+            }
+
+            let count = Self::record_size(name, version).map(|record| size / record);
+            sections.push(MetadataSection { name, offset, size, count });
+        }
+
+        Ok(MetadataInfo {
+            path: path.to_path_buf(),
+            file_size: data.len() as u64,
+            version,
+            sections,
+        })
+    }
+
+    pub fn inspect_elf(path: &Path) -> Result<ElfInfo> {
+        let data = fs::read(path)
+            .wrap_err_with(|| format!("Failed to read library at {}", path.display()))?;
+
+        if data.get(0..4) != Some(&ELF_MAGIC[..]) {
+            return Err(eyre!("{} is not an ELF file", path.display()));
+        }
+
+        let truncated = || eyre!("{} has a truncated ELF header", path.display());
+        let class = data.get(4).copied().ok_or_else(truncated)?;
+        let encoding = data.get(5).copied().ok_or_else(truncated)?;
+        if encoding != ELF_DATA_LSB {
+            return Err(eyre!("{} is not a little-endian ELF", path.display()));
+        }
+
+        let elf_type = Self::read_u16(&data, 16).ok_or_else(truncated)?;
+        if elf_type != ELF_TYPE_DYN {
+            return Err(eyre!(
+                "{} has ELF type {}, expected a shared object",
+                path.display(),
+                elf_type
+            ));
+        }
+
+        let machine = Self::read_u16(&data, 18).ok_or_else(truncated)?;
+        let (arch, shnum_offset) = match (class, machine) {
+            (ELF_CLASS_64, ELF_MACHINE_AARCH64) => (ElfArch::Arm64, 60),
+            (ELF_CLASS_32, ELF_MACHINE_ARM) => (ElfArch::Armv7, 48),
+            _ => {
+                return Err(eyre!(
+                    "{} targets machine 0x{:X} (class {}), expected arm64 or armv7",
+                    path.display(),
+                    machine,
+                    class
+                ));
+            }
+        };
+
+        let section_headers = Self::read_u16(&data, shnum_offset).ok_or_else(truncated)?;
+
+        Ok(ElfInfo {
+            path: path.to_path_buf(),
+            file_size: data.len() as u64,
+            arch,
+            section_headers,
+        })
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata = &self.metadata;
+        writeln!(f, "Metadata: {}", metadata.path.display())?;
+        writeln!(f, "  Size: {} bytes", metadata.file_size)?;
+        writeln!(f, "  Version: {}", metadata.version)?;
+        writeln!(f, "  Sections:")?;
+        for section in &metadata.sections {
+            match section.count {
+                Some(count) => writeln!(
+                    f,
+                    "    {:<38} @0x{:08X} {:>10} bytes {:>8} entries",
+                    section.name, section.offset, section.size, count
+                )?,
+                None => writeln!(
+                    f,
+                    "    {:<38} @0x{:08X} {:>10} bytes",
+                    section.name, section.offset, section.size
+                )?,
+            }
+        }
+
+        let libil2cpp = &self.libil2cpp;
+        writeln!(f, "Library: {}", libil2cpp.path.display())?;
+        writeln!(f, "  Size: {} bytes", libil2cpp.file_size)?;
+        writeln!(f, "  Architecture: {}", libil2cpp.arch)?;
+        write!(f, "  Section headers: {}", libil2cpp.section_headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A little-endian shared object header of `class` and `machine`, `len` bytes long
+    fn elf(class: u8, machine: u16, len: usize) -> Vec<u8> {
+        let mut data = vec![0; 64];
+        data[..4].copy_from_slice(&ELF_MAGIC);
+        data[4] = class;
+        data[5] = ELF_DATA_LSB;
+        data[16..18].copy_from_slice(&ELF_TYPE_DYN.to_le_bytes());
+        data[18..20].copy_from_slice(&machine.to_le_bytes());
+        data[60..62].copy_from_slice(&27u16.to_le_bytes());
+        data.truncate(len);
+        data
+    }
+
+    /// Metadata of `version` with an empty section table and two string literals
+    fn metadata(version: i32) -> Vec<u8> {
+        let sections = SECTIONS_V24.len() + if version >= 29 { SECTIONS_V29.len() } else { 0 };
+        let header = 8 + sections * 8;
+        let mut data = vec![0; header + 16];
+        data[..4].copy_from_slice(&METADATA_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&version.to_le_bytes());
+        data[8..12].copy_from_slice(&(header as u32).to_le_bytes());
+        data[12..16].copy_from_slice(&16u32.to_le_bytes());
+        data
+    }
+
+    fn with_file<T>(name: &str, data: &[u8], inspect: fn(&Path) -> Result<T>) -> Result<T> {
+        let path = std::env::temp_dir().join(format!(
+            "bafb-preflight-{}-{}",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, data).unwrap();
+        let info = inspect(&path);
+        fs::remove_file(&path).unwrap();
+        info
+    }
+
+    fn inspect(name: &str, data: &[u8]) -> Result<ElfInfo> {
+        with_file(name, data, Preflight::inspect_elf)
+    }
+
+    fn inspect_metadata(name: &str, data: &[u8]) -> Result<MetadataInfo> {
+        with_file(name, data, Preflight::inspect_metadata)
+    }
+
+    #[test]
+    fn inspects_metadata_sections() {
+        let info = inspect_metadata("metadata-v24", &metadata(24)).unwrap();
+        assert_eq!(info.version, 24);
+        assert_eq!(info.sections.len(), SECTIONS_V24.len());
+        assert_eq!(info.sections[0].name, "stringLiteral");
+        assert_eq!(info.sections[0].count, Some(2));
+
+        let info = inspect_metadata("metadata-v29", &metadata(29)).unwrap();
+        assert_eq!(info.sections.len(), SECTIONS_V24.len() + SECTIONS_V29.len());
+    }
+
+    #[test]
+    fn rejects_encrypted_metadata() {
+        let mut data = metadata(24);
+        data[..4].copy_from_slice(&0x1234_5678u32.to_le_bytes());
+        let error = inspect_metadata("metadata-magic", &data)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("has magic 0x12345678"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_truncated_metadata() {
+        let error = inspect_metadata("metadata-empty", &[0xAF, 0x1B])
+            .unwrap_err()
+            .to_string();
+        assert!(error.ends_with("is too small to be il2cpp metadata"), "{}", error);
+
+        let mut data = metadata(24);
+        data[8..16].fill(0);
+        for len in [6, 8, 100] {
+            let error = inspect_metadata("metadata-truncated", &data[..len])
+                .unwrap_err()
+                .to_string();
+            assert!(error.ends_with("has a truncated header"), "{}: {}", len, error);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_metadata_versions() {
+        for version in [23, 36, -1] {
+            let error = inspect_metadata("metadata-version", &metadata(version))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("supported versions are 24 to 35"), "{}", error);
+        }
+
+        let mut data = metadata(24);
+        data.truncate(data.len() - 1);
+        let error = inspect_metadata("metadata-section", &data)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("extends past the end of"), "{}", error);
+    }
+
+    #[test]
+    fn inspects_arm64_libraries() {
+        let info = inspect("arm64", &elf(ELF_CLASS_64, ELF_MACHINE_AARCH64, 64)).unwrap();
+        assert_eq!(info.arch, ElfArch::Arm64);
+        assert_eq!(info.section_headers, 27);
+    }
+
+    #[test]
+    fn rejects_truncated_headers() {
+        for len in [4, 5, 17, 19, 61] {
+            let error = inspect("truncated", &elf(ELF_CLASS_64, ELF_MACHINE_AARCH64, len))
+                .unwrap_err()
+                .to_string();
+            assert!(error.ends_with("has a truncated ELF header"), "{}: {}", len, error);
+        }
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        let error = inspect("text", b"not a library").unwrap_err().to_string();
+        assert!(error.ends_with("is not an ELF file"), "{}", error);
+
+        let error = inspect("class", &elf(ELF_CLASS_32, ELF_MACHINE_AARCH64, 64))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("expected arm64 or armv7"), "{}", error);
+    }
+}
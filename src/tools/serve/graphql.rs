@@ -0,0 +1,555 @@
+use crate::schema::export::{Names, key, rows, sorted, target};
+use crate::schema::{
+    Declaration, Field as SchemaField, Kind, Scalar as SchemaScalar, Schema, Type,
+};
+use crate::tools::serve::Index;
+
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, Scalar,
+    SchemaBuilder, TypeRef, Union,
+};
+use async_graphql::{Name, SDLExportOptions, Value as GraphqlValue};
+use eyre::{Result, eyre};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const QUERY: &str = "Query";
+/// Integers that do not fit the 32 bits of `Int`
+const LONG: &str = "Long";
+const DEPTH: usize = 32;
+
+type Resolved<'a> = async_graphql::Result<Option<FieldValue<'a>>>;
+
+/// The SDL of the GraphQL schema `bafb serve` answers queries with
+pub fn sdl(schema: &Schema) -> Result<String> {
+    let options = SDLExportOptions::new()
+        .prefer_single_line_descriptions()
+        .use_space_ident();
+    Ok(build(schema)?.finish()?.sdl_with_options(options))
+}
+
+/// GraphQL types for the decoded JSON of every struct and table with a lookup by key and a paged
+/// list of every Excel row type on `Query`, the rows are read from an `Arc<Index>` in the data
+pub fn build(schema: &Schema) -> Result<SchemaBuilder> {
+    Builder::new(schema)?.build()
+}
+
+/// How the value of a field is turned into a GraphQL value
+#[derive(Debug, Clone)]
+enum Output {
+    Scalar(&'static str),
+    /// GraphQL name and the names of the values by number
+    Enum(String, HashMap<i64, String>),
+    Object(String),
+    /// GraphQL name and the object of every variant by name
+    Union(String, HashMap<String, String>),
+    /// Rows of an Excel table looked up by the value of an `<Row>Id` field
+    Reference(String, String),
+}
+
+impl Output {
+    fn type_name(&self) -> &str {
+        match self {
+            Output::Scalar(name) => name,
+            Output::Enum(name, _)
+            | Output::Object(name)
+            | Output::Union(name, _)
+            | Output::Reference(_, name) => name,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shape {
+    /// Key of the value in the decoded JSON
+    key: String,
+    list: bool,
+    output: Output,
+}
+
+impl Shape {
+    fn resolve<'a>(&self, index: &'a Index, parent: &'a Value) -> Resolved<'a> {
+        let value = match parent.get(&self.key) {
+            None | Some(Value::Null) => return Ok(None),
+            Some(value) => value,
+        };
+        // The variant names of unions are written next to them, a list of them for lists
+        let types = parent.get(format!("{}_type", self.key));
+
+        if !self.list {
+            return self.element(index, value, types);
+        }
+        let Value::Array(elements) = value else {
+            return Err(format!("{} is not a list", self.key).into());
+        };
+        let mut values = Vec::new();
+        for (position, element) in elements.iter().enumerate() {
+            let ty = types.and_then(|types| types.get(position));
+            match self.element(index, element, ty)? {
+                Some(value) => values.push(value),
+                None if matches!(self.output, Output::Reference(_, _)) => {}
+                None => values.push(FieldValue::NULL),
+            }
+        }
+        Ok(Some(FieldValue::list(values)))
+    }
+
+    fn element<'a>(&self, index: &'a Index, value: &'a Value, ty: Option<&Value>) -> Resolved<'a> {
+        match &self.output {
+            Output::Scalar(_) => Ok(Some(FieldValue::value(GraphqlValue::from_json(
+                value.clone(),
+            )?))),
+            Output::Enum(name, values) => {
+                let found = match value {
+                    Value::String(name) => Some(name.clone()),
+                    Value::Number(number) => number
+                        .as_i64()
+                        .and_then(|number| values.get(&number))
+                        .cloned(),
+                    _ => None,
+                };
+                let found = found.ok_or_else(|| format!("{} has no value {}", name, value))?;
+                Ok(Some(FieldValue::value(GraphqlValue::Enum(Name::new(
+                    found,
+                )))))
+            }
+            Output::Object(_) => Ok(Some(FieldValue::borrowed_any(value))),
+            Output::Union(name, variants) => match ty.and_then(Value::as_str) {
+                None | Some("NONE") => Ok(None),
+                Some(variant) => {
+                    let object = variants
+                        .get(variant)
+                        .ok_or_else(|| format!("{} has no variant {}", name, variant))?;
+                    Ok(Some(
+                        FieldValue::borrowed_any(value).with_type(object.clone()),
+                    ))
+                }
+            },
+            Output::Reference(row, _) => Ok(index
+                .table(row)
+                .and_then(|table| table.get(&value.to_string()))
+                .map(object)),
+        }
+    }
+}
+
+struct Builder<'a> {
+    schema: &'a Schema,
+    names: Names,
+    /// Qualified names of the structs and tables with at least one field GraphQL can describe
+    exposed: HashSet<String>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(schema: &'a Schema) -> Result<Self> {
+        let names = Names::new(schema)?;
+        for declaration in &schema.declarations {
+            let name = names.get(declaration);
+            if [QUERY, LONG, "Int", "Float", "String", "Boolean", "ID"].contains(&name)
+                || name.starts_with("__")
+            {
+                return Err(eyre!(
+                    "{} cannot be named {} in GraphQL",
+                    declaration.qualified_name(),
+                    name
+                ));
+            }
+        }
+
+        let mut builder = Self {
+            schema,
+            names,
+            exposed: HashSet::new(),
+        };
+        // Objects need a field, which may be another object that needs one
+        loop {
+            let found: Vec<String> = sorted(schema)
+                .into_iter()
+                .filter(|declaration| {
+                    matches!(declaration.kind, Kind::Table(_) | Kind::Struct(_))
+                        && !builder.exposed.contains(&declaration.qualified_name())
+                        && builder.fields(declaration).next().is_some()
+                })
+                .map(|declaration| declaration.qualified_name())
+                .collect();
+            if found.is_empty() {
+                break;
+            }
+            builder.exposed.extend(found);
+        }
+
+        Ok(builder)
+    }
+
+    fn build(&self) -> Result<SchemaBuilder> {
+        let long = Scalar::new(LONG)
+            .description("An integer of up to 64 bits, signed or not")
+            .validator(|value| {
+                matches!(value, GraphqlValue::Number(number) if number.is_i64() || number.is_u64())
+            });
+        let mut builder = async_graphql::dynamic::Schema::build(QUERY, None, None)
+            .register(long)
+            .limit_depth(DEPTH);
+
+        for declaration in sorted(self.schema) {
+            let name = self.names.get(declaration).to_string();
+            match &declaration.kind {
+                Kind::Enum(_, values) if !declaration.has_attribute("bit_flags") => {
+                    let mut graphql = describe(Enum::new(name), &declaration.doc, |value, doc| {
+                        value.description(doc)
+                    });
+                    for value in values {
+                        if ["true", "false", "null"].contains(&value.name.as_str()) {
+                            return Err(eyre!(
+                                "{}.{} cannot be a GraphQL enum value",
+                                declaration.qualified_name(),
+                                value.name
+                            ));
+                        }
+                        graphql = graphql.item(describe(
+                            EnumItem::new(&value.name),
+                            &value.doc,
+                            |value, doc| value.description(doc),
+                        ));
+                    }
+                    builder = builder.register(graphql);
+                }
+                Kind::Union(variants) => {
+                    let objects = self.variants(declaration, variants);
+                    if objects.is_empty() {
+                        continue;
+                    }
+                    let mut members: Vec<&String> = objects.values().collect();
+                    members.sort();
+                    members.dedup();
+                    let mut union = describe(Union::new(name), &declaration.doc, |value, doc| {
+                        value.description(doc)
+                    });
+                    for member in members {
+                        union = union.possible_type(member);
+                    }
+                    builder = builder.register(union);
+                }
+                Kind::Table(_) | Kind::Struct(_)
+                    if self.exposed.contains(&declaration.qualified_name()) =>
+                {
+                    let mut object = describe(Object::new(name), &declaration.doc, |value, doc| {
+                        value.description(doc)
+                    });
+                    for (field, ty, shape, doc) in self.fields(declaration) {
+                        object = object.field(describe(
+                            resolver(&field, ty, shape),
+                            &doc,
+                            |value, doc| value.description(doc),
+                        ));
+                    }
+                    builder = builder.register(object);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(builder.register(self.query()))
+    }
+
+    /// `tables`, `<row>(<key>: ...)` and `<row>List(offset: ..., limit: ...)`
+    fn query(&self) -> Object {
+        let rows: Vec<&Declaration> = rows(self.schema)
+            .into_iter()
+            .filter(|row| self.exposed.contains(&row.qualified_name()))
+            .collect();
+
+        let names: Vec<String> = rows
+            .iter()
+            .map(|row| self.names.get(row).to_string())
+            .collect();
+        let mut query = Object::new(QUERY).field(
+            Field::new(
+                "tables",
+                TypeRef::named_nn_list_nn(TypeRef::STRING),
+                move |_| {
+                    let names = names.iter().map(|name| GraphqlValue::from(name.as_str()));
+                    FieldFuture::from_value(Some(GraphqlValue::List(names.collect())))
+                },
+            )
+            .description("Names of the Excel row types"),
+        );
+
+        for row in rows {
+            let object = self.names.get(row).to_string();
+            let qualified = row.qualified_name();
+            let field = lower_camel(&object);
+
+            if let Some(key) = key(row)
+                && let Some(output) = self.output(row, key)
+            {
+                let argument = key.name.clone();
+                let row = qualified.clone();
+                query = query.field(
+                    Field::new(&field, TypeRef::named(&object), move |ctx| {
+                        let argument = argument.clone();
+                        let row = row.clone();
+                        FieldFuture::new(async move { lookup(ctx, &row, &argument) })
+                    })
+                    .argument(InputValue::new(
+                        &key.name,
+                        TypeRef::named_nn(output.type_name()),
+                    ))
+                    .description(format!("The {} whose {} is given", object, key.name)),
+                );
+            }
+
+            query = query.field(
+                Field::new(
+                    format!("{}List", field),
+                    TypeRef::named_nn_list_nn(&object),
+                    move |ctx| {
+                        let row = qualified.clone();
+                        FieldFuture::new(async move { list(ctx, &row) })
+                    },
+                )
+                .argument(
+                    InputValue::new("offset", TypeRef::named_nn(TypeRef::INT)).default_value(0),
+                )
+                .argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
+                .description(format!("Every {} in file order", object)),
+            );
+        }
+
+        query
+    }
+
+    /// The GraphQL fields of a struct or table with their types, shapes and descriptions
+    fn fields(
+        &self,
+        declaration: &'a Declaration,
+    ) -> impl Iterator<Item = (String, TypeRef, Shape, Vec<String>)> + '_ {
+        let is_struct = matches!(declaration.kind, Kind::Struct(_));
+        let fields = declaration.fields();
+
+        let own = fields
+            .iter()
+            .filter(|field| !field.has_attribute("deprecated"))
+            .filter_map(move |field| {
+                let output = self.output(declaration, field)?;
+                // Scalars are always written unless they are optional, anything else only when set
+                let is_scalar = match field.ty {
+                    Type::Scalar(_) => true,
+                    Type::Named(_) => matches!(output, Output::Scalar(_) | Output::Enum(_, _)),
+                    _ => false,
+                };
+                let optional = field.default.as_deref() == Some("null");
+                let non_null =
+                    is_struct || (is_scalar && !optional) || field.has_attribute("required");
+
+                let list = matches!(field.ty, Type::Vector(_) | Type::Array(_, _));
+                let element = TypeRef::named(output.type_name());
+                let element = match output {
+                    // Lists of unions may hold NONE
+                    Output::Union(_, _) => element,
+                    _ => TypeRef::NonNull(Box::new(element)),
+                };
+                let ty = if list {
+                    TypeRef::List(Box::new(element))
+                } else {
+                    element
+                };
+                let ty = match (ty, non_null) {
+                    (TypeRef::NonNull(ty), false) => *ty,
+                    (TypeRef::NonNull(ty), true) => TypeRef::NonNull(ty),
+                    (ty, true) => TypeRef::NonNull(Box::new(ty)),
+                    (ty, false) => ty,
+                };
+
+                let shape = Shape {
+                    key: field.name.clone(),
+                    list,
+                    output,
+                };
+                Some((field.name.clone(), ty, shape, field.doc.clone()))
+            });
+
+        // References never shadow a field or another reference
+        let mut taken: HashSet<String> = fields.iter().map(|field| field.name.clone()).collect();
+        let references = fields
+            .iter()
+            .filter(|field| !field.has_attribute("deprecated"))
+            .filter_map(move |field| {
+                let (stem, row) = self.reference(declaration, field)?;
+                if !taken.insert(stem.clone()) {
+                    return None;
+                }
+                let object = self.names.get(row).to_string();
+                let list = matches!(field.ty, Type::Vector(_));
+                let ty = if list {
+                    TypeRef::named_nn_list(&object)
+                } else {
+                    TypeRef::named(&object)
+                };
+                let doc = vec![format!("The {} whose key is {}", object, field.name)];
+                let shape = Shape {
+                    key: field.name.clone(),
+                    list,
+                    output: Output::Reference(row.qualified_name(), object),
+                };
+                Some((stem, ty, shape, doc))
+            });
+
+        own.chain(references)
+    }
+
+    fn output(&self, owner: &Declaration, field: &SchemaField) -> Option<Output> {
+        match field.ty.element() {
+            Type::Scalar(scalar) => Some(Output::Scalar(scalar_name(*scalar))),
+            Type::String => Some(Output::Scalar(TypeRef::STRING)),
+            Type::Named(_) => {
+                let target = target(self.schema, owner, &field.ty)?;
+                let name = self.names.get(target).to_string();
+                match &target.kind {
+                    // bit_flags values are combined, so they stay numbers
+                    Kind::Enum(underlying, _) if target.has_attribute("bit_flags") => {
+                        Some(Output::Scalar(scalar_name(*underlying)))
+                    }
+                    Kind::Enum(_, values) => Some(Output::Enum(
+                        name,
+                        values
+                            .iter()
+                            .map(|value| (value.value, value.name.clone()))
+                            .collect(),
+                    )),
+                    Kind::Union(variants) => {
+                        let objects = self.variants(target, variants);
+                        (!objects.is_empty()).then_some(Output::Union(name, objects))
+                    }
+                    Kind::Table(_) | Kind::Struct(_) => self
+                        .exposed
+                        .contains(&target.qualified_name())
+                        .then_some(Output::Object(name)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The objects of the variants of a union by variant name, variants that are not objects are
+    /// left out
+    fn variants(
+        &self,
+        union: &Declaration,
+        variants: &[crate::schema::UnionVariant],
+    ) -> HashMap<String, String> {
+        variants
+            .iter()
+            .filter_map(|variant| {
+                let table = self.schema.resolve(&variant.ty, &union.namespace)?;
+                self.exposed
+                    .contains(&table.qualified_name())
+                    .then(|| (variant.name.clone(), self.names.get(table).to_string()))
+            })
+            .collect()
+    }
+
+    /// An integer field named `<Stem>Id`, or a list of them named `<Stem>Ids`, next to a
+    /// `<Stem>Excel` row type with an integer key gives a `<Stem>` or `<Stem>s` field
+    fn reference(
+        &self,
+        owner: &Declaration,
+        field: &SchemaField,
+    ) -> Option<(String, &'a Declaration)> {
+        let (stem, name) = match &field.ty {
+            Type::Scalar(scalar) if scalar.is_integer() => {
+                let stem = field.name.strip_suffix("Id")?;
+                (stem, stem.to_string())
+            }
+            Type::Vector(element) if matches!(element.as_ref(), Type::Scalar(scalar) if scalar.is_integer()) =>
+            {
+                let stem = field.name.strip_suffix("Ids")?;
+                (stem, format!("{}s", stem))
+            }
+            _ => return None,
+        };
+        if stem.is_empty() {
+            return None;
+        }
+
+        let row = self
+            .schema
+            .resolve(&format!("{}Excel", stem), &owner.namespace)?;
+        let key = key(row)?;
+        (matches!(row.kind, Kind::Table(_))
+            && self.exposed.contains(&row.qualified_name())
+            && matches!(key.ty, Type::Scalar(scalar) if scalar.is_integer()))
+        .then_some((name, row))
+    }
+}
+
+fn resolver(name: &str, ty: TypeRef, shape: Shape) -> Field {
+    let shape = Arc::new(shape);
+    Field::new(name, ty, move |ctx| {
+        let shape = shape.clone();
+        FieldFuture::new(async move {
+            let index = ctx.ctx.data::<Arc<Index>>()?;
+            let parent = ctx.parent_value;
+            shape.resolve(index, parent.try_downcast_ref::<Value>()?)
+        })
+    })
+}
+
+fn lookup<'a>(ctx: ResolverContext<'a>, row: &str, argument: &str) -> Resolved<'a> {
+    let index = ctx.ctx.data::<Arc<Index>>()?;
+    let key = ctx
+        .args
+        .try_get(argument)?
+        .as_value()
+        .clone()
+        .into_json()?
+        .to_string();
+    let table = index
+        .table(row)
+        .ok_or_else(|| format!("Unknown table {}", row))?;
+    Ok(table.get(&key).map(object))
+}
+
+fn list<'a>(ctx: ResolverContext<'a>, row: &str) -> Resolved<'a> {
+    let index = ctx.ctx.data::<Arc<Index>>()?;
+    let offset = ctx.args.try_get("offset")?.i64()?.max(0) as usize;
+    let limit = match ctx.args.get("limit") {
+        Some(limit) if !limit.is_null() => limit.i64()?.max(0) as usize,
+        _ => usize::MAX,
+    };
+    let table = index
+        .table(row)
+        .ok_or_else(|| format!("Unknown table {}", row))?;
+    let rows = table.rows.iter().skip(offset).take(limit).map(object);
+    Ok(Some(FieldValue::list(rows)))
+}
+
+fn object(value: &Value) -> FieldValue<'_> {
+    FieldValue::borrowed_any(value)
+}
+
+fn scalar_name(scalar: SchemaScalar) -> &'static str {
+    match scalar {
+        SchemaScalar::Bool => TypeRef::BOOLEAN,
+        SchemaScalar::Float | SchemaScalar::Double => TypeRef::FLOAT,
+        SchemaScalar::UInt | SchemaScalar::Long | SchemaScalar::ULong => LONG,
+        _ => TypeRef::INT,
+    }
+}
+
+fn describe<T>(value: T, doc: &[String], description: impl Fn(T, String) -> T) -> T {
+    if doc.is_empty() {
+        return value;
+    }
+    let lines: Vec<&str> = doc.iter().map(|line| line.trim()).collect();
+    description(value, lines.join("\n"))
+}
+
+fn lower_camel(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,261 @@
+pub mod graphql;
+
+use crate::schema::Schema;
+use crate::schema::export::{Names, key, rows};
+use crate::tools::decoded::DecodedData;
+
+use async_graphql::Variables;
+use async_graphql::dynamic::Schema as GraphqlSchema;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use eyre::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Rows of an Excel table with their keys
+#[derive(Debug)]
+pub struct IndexedTable {
+    /// Qualified name of the row type
+    pub row: String,
+    pub name: String,
+    pub key: Option<String>,
+    pub rows: Vec<Value>,
+    keys: HashMap<String, usize>,
+}
+
+impl IndexedTable {
+    /// The first row whose key is `key`, in JSON
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.keys.get(key).map(|index| &self.rows[*index])
+    }
+
+    /// The first row whose key is `key` as written in a URL, a number or a name
+    pub fn get_text(&self, key: &str) -> Option<&Value> {
+        self.get(key)
+            .or_else(|| self.get(&Value::String(key.to_string()).to_string()))
+    }
+}
+
+/// Every Excel row type of a schema with the decoded rows of it, tables without a file are empty
+#[derive(Debug)]
+pub struct Index {
+    pub tables: Vec<IndexedTable>,
+}
+
+impl Index {
+    pub fn new(schema: &Schema, data: DecodedData) -> Result<Self> {
+        let names = Names::new(schema)?;
+        let mut decoded: HashMap<String, Vec<Value>> = data
+            .tables
+            .into_iter()
+            .map(|table| {
+                let rows = table.rows.into_iter().map(Value::Object).collect();
+                (table.row, rows)
+            })
+            .collect();
+
+        let tables = rows(schema)
+            .into_iter()
+            .map(|row| {
+                let rows = decoded.remove(&row.qualified_name()).unwrap_or_default();
+                let key = key(row).map(|field| field.name.clone());
+
+                let mut keys = HashMap::new();
+                if let Some(key) = &key {
+                    for (index, value) in rows.iter().enumerate() {
+                        if let Some(value) = value.get(key) {
+                            keys.entry(value.to_string()).or_insert(index);
+                        }
+                    }
+                }
+
+                IndexedTable {
+                    row: row.qualified_name(),
+                    name: names.get(row).to_string(),
+                    key,
+                    rows,
+                    keys,
+                }
+            })
+            .collect();
+
+        Ok(Self { tables })
+    }
+
+    pub fn table(&self, row: &str) -> Option<&IndexedTable> {
+        self.tables.iter().find(|table| table.row == row)
+    }
+
+    /// The table named `name`, case insensitively
+    pub fn find(&self, name: &str) -> Option<&IndexedTable> {
+        self.tables
+            .iter()
+            .find(|table| table.name.eq_ignore_ascii_case(name))
+    }
+}
+
+struct ServerState {
+    index: Arc<Index>,
+    graphql: GraphqlSchema,
+    sdl: String,
+}
+
+/// A read-only HTTP server over decoded Excel tables, GraphQL at `/graphql` and REST below
+/// `/tables`
+pub struct Server {
+    router: Router,
+    tables: usize,
+}
+
+impl Server {
+    pub fn new(schema: &Schema, data: DecodedData) -> Result<Self> {
+        let index = Arc::new(Index::new(schema, data)?);
+        let graphql = graphql::build(schema)?
+            .data(index.clone())
+            .finish()
+            .wrap_err("Failed to build the GraphQL schema")?;
+        let sdl = graphql::sdl(schema)?;
+        let tables = index.tables.len();
+
+        let state = Arc::new(ServerState {
+            index,
+            graphql,
+            sdl,
+        });
+        let router = Router::new()
+            .route("/", get(Self::overview))
+            .route("/tables/{name}", get(Self::rows))
+            .route("/tables/{name}/{key}", get(Self::row))
+            .route("/graphql", get(Self::graphql_get).post(Self::graphql_post))
+            .route("/schema.graphql", get(Self::sdl))
+            .with_state(state);
+
+        Ok(Self { router, tables })
+    }
+
+    /// Serves until interrupted, `on_bound` is called with the address once it is listening
+    pub async fn run(self, address: SocketAddr, on_bound: impl FnOnce(SocketAddr)) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .wrap_err_with(|| format!("Failed to listen on {}", address))?;
+        on_bound(listener.local_addr()?);
+
+        axum::serve(listener, self.router)
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+            .wrap_err("Server failed")
+    }
+
+    pub fn tables(&self) -> usize {
+        self.tables
+    }
+
+    async fn overview(State(state): State<Arc<ServerState>>) -> Json<Value> {
+        let tables: Vec<Value> = state
+            .index
+            .tables
+            .iter()
+            .map(|table| {
+                json!({
+                    "name": table.name,
+                    "key": table.key,
+                    "rows": table.rows.len(),
+                })
+            })
+            .collect();
+        Json(json!({ "tables": tables }))
+    }
+
+    async fn rows(
+        State(state): State<Arc<ServerState>>,
+        Path(name): Path<String>,
+        Query(page): Query<Page>,
+    ) -> Response {
+        let Some(table) = state.index.find(&name) else {
+            return not_found(format!("Unknown table {}", name));
+        };
+        let rows: Vec<&Value> = table
+            .rows
+            .iter()
+            .skip(page.offset.unwrap_or_default())
+            .take(page.limit.unwrap_or(usize::MAX))
+            .collect();
+        Json(rows).into_response()
+    }
+
+    async fn row(
+        State(state): State<Arc<ServerState>>,
+        Path((name, key)): Path<(String, String)>,
+    ) -> Response {
+        let Some(table) = state.index.find(&name) else {
+            return not_found(format!("Unknown table {}", name));
+        };
+        if table.key.is_none() {
+            return not_found(format!("{} has no key", table.name));
+        }
+        match table.get_text(&key) {
+            Some(row) => Json(row).into_response(),
+            None => not_found(format!("{} has no row {}", table.name, key)),
+        }
+    }
+
+    async fn graphql_get(
+        State(state): State<Arc<ServerState>>,
+        Query(query): Query<GraphqlQuery>,
+    ) -> Response {
+        let mut request = async_graphql::Request::new(query.query);
+        if let Some(operation) = query.operation_name {
+            request = request.operation_name(operation);
+        }
+        if let Some(variables) = query.variables {
+            match serde_json::from_str(&variables) {
+                Ok(variables) => request = request.variables(Variables::from_json(variables)),
+                Err(error) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "error": format!("Invalid variables: {}", error) })),
+                    )
+                        .into_response();
+                }
+            }
+        }
+        Json(state.graphql.execute(request).await).into_response()
+    }
+
+    async fn graphql_post(
+        State(state): State<Arc<ServerState>>,
+        Json(request): Json<async_graphql::Request>,
+    ) -> Response {
+        Json(state.graphql.execute(request).await).into_response()
+    }
+
+    async fn sdl(State(state): State<Arc<ServerState>>) -> Response {
+        ([(header::CONTENT_TYPE, "text/plain")], state.sdl.clone()).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct Page {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlQuery {
+    query: String,
+    operation_name: Option<String>,
+    variables: Option<String>,
+}
+
+fn not_found(message: String) -> Response {
+    (StatusCode::NOT_FOUND, Json(json!({ "error": message }))).into_response()
+}
@@ -0,0 +1,26 @@
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Default)]
+pub struct CommandArgs(Vec<OsString>);
+
+impl CommandArgs {
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.0.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn command(&self, binary: &Path) -> Command {
+        let mut cmd = Command::new(binary);
+        cmd.args(&self.0);
+        cmd
+    }
+
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+}
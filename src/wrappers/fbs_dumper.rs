@@ -1,13 +1,14 @@
+use crate::wrappers::command::CommandArgs;
+
 use eyre::{eyre, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::process::Command;
 
 pub struct FbsDumper {
     binary: PathBuf,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct FbsDumperOptions {
     pub url: Option<String>,
@@ -31,8 +32,8 @@ impl FbsDumper {
         Ok(Self { binary })
     }
 
-    pub fn run(&self, options: FbsDumperOptions) -> Result<()> {
-        let mut cmd = Command::new(&self.binary);
+    pub fn args(options: &FbsDumperOptions) -> CommandArgs {
+        let mut cmd = CommandArgs::default();
 
         cmd.arg("--dummy-dll").arg(&options.dummy_dll);
 
@@ -69,9 +70,16 @@ impl FbsDumper {
             cmd.arg("--suppress-warnings");
         }
 
-        let status = cmd.status().wrap_err_with(|| {
-            format!("Failed to execute FbsDumper at {}", self.binary.display())
-        })?;
+        cmd
+    }
+
+    pub fn run(&self, options: FbsDumperOptions) -> Result<()> {
+        let status = Self::args(&options)
+            .command(&self.binary)
+            .status()
+            .wrap_err_with(|| {
+                format!("Failed to execute FbsDumper at {}", self.binary.display())
+            })?;
 
         if !status.success() {
             return Err(eyre!("FbsDumper failed with exit code {:?}", status.code()))
@@ -1,8 +1,10 @@
+use crate::wrappers::command::CommandArgs;
+
 use clap::ValueEnum;
 use eyre::{eyre, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
-use std::process::Command;
 
 pub struct FlatC {
     binary: PathBuf,
@@ -14,10 +16,13 @@ pub enum Language {
     Java,
     Kotlin,
     KotlinKmp,
+    #[value(name = "csharp")]
     CSharp,
     Go,
     Python,
+    #[value(name = "javascript")]
     JavaScript,
+    #[value(name = "typescript")]
     TypeScript,
     Php,
     Dart,
@@ -28,15 +33,27 @@ pub enum Language {
     Nim,
 }
 
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum CppStd {
+    #[serde(rename = "c++0x")]
     Cpp0x,
+    #[serde(rename = "c++11")]
     Cpp11,
+    #[serde(rename = "c++17")]
     Cpp17,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct FlatCOptions {
     pub url: Option<String>,
@@ -135,6 +152,64 @@ pub struct FlatCOptions {
     pub grpc_python_typed_handlers: bool,
 }
 
+impl FlatCOptions {
+    /// Options for a single language, with the options of every other generator reset
+    pub fn for_language(&self, language: Language) -> Self {
+        let mut options = self.clone();
+        options.languages = vec![language];
+
+        if language != Language::Cpp {
+            options.cpp_include = Vec::new();
+            options.cpp_ptr_type = None;
+            options.cpp_str_type = None;
+            options.cpp_str_flex_ctor = false;
+            options.cpp_field_case_style = None;
+            options.no_cpp_direct_copy = false;
+            options.cpp_std = None;
+            options.cpp_static_reflection = false;
+            options.gen_compare = false;
+        }
+        if language != Language::Java {
+            options.java_package_prefix = None;
+            options.java_checkerframework = false;
+            options.gen_nullable = false;
+        }
+        if !matches!(language, Language::Kotlin | Language::KotlinKmp) {
+            options.gen_jvmstatic = false;
+            options.gen_json_emit = false;
+        }
+        if language != Language::Go {
+            options.go_namespace = None;
+            options.go_import = None;
+            options.go_module_name = None;
+        }
+        if language != Language::CSharp {
+            options.cs_global_alias = false;
+            options.cs_gen_json_serializer = false;
+        }
+        if language != Language::Python {
+            options.python_no_type_prefix_suffix = false;
+            options.python_typing = false;
+            options.python_version = None;
+            options.python_gen_numpy = false;
+        }
+        if language != Language::TypeScript {
+            options.ts_flat_files = false;
+            options.ts_entry_points = false;
+            options.ts_omit_entrypoint = false;
+        }
+        if language != Language::Rust {
+            options.rust_serialize = false;
+            options.rust_module_root_file = false;
+        }
+        if language != Language::Swift {
+            options.swift_implementation_only = false;
+        }
+
+        options
+    }
+}
+
 impl FlatC {
     pub fn new(binary: PathBuf) -> Result<Self> {
         if !binary.exists() {
@@ -146,13 +221,12 @@ impl FlatC {
         Ok(Self { binary })
     }
 
-    pub fn compile(
-        &self,
-        options: FlatCOptions,
-        files: Vec<PathBuf>,
-        binary_files: Vec<PathBuf>,
-    ) -> Result<()> {
-        let mut cmd = Command::new(&self.binary);
+    pub fn args(
+        options: &FlatCOptions,
+        files: &[PathBuf],
+        binary_files: &[PathBuf],
+    ) -> CommandArgs {
+        let mut cmd = CommandArgs::default();
 
         for lang in &options.languages {
             match lang {
@@ -466,18 +540,43 @@ impl FlatC {
             cmd.arg("--grpc-python-typed-handlers");
         }
 
-        for file in &files {
+        for file in files {
             cmd.arg(file);
         }
 
         if !binary_files.is_empty() {
             cmd.arg("--");
-            for binary_file in &binary_files {
+            for binary_file in binary_files {
                 cmd.arg(binary_file);
             }
         }
 
-        let status = cmd
+        cmd
+    }
+
+    /// Version reported by `flatc --version`, e.g. `25.12.19`
+    pub fn version(&self) -> Result<String> {
+        let output = CommandArgs::default()
+            .arg("--version")
+            .command(&self.binary)
+            .output()
+            .wrap_err_with(|| format!("Failed to execute FlatC at {}", self.binary.display()))?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .last()
+            .map(str::to_string)
+            .ok_or_else(|| eyre!("FlatC did not report a version"))
+    }
+
+    pub fn compile(
+        &self,
+        options: FlatCOptions,
+        files: Vec<PathBuf>,
+        binary_files: Vec<PathBuf>,
+    ) -> Result<()> {
+        let status = Self::args(&options, &files, &binary_files)
+            .command(&self.binary)
             .status()
             .wrap_err_with(|| format!("Failed to execute FlatC at {}", self.binary.display()))?;
 
@@ -1,13 +1,14 @@
+use crate::wrappers::command::CommandArgs;
+
 use eyre::{eyre, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::process::Command;
 
 pub struct Il2CppDumper {
     binary: PathBuf,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Il2CppDumperOptions {
     pub url: Option<String>,
@@ -74,8 +75,8 @@ impl Il2CppDumper {
         Ok(Self { binary })
     }
 
-    pub fn run(&self, options: Il2CppDumperOptions) -> Result<()> {
-        let mut cmd = Command::new(&self.binary);
+    pub fn args(options: &Il2CppDumperOptions) -> CommandArgs {
+        let mut cmd = CommandArgs::default();
 
         cmd.arg("--il2cpp").arg(&options.il2cpp);
 
@@ -156,12 +157,19 @@ impl Il2CppDumper {
             cmd.arg("--extract-il2-cpp-files");
         }
 
-        let status = cmd.status().wrap_err_with(|| {
-            format!(
-                "Failed to execute IL2CPP dumper at {}",
-                self.binary.display()
-            )
-        })?;
+        cmd
+    }
+
+    pub fn run(&self, options: Il2CppDumperOptions) -> Result<()> {
+        let status = Self::args(&options)
+            .command(&self.binary)
+            .status()
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to execute IL2CPP dumper at {}",
+                    self.binary.display()
+                )
+            })?;
 
         if !status.success() {
             return Err(eyre!(
@@ -1,3 +1,4 @@
+pub mod command;
 pub mod il2cpp_dumper;
 pub mod fbs_dumper;
 pub mod flatc;
\ No newline at end of file
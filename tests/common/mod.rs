@@ -0,0 +1,81 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+pub fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// `tests/fixtures/excel.fbs`, the schema most tests run against
+pub fn fixture() -> PathBuf {
+    manifest_dir()
+        .join("tests")
+        .join("fixtures")
+        .join("excel.fbs")
+}
+
+/// Scratch directory that starts out empty
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("bafb-{}-{}", name, std::process::id()));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs bafb in `dir` without any user, project or environment configuration
+pub fn bafb(dir: &Path, args: &[&str]) -> Output {
+    command(dir, args).output().unwrap()
+}
+
+/// bafb in `dir` without any user, project or environment configuration, for commands that are
+/// not expected to exit on their own
+pub fn command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_bafb"));
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir);
+    for (key, _) in env::vars() {
+        if key.starts_with("BAFB_") {
+            command.env_remove(key);
+        }
+    }
+    command
+}
+
+/// Everything bafb printed, errors are logged rather than returned for generation jobs
+pub fn printed(output: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+pub fn read_tree(root: &Path) -> BTreeMap<String, String> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(root, &path, files);
+            } else {
+                let relative = path.strip_prefix(root).unwrap();
+                let key = relative.to_string_lossy().replace('\\', "/");
+                files.insert(key, fs::read_to_string(&path).unwrap());
+            }
+        }
+    }
+
+    let mut files = BTreeMap::new();
+    if root.exists() {
+        walk(root, root, &mut files);
+    }
+    files
+}
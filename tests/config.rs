@@ -0,0 +1,351 @@
+//! Tests for configuration layering and overrides.
+//!
+//! `bafb` runs with `XDG_CONFIG_HOME` set to the scratch directory, so the user config is
+//! `<scratch>/bafb/config.toml` and the project config `<scratch>/bafb.toml`.
+
+mod common;
+
+use common::{bafb, command, printed, scratch};
+use std::fs;
+use std::path::Path;
+
+fn user_config(dir: &Path, content: &str) {
+    fs::create_dir_all(dir.join("bafb")).unwrap();
+    fs::write(dir.join("bafb").join("config.toml"), content).unwrap();
+}
+
+fn project_config(dir: &Path, content: &str) {
+    fs::write(dir.join("bafb.toml"), content).unwrap();
+}
+
+/// The flatc plan of `generate`, its command line and the options it was given
+fn generate_plan(dir: &Path, args: &[&str]) -> String {
+    fs::write(
+        dir.join("schema.fbs"),
+        "table Row {\n  Id: long;\n}\n\nroot_type Row;\n",
+    )
+    .unwrap();
+    let mut command = vec![
+        "--dry-run",
+        "generate",
+        "-f",
+        "schema.fbs",
+        "-l",
+        "rust",
+        "--backend",
+        "flatc",
+    ];
+    command.extend_from_slice(args);
+
+    let result = bafb(dir, &command);
+    assert!(result.status.success(), "{}", printed(&result));
+    String::from_utf8_lossy(&result.stdout).into_owned()
+}
+
+#[test]
+fn cli_flags_override_the_project_config() {
+    let dir = scratch("config-cli-project");
+    project_config(&dir, "[flatc]\noutput_path = \"project\"\n");
+
+    let plan = generate_plan(&dir, &["-o", "cli"]);
+    assert!(plan.contains("--rust -o cli "), "{}", plan);
+    assert!(plan.contains("output_path = \"cli\""), "{}", plan);
+}
+
+#[test]
+fn project_config_overrides_the_user_config() {
+    let dir = scratch("config-project-user");
+    user_config(
+        &dir,
+        "[flatc]\ngen_object_api = false\nscoped_enums = true\n",
+    );
+    project_config(&dir, "[flatc]\ngen_object_api = true\n");
+
+    let plan = generate_plan(&dir, &["-o", "generated"]);
+    assert!(plan.contains(" --gen-object-api "), "{}", plan);
+    assert!(plan.contains("gen_object_api = true (config)"), "{}", plan);
+    // Keys the project config leaves out keep their user value
+    assert!(plan.contains("scoped_enums = true (config)"), "{}", plan);
+}
+
+#[test]
+fn user_config_overrides_hardcoded_options_with_default_values() {
+    let dir = scratch("config-user-default");
+    // `false` is the default, it still turns off what `generate` sets for Rust
+    user_config(&dir, "[flatc]\ngen_object_api = false\n");
+
+    let plan = generate_plan(&dir, &["-o", "generated"]);
+    assert!(!plan.contains("--gen-object-api"), "{}", plan);
+    assert!(plan.contains("gen_object_api = false (config)"), "{}", plan);
+}
+
+#[test]
+fn unset_options_fall_through_to_hardcoded_values() {
+    let dir = scratch("config-fall-through");
+    user_config(&dir, "[flatc]\nscoped_enums = false\n");
+    project_config(&dir, "[flatc]\nno_prefix = false\n");
+
+    let plan = generate_plan(&dir, &["-o", "generated"]);
+    assert!(
+        plan.contains(" --gen-object-api --rust-serialize "),
+        "{}",
+        plan
+    );
+    assert!(
+        plan.contains("rust_serialize = true (hardcoded)"),
+        "{}",
+        plan
+    );
+    assert!(
+        plan.contains("gen_object_api = true (hardcoded)"),
+        "{}",
+        plan
+    );
+}
+
+/// `config show --origin` lines of the keys in `keys`, e.g. `flatc.scoped_enums`
+fn origins(dir: &Path, args: &[&str], keys: &[&str]) -> Vec<String> {
+    let mut command = vec!["config", "show", "--origin"];
+    command.extend_from_slice(args);
+    let result = bafb(dir, &command);
+    assert!(result.status.success(), "{}", printed(&result));
+
+    let shown = String::from_utf8_lossy(&result.stdout).into_owned();
+    let mut section = String::new();
+    let mut lines = Vec::new();
+    for line in shown.lines() {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name.to_string();
+        } else if let Some((key, _)) = line.split_once(" = ")
+            && keys.contains(&format!("{}.{}", section, key).as_str())
+        {
+            lines.push(format!("{}.{}", section, line));
+        }
+    }
+    lines
+}
+
+#[test]
+fn layers_user_then_project_files() {
+    let dir = scratch("config-layers");
+    user_config(
+        &dir,
+        "[flatc]\nno_prefix = true\nscoped_enums = true\ngen_mutable = true\n",
+    );
+    fs::write(
+        dir.join("config.toml"),
+        "[flatc]\nscoped_enums = false\ngen_mutable = false\n",
+    )
+    .unwrap();
+    project_config(&dir, "[flatc]\ngen_mutable = true\n");
+
+    let user = dir.join("bafb").join("config.toml");
+    assert_eq!(
+        origins(
+            &dir,
+            &[],
+            &[
+                "flatc.gen_mutable",
+                "flatc.no_prefix",
+                "flatc.scoped_enums",
+                "flatc.gen_compare",
+            ],
+        ),
+        [
+            "flatc.gen_compare = false  # default".to_string(),
+            // bafb.toml is read after config.toml
+            "flatc.gen_mutable = true  # project config bafb.toml".to_string(),
+            format!("flatc.no_prefix = true  # user config {}", user.display()),
+            "flatc.scoped_enums = false  # project config config.toml".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn config_flag_replaces_the_project_files() {
+    let dir = scratch("config-custom");
+    user_config(&dir, "[flatc]\nno_prefix = true\n");
+    project_config(&dir, "[flatc]\nno_prefix = false\nscoped_enums = true\n");
+    fs::write(dir.join("custom.toml"), "[flatc]\ngen_mutable = true\n").unwrap();
+
+    let user = dir.join("bafb").join("config.toml");
+    assert_eq!(
+        origins(
+            &dir,
+            &["--config", "custom.toml"],
+            &["flatc.gen_mutable", "flatc.no_prefix", "flatc.scoped_enums"],
+        ),
+        [
+            "flatc.gen_mutable = true  # project config custom.toml".to_string(),
+            format!("flatc.no_prefix = true  # user config {}", user.display()),
+            "flatc.scoped_enums = false  # default".to_string(),
+        ]
+    );
+
+    let result = bafb(&dir, &["config", "show", "--config", "missing.toml"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("Config file not found: missing.toml"));
+}
+
+#[test]
+fn env_and_set_override_the_config_files() {
+    let dir = scratch("config-overrides");
+    user_config(&dir, "[flatc]\nno_prefix = true\n");
+    project_config(&dir, "[flatc]\ngen_mutable = true\nscoped_enums = true\n");
+
+    let result = command(
+        &dir,
+        &[
+            "config",
+            "show",
+            "--origin",
+            "--set",
+            "flatc.gen_mutable=false",
+            "--set",
+            "flatc.gen_onefile = true",
+        ],
+    )
+    .env("BAFB_FLATC__SCOPED_ENUMS", "false")
+    .env("BAFB_FLATC__GEN_MUTABLE", "true")
+    .output()
+    .unwrap();
+    assert!(result.status.success(), "{}", printed(&result));
+
+    let shown = String::from_utf8_lossy(&result.stdout);
+    let user = dir.join("bafb").join("config.toml");
+    let expected = [
+        "gen_compare = false  # default".to_string(),
+        // `--set` is applied after the environment
+        "gen_mutable = false  # --set".to_string(),
+        "gen_onefile = true  # --set".to_string(),
+        format!("no_prefix = true  # user config {}", user.display()),
+        "scoped_enums = false  # environment variable BAFB_FLATC__SCOPED_ENUMS".to_string(),
+    ];
+    for line in expected {
+        assert!(
+            shown.lines().any(|shown| shown == line),
+            "{}\n{}",
+            line,
+            shown
+        );
+    }
+}
+
+#[test]
+fn rejects_malformed_overrides() {
+    let dir = scratch("config-malformed");
+
+    let result = bafb(&dir, &["config", "show", "--set", "flatc.scoped_enums"]);
+    assert!(!result.status.success());
+    assert!(
+        printed(&result).contains("Invalid override 'flatc.scoped_enums', expected KEY=VALUE"),
+        "{}",
+        printed(&result)
+    );
+
+    let result = bafb(
+        &dir,
+        &["config", "show", "--set", "flatc.proto_id_gap=bogus"],
+    );
+    assert!(!result.status.success());
+    assert!(printed(&result).contains(
+        "--set: `flatc.proto_id_gap`: unsupported value \"bogus\", expected one of nop, warn, error"
+    ));
+
+    let result = command(&dir, &["config", "show"])
+        .env("BAFB_FLATC__PROTO_ID_GAP", "bogus")
+        .output()
+        .unwrap();
+    assert!(!result.status.success());
+    assert!(
+        printed(&result)
+            .contains("environment variable BAFB_FLATC__PROTO_ID_GAP: `flatc.proto_id_gap`")
+    );
+}
+
+#[test]
+fn rejects_unknown_keys_and_values_with_their_location() {
+    let dir = scratch("config-invalid");
+    project_config(
+        &dir,
+        "[flatc]\nscoped_enum = true\n\n[il2cpp_dumper]\nlayout = \"bogus\"\n",
+    );
+
+    let result = bafb(&dir, &["config", "validate"]);
+    assert!(!result.status.success());
+    let printed = printed(&result);
+    assert!(
+        printed.contains(
+            "bafb.toml:2:1: unknown key `flatc.scoped_enum`, did you mean `scoped_enums`?"
+        ),
+        "{}",
+        printed
+    );
+    assert!(printed.contains(
+        "bafb.toml:5:10: `il2cpp_dumper.layout`: unsupported value \"bogus\", expected one of single, namespace, assembly, class, tree"
+    ));
+}
+
+#[test]
+fn profiles_apply_over_the_config_files() {
+    let dir = scratch("config-profile");
+    user_config(&dir, "[flatc]\nno_prefix = true\n");
+    project_config(
+        &dir,
+        "[flatc]\nscoped_enums = true\n\n[profiles.release.flatc]\ngen_mutable = true\nscoped_enums = false\n",
+    );
+
+    assert_eq!(
+        origins(&dir, &[], &["flatc.gen_mutable", "flatc.scoped_enums"],),
+        [
+            "flatc.gen_mutable = false  # default",
+            "flatc.scoped_enums = true  # project config bafb.toml",
+        ]
+    );
+    assert_eq!(
+        origins(
+            &dir,
+            &["--profile", "release"],
+            &["flatc.gen_mutable", "flatc.scoped_enums"],
+        ),
+        [
+            "flatc.gen_mutable = true  # profile release in project config bafb.toml",
+            "flatc.scoped_enums = false  # profile release in project config bafb.toml",
+        ]
+    );
+    // Explicit overrides win over the profile
+    assert_eq!(
+        origins(
+            &dir,
+            &["--profile", "release", "--set", "flatc.gen_mutable=false"],
+            &["flatc.gen_mutable"],
+        ),
+        ["flatc.gen_mutable = false  # --set"]
+    );
+}
+
+#[test]
+fn rejects_unknown_profiles() {
+    let dir = scratch("config-unknown-profile");
+
+    let result = bafb(&dir, &["config", "show", "--profile", "release"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("Unknown profile `release`, no profiles are defined"));
+
+    project_config(
+        &dir,
+        "[profiles.release.flatc]\ngen_mutable = true\n\n[profiles.debug.flatc]\ngen_mutable = false\n",
+    );
+    let result = bafb(&dir, &["config", "show", "--profile", "relase"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("Unknown profile `relase`, did you mean `release`?"));
+
+    let result = bafb(&dir, &["config", "show", "--profile", "nightly"]);
+    assert!(!result.status.success());
+    assert!(
+        printed(&result).contains("Unknown profile `nightly`, available profiles: debug, release")
+    );
+}
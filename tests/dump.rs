@@ -0,0 +1,68 @@
+//! Tests for `bafb dump` that do not need the game files.
+
+mod common;
+
+use common::{bafb, printed, scratch};
+
+#[test]
+fn rejects_a_stage_range_that_runs_nothing() {
+    let dir = scratch("dump-stages");
+
+    for dry_run in [false, true] {
+        let mut args = vec![
+            "dump", "japan", "-o", "output", "--from", "fbs", "--to", "fetch",
+        ];
+        if dry_run {
+            args.insert(0, "--dry-run");
+        }
+        let result = bafb(&dir, &args);
+        assert!(!result.status.success());
+        assert!(
+            printed(&result).contains("--from fbs comes after --to fetch"),
+            "{}",
+            printed(&result)
+        );
+    }
+    assert!(!dir.join("output").exists());
+}
+
+#[test]
+fn changelog_needs_a_game_version() {
+    let dir = scratch("dump-changelog");
+
+    let result = bafb(
+        &dir,
+        &[
+            "--dry-run",
+            "dump",
+            "japan",
+            "-o",
+            "output",
+            "--changelog",
+            "previous",
+        ],
+    );
+    assert!(!result.status.success());
+    assert!(
+        printed(&result).contains("--game-version <VERSION>"),
+        "{}",
+        printed(&result)
+    );
+
+    let result = bafb(
+        &dir,
+        &[
+            "--dry-run",
+            "dump",
+            "japan",
+            "-o",
+            "output",
+            "--changelog",
+            "previous",
+            "--game-version",
+            "1.46.0",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    assert!(printed(&result).contains("schema changelog in output/CHANGELOG.md"));
+}
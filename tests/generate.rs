@@ -0,0 +1,50 @@
+//! Tests for the plans of `bafb generate`.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+
+/// The flatc command line `plan` runs for `language`
+fn flatc_args<'a>(plan: &'a str, language: &str) -> &'a str {
+    let flag = format!(" --{} ", language);
+    plan.lines()
+        .map(str::trim)
+        .find(|line| line.starts_with('$') && line.contains(&flag))
+        .unwrap_or_else(|| panic!("no flatc run for {}\n{}", language, plan))
+}
+
+#[test]
+fn generates_each_language_into_its_own_directory() {
+    let dir = scratch("generate-languages");
+    let fixture = fixture();
+
+    let result = bafb(
+        &dir,
+        &[
+            "--dry-run",
+            "generate",
+            "-f",
+            fixture.to_str().unwrap(),
+            "-l",
+            "rust",
+            "-l",
+            "csharp",
+            "-o",
+            "out",
+            "--backend",
+            "flatc",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    let plan = String::from_utf8_lossy(&result.stdout);
+
+    let rust = flatc_args(&plan, "rust");
+    assert!(rust.contains(" -o out/rust "), "{}", rust);
+    assert!(rust.contains(" --rust-serialize "), "{}", rust);
+
+    let csharp = flatc_args(&plan, "csharp");
+    assert!(csharp.contains(" -o out/csharp "), "{}", csharp);
+    for flag in ["--rust-serialize", "--rust-module-root-file"] {
+        assert!(!csharp.contains(flag), "{}", csharp);
+    }
+}
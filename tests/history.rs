@@ -0,0 +1,144 @@
+//! Tests for the `bafb history` subcommands.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+use std::fs;
+use std::path::Path;
+
+/// Adds three versions of the fixture schema, the middle one adds `Scale` and the newest one
+/// changes its type
+fn populate(dir: &Path, store: &str) {
+    let schema = fs::read_to_string(fixture()).unwrap();
+    let versions = [
+        (
+            "1.9.2",
+            "2026-01-02",
+            schema
+                .replace("    Scale: float = 1.5;\n", "")
+                .replace("    Tanker = 2,\n", ""),
+        ),
+        ("1.9.10", "2026-02-03", schema.clone()),
+        (
+            "1.10.0",
+            "2026-03-04",
+            schema.replace("Scale: float = 1.5;", "Scale: double = 1.5;"),
+        ),
+    ];
+
+    // Versions are stored out of order, the store orders them by their numbers
+    for (version, date, source) in versions.iter().rev() {
+        let file = format!("{}.fbs", version);
+        fs::write(dir.join(&file), source).unwrap();
+        let result = bafb(
+            dir,
+            &[
+                "history",
+                "add",
+                "--fbs",
+                &file,
+                "--game-version",
+                version,
+                "--date",
+                date,
+                "--store",
+                store,
+            ],
+        );
+        assert!(result.status.success(), "{}", printed(&result));
+    }
+}
+
+fn stdout(dir: &Path, args: &[&str]) -> String {
+    let result = bafb(dir, args);
+    assert!(result.status.success(), "{}", printed(&result));
+    String::from_utf8_lossy(&result.stdout).into_owned()
+}
+
+#[test]
+fn logs_and_blames_fields_across_versions() {
+    for store in ["history", "history.sqlite"] {
+        let dir = scratch(&format!("history-{}", store.replace('.', "-")));
+        populate(&dir, store);
+
+        let log = stdout(
+            &dir,
+            &["history", "log", "CharacterExcel.Scale", "--store", store],
+        );
+        assert_eq!(
+            log,
+            "1.9.10 (2026-02-03) added FlatData.CharacterExcel.Scale: float = 1.5\n\
+             1.10.0 (2026-03-04) changed FlatData.CharacterExcel.Scale: float = 1.5 -> double = 1.5\n"
+        );
+
+        let log = stdout(
+            &dir,
+            &["history", "log", "FlatData.TacticRole", "--store", store],
+        );
+        assert!(log.starts_with("1.9.2 (2026-01-02) added FlatData.TacticRole: enum : long\n"));
+        assert!(log.ends_with("1.9.10 (2026-02-03) added FlatData.TacticRole.Tanker: 2\n"));
+
+        let blame = stdout(
+            &dir,
+            &["history", "blame", "CharacterExcel", "--store", store],
+        );
+        assert!(blame.starts_with("1.9.2  2026-01-02 Id              long (key)\n"));
+        assert!(blame.contains("\n1.10.0 2026-03-04 Scale           double = 1.5\n"));
+
+        let blame = stdout(
+            &dir,
+            &["history", "blame", "--store", store, "--format", "json"],
+        );
+        let blame: serde_json::Value = serde_json::from_str(&blame).unwrap();
+        let character = blame
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["name"] == "FlatData.CharacterExcel")
+            .unwrap();
+        assert_eq!(character["version"], "1.10.0");
+        assert_eq!(character["current"]["what"], "table");
+
+        // A version is only added once
+        let result = bafb(
+            &dir,
+            &[
+                "history",
+                "add",
+                "--fbs",
+                "1.9.2.fbs",
+                "--game-version",
+                "1.9.2",
+                "--store",
+                store,
+            ],
+        );
+        assert!(!result.status.success());
+        assert!(printed(&result).contains("already holds 1.9.2, pass --replace"));
+    }
+}
+
+#[test]
+fn doc_reads_the_history_store() {
+    let dir = scratch("history-doc");
+    populate(&dir, "history.db");
+
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "doc",
+            "--fbs",
+            "1.10.0.fbs",
+            "--output",
+            "site",
+            "--format",
+            "markdown",
+            "--history",
+            "history.db",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    let page = fs::read_to_string(dir.join("site/FlatData.TacticRole.md")).unwrap();
+    assert!(page.contains("| Tanker | 2 |  | 1.9.10 |\n"));
+}
@@ -0,0 +1,270 @@
+//! Tests for `bafb import` and the SQL exports it loads into.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+use rusqlite::Connection;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `_row`, `Id`, `DevName`, `IsPlayable`, `ProductionStep`, `Rarity`, `Spawn_x` and `Optional`
+type Character = (
+    i64,
+    i64,
+    Option<String>,
+    bool,
+    String,
+    String,
+    Option<f64>,
+    Option<i64>,
+);
+
+/// Decoded tables as the serde support of the generated code writes them
+fn decoded(dir: &Path) -> PathBuf {
+    let data = dir.join("decoded");
+    fs::create_dir_all(data.join("nested")).unwrap();
+    fs::write(
+        data.join("CharacterExcelTable.json"),
+        r#"{"DataList":[
+            {"Id":10010,"DevName":"Shiroko","CostumeGroupId":0,"IsPlayable":true,"ProductionStep":"Doing","Rarity":"N","TacticRole":"Supporter","Tags":["tag","other"],"StatLevelUpType":[1,2],"Spawn":{"x":1.0,"y":2.0,"layer":3},"Scale":1.5,"Optional":7},
+            {"Id":10011,"CostumeGroupId":0,"IsPlayable":false,"ProductionStep":"Release","Rarity":3,"TacticRole":"None","Scale":1.5}
+        ]}"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("scenarioexcel.json"),
+        r#"[{"FrameHash":18446744073709551615,"Bounds":[{"origin":{"x":0.5,"y":0.0,"layer":1},"size":2.0,"visible":true}],"Name":"s"}]"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("nested").join("RewardExcel.json"),
+        r#"[{"Reward_type":"ScenarioExcel","Reward":{"Name":"s"},"Amount":3},{"Reward_type":"NONE","Amount":-5}]"#,
+    )
+    .unwrap();
+    fs::write(data.join("notes.json"), "{}").unwrap();
+    data
+}
+
+fn import(dir: &Path, extra: &[&str]) -> std::process::Output {
+    let fixture = fixture();
+    let mut args = vec![
+        "import",
+        "--fbs",
+        fixture.to_str().unwrap(),
+        "--data",
+        "decoded",
+    ];
+    args.extend_from_slice(extra);
+    bafb(dir, &args)
+}
+
+#[test]
+fn exports_sql_tables() {
+    let dir = scratch("export-sql");
+    let fixture = fixture();
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "export",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--format",
+            "sqlite",
+            "--output",
+            "excel.sql",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "export failed:\n{}",
+        printed(&result)
+    );
+    let ddl = fs::read_to_string(dir.join("excel.sql")).unwrap();
+
+    assert!(ddl.contains(
+        "-- Where a character shows up\nCREATE TABLE \"TacticRole\" (\n    \"name\" TEXT PRIMARY KEY,\n    \"value\" INTEGER NOT NULL\n);\n"
+    ));
+    assert!(ddl.contains("    ('Supporter', -1);\n"));
+    assert!(ddl.contains("    \"Rarity\" TEXT NOT NULL REFERENCES \"Rarity\" (\"name\"),\n"));
+    assert!(ddl.contains("    \"Spawn_layer\" INTEGER,\n"));
+    assert!(!ddl.contains("OldTag"));
+    assert!(ddl.contains(
+        "CREATE TABLE \"CharacterExcel_Tags\" (\n    \"_row\" INTEGER NOT NULL REFERENCES \"CharacterExcel\" (\"_row\"),\n    \"_index\" INTEGER NOT NULL,\n    \"value\" TEXT NOT NULL,\n    PRIMARY KEY (\"_row\", \"_index\")\n);\n"
+    ));
+    assert!(ddl.contains("    \"Members\" TEXT,\n"));
+    assert!(ddl.contains("    \"Reward_type\" TEXT REFERENCES \"Reward\" (\"name\"),\n"));
+    assert!(ddl.contains("    \"FrameHash\" TEXT NOT NULL,\n"));
+    assert!(ddl.contains("CREATE TABLE \"Nested_GroupExcel\" (\n"));
+
+    // The exported tables are the ones the import creates
+    Connection::open_in_memory()
+        .unwrap()
+        .execute_batch(&ddl)
+        .unwrap();
+}
+
+#[test]
+fn imports_decoded_tables_into_sqlite() {
+    let dir = scratch("import-sqlite");
+    decoded(&dir);
+
+    let result = import(&dir, &["--output", "game.db"]);
+    assert!(
+        result.status.success(),
+        "import failed:\n{}",
+        printed(&result)
+    );
+
+    let connection = Connection::open(dir.join("game.db")).unwrap();
+    let characters: Vec<Character> = connection
+        .prepare(
+            "SELECT _row, Id, DevName, IsPlayable, ProductionStep, Rarity, Spawn_x, Optional \
+             FROM CharacterExcel ORDER BY _row",
+        )
+        .unwrap()
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        characters,
+        vec![
+            (
+                1,
+                10010,
+                Some("Shiroko".to_string()),
+                true,
+                "Doing".to_string(),
+                "N".to_string(),
+                Some(1.0),
+                Some(7)
+            ),
+            // Numbers of enum values are stored as their names
+            (
+                2,
+                10011,
+                None,
+                false,
+                "Release".to_string(),
+                "SR".to_string(),
+                None,
+                None
+            ),
+        ]
+    );
+
+    let tags: Vec<(i64, i64, String)> = connection
+        .prepare("SELECT _row, _index, value FROM CharacterExcel_Tags ORDER BY _index")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        tags,
+        vec![(1, 0, "tag".to_string()), (1, 1, "other".to_string())]
+    );
+
+    let (hash, layer, visible): (String, i64, bool) = connection
+        .query_row(
+            "SELECT FrameHash, origin_layer, visible FROM ScenarioExcel \
+             JOIN ScenarioExcel_Bounds USING (_row)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(
+        (hash.as_str(), layer, visible),
+        ("18446744073709551615", 1, true)
+    );
+
+    let role: i64 = connection
+        .query_row(
+            "SELECT TacticRole.value FROM CharacterExcel \
+             JOIN TacticRole ON TacticRole.name = CharacterExcel.TacticRole WHERE Id = 10010",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(role, -1);
+
+    let rewards: Vec<(String, Option<String>, i64)> = connection
+        .prepare("SELECT Reward_type, Reward ->> '$.Name', Amount FROM RewardExcel ORDER BY _row")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        rewards,
+        vec![
+            ("ScenarioExcel".to_string(), Some("s".to_string()), 3),
+            ("NONE".to_string(), None, -5),
+        ]
+    );
+
+    // Tables without a file are created empty
+    let groups: i64 = connection
+        .query_row("SELECT COUNT(*) FROM Nested_GroupExcel", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(groups, 0);
+}
+
+#[test]
+fn import_writes_scripts_and_rejects_invalid_rows() {
+    let dir = scratch("import-script");
+    let data = decoded(&dir);
+
+    let result = import(&dir, &["--output", "game.sql", "--script", "postgres"]);
+    assert!(
+        result.status.success(),
+        "import failed:\n{}",
+        printed(&result)
+    );
+    let script = fs::read_to_string(dir.join("game.sql")).unwrap();
+    assert!(script.contains("    \"FrameHash\" NUMERIC(20) NOT NULL,\n"));
+    assert!(script.contains(
+        "INSERT INTO \"ScenarioExcel\" (\"_row\", \"FrameHash\", \"Name\") VALUES (1, 18446744073709551615, 's');\n"
+    ));
+    assert!(script.contains(
+        "INSERT INTO \"ScenarioExcel_Bounds\" (\"_row\", \"_index\", \"origin_x\", \"origin_y\", \"origin_layer\", \"size\", \"visible\") VALUES (1, 0, 0.5, 0.0, 1, 2.0, TRUE);\n"
+    ));
+    assert!(script.ends_with("COMMIT;\n"));
+
+    fs::write(
+        data.join("scenarioexcel.json"),
+        r#"[{"FrameHash":1,"Bounds":[],"Name":"s","Extra":1}]"#,
+    )
+    .unwrap();
+    let result = import(&dir, &["--output", "game.db"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("Unknown field Extra"));
+
+    fs::write(
+        data.join("scenarioexcel.json"),
+        r#"[{"FrameHash":1,"Bounds":[],"Name":"s"}]"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("CharacterExcelTable.json"),
+        r#"{"DataList":[{"Id":1,"CostumeGroupId":0,"IsPlayable":true,"ProductionStep":"Later","Rarity":"N","TacticRole":"None","Scale":1.0}]}"#,
+    )
+    .unwrap();
+    let result = import(&dir, &["--output", "game.db"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("ProductionStep cannot hold \"Later\""));
+}
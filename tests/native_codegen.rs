@@ -0,0 +1,218 @@
+//! Snapshot tests for the native Rust backend.
+//!
+//! Every `tests/fixtures/*.fbs` schema is generated with `bafb generate -l rust` and compared
+//! with `tests/snapshots/<schema>/`. Run with `BAFB_UPDATE_SNAPSHOTS=1` to rewrite them.
+//!
+//! The snapshots are written by the native backend itself, not by flatc. They catch unintended
+//! changes to the generated code, they do not check that it matches what flatc generates.
+
+mod common;
+
+use common::{bafb, fixture, manifest_dir, printed, read_tree, scratch};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+const UPDATE_VARIABLE: &str = "BAFB_UPDATE_SNAPSHOTS";
+
+fn generate_with(fixture: &Path, output: &Path, extra: &[&str]) -> Output {
+    let scratch = output.parent().unwrap();
+    let mut args = vec![
+        "generate",
+        "--fbs",
+        fixture.to_str().unwrap(),
+        "--language",
+        "rust",
+        "--output",
+        output.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    bafb(scratch, &args)
+}
+
+fn generate(fixture: &Path, output: &Path) -> Output {
+    generate_with(fixture, output, &[])
+}
+
+#[test]
+fn rust_snapshots() {
+    let fixtures = manifest_dir().join("tests").join("fixtures");
+    let snapshots = manifest_dir().join("tests").join("snapshots");
+    let update = env::var_os(UPDATE_VARIABLE).is_some();
+
+    let mut schemas: Vec<PathBuf> = fs::read_dir(&fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "fbs"))
+        .collect();
+    schemas.sort();
+    assert!(!schemas.is_empty(), "no fixtures in {}", fixtures.display());
+
+    let mut mismatches = Vec::new();
+    for schema in schemas {
+        let name = schema.file_stem().unwrap().to_string_lossy().into_owned();
+        let output = scratch(&name).join("generated");
+
+        let result = generate(&schema, &output);
+        assert!(
+            result.status.success(),
+            "generating {} failed:\n{}",
+            name,
+            printed(&result)
+        );
+
+        let generated = read_tree(&output);
+        let expected_dir = snapshots.join(&name);
+        if update {
+            if expected_dir.exists() {
+                fs::remove_dir_all(&expected_dir).unwrap();
+            }
+            for (file, content) in &generated {
+                let path = expected_dir.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+            continue;
+        }
+
+        let expected = read_tree(&expected_dir);
+        for file in expected.keys().chain(generated.keys()) {
+            let key = format!("{}/{}", name, file);
+            if expected.get(file) != generated.get(file) && !mismatches.contains(&key) {
+                mismatches.push(key);
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "generated code differs from the snapshots, rerun with {}=1 to accept:\n  {}",
+        UPDATE_VARIABLE,
+        mismatches.join("\n  ")
+    );
+}
+
+#[test]
+fn rejects_unknown_types() {
+    let dir = scratch("unknown-type");
+    let schema = dir.join("broken.fbs");
+    fs::write(&schema, "table Broken {\n  Value: Missing;\n}\n").unwrap();
+
+    let result = generate(&schema, &dir.join("generated"));
+    let printed = printed(&result);
+    assert!(!result.status.success());
+    assert!(
+        printed.contains("Missing"),
+        "unexpected error:\n{}",
+        printed
+    );
+    assert!(!dir.join("generated").join("broken_generated.rs").exists());
+}
+
+#[test]
+fn reports_syntax_error_location() {
+    let dir = scratch("syntax-error");
+    let schema = dir.join("broken.fbs");
+    fs::write(&schema, "table Broken {\n  Value int;\n}\n").unwrap();
+
+    let result = generate(&schema, &dir.join("generated"));
+    let printed = printed(&result);
+    assert!(!result.status.success());
+    assert!(
+        printed.contains("2:9"),
+        "error without a location:\n{}",
+        printed
+    );
+}
+
+#[test]
+fn emits_decrypt_helpers() {
+    let fixture = fixture();
+    let output = scratch("decrypt-helpers").join("generated");
+
+    let result = generate_with(&fixture, &output, &["--decrypt-helpers"]);
+    assert!(
+        result.status.success(),
+        "generating failed:\n{}",
+        printed(&result)
+    );
+
+    let files = read_tree(&output);
+    assert!(files["mod.rs"].contains("mod table_encryption_generated;"));
+    assert!(files["table_encryption_generated.rs"].contains("pub fn create_key"));
+
+    let row = &files["flat_data/character_excel_generated.rs"];
+    assert!(
+        row.contains("create_key(\"Character\")"),
+        "row without decrypt:\n{}",
+        row
+    );
+    let table = &files["flat_data/character_excel_table_generated.rs"];
+    assert!(table.contains("pub fn load_decrypted(bytes: &[u8])"));
+    assert!(table.contains("xor(\"CharacterExcelTable\", &mut data)"));
+
+    // Helpers are an addition, the rest of the output matches the plain snapshots
+    let snapshot = read_tree(&manifest_dir().join("tests").join("snapshots").join("excel"));
+    let position = "flat_data/position_generated.rs";
+    assert_eq!(files.get(position), snapshot.get(position));
+}
+
+#[test]
+fn decrypt_helpers_need_native_rust() {
+    let fixture = fixture();
+    let output = scratch("decrypt-flatc").join("generated");
+
+    let result = generate_with(
+        &fixture,
+        &output,
+        &["--decrypt-helpers", "--backend", "flatc"],
+    );
+    let printed = printed(&result);
+    assert!(!result.status.success());
+    assert!(
+        printed.contains("native backend"),
+        "unexpected error:\n{}",
+        printed
+    );
+}
+
+#[test]
+fn filters_tables() {
+    let dir = scratch("filter");
+    let schema = dir.join("excel.fbs");
+    fs::copy(
+        fixture(),
+        &schema,
+    )
+    .unwrap();
+    let output = dir.join("generated");
+
+    let result = generate_with(
+        &schema,
+        &output,
+        &["--include", "*Excel", "--exclude", "Scenario*,Reward*"],
+    );
+    assert!(
+        result.status.success(),
+        "generating failed:\n{}",
+        printed(&result)
+    );
+
+    let filtered = fs::read_to_string(dir.join("excel.filtered.fbs")).unwrap();
+    assert!(filtered.contains("table GroupExcel {"));
+    assert!(filtered.contains("enum TacticRole : long {"));
+    assert!(!filtered.contains("ScenarioExcel"));
+    assert!(!filtered.contains("root_type"));
+
+    let files = read_tree(&output);
+    assert!(files.contains_key("flat_data/nested/group_excel_generated.rs"));
+    assert!(files.contains_key("flat_data/character_excel_generated.rs"));
+    assert!(!files.contains_key("flat_data/character_excel_table_generated.rs"));
+    assert!(!files.contains_key("flat_data/bounds_generated.rs"));
+
+    // The filtered schema generates the same code for the types it kept
+    let snapshot = read_tree(&manifest_dir().join("tests").join("snapshots").join("excel"));
+    let row = "flat_data/character_excel_generated.rs";
+    assert_eq!(files.get(row), snapshot.get(row));
+}
@@ -0,0 +1,155 @@
+//! Tests for `bafb generate --package`.
+//!
+//! Only the Rust backend runs without flatc, the other package kinds are covered by the tests in
+//! `src/tools/package/mod.rs`.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+/// `generate` packaging the fixture as a crate, with `global` options in front of the command
+fn generate(dir: &Path, global: &[&str], args: &[&str]) -> Output {
+    let fixture = fixture();
+    let command = [
+        global,
+        &[
+            "generate",
+            "-f",
+            fixture.to_str().unwrap(),
+            "-l",
+            "rust",
+            "-o",
+            "ba-flatdata",
+            "--package",
+            "cargo",
+        ],
+        args,
+    ]
+    .concat();
+    bafb(dir, &command)
+}
+
+#[test]
+fn packages_need_an_explicit_version() {
+    let dir = scratch("package-version");
+
+    for global in [&[][..], &["--dry-run"]] {
+        let result = generate(&dir, global, &[]);
+        assert!(!result.status.success());
+        assert!(
+            printed(&result)
+                .contains("Pass --package-version or --dump-date to version the packages"),
+            "{}",
+            printed(&result)
+        );
+    }
+    assert!(!dir.join("ba-flatdata").exists());
+
+    let result = generate(&dir, &[], &["--dump-date", "2025-6-17"]);
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("Expected a YYYY-MM-DD dump date, got 2025-6-17"));
+
+    let result = generate(&dir, &[], &["--dump-date", "2025-06-17"]);
+    assert!(result.status.success(), "{}", printed(&result));
+    let manifest = fs::read_to_string(dir.join("ba-flatdata/Cargo.toml")).unwrap();
+    assert!(
+        manifest.contains("\nversion = \"2025.6.17\"\n"),
+        "{}",
+        manifest
+    );
+
+    let result = generate(
+        &dir,
+        &[],
+        &["--dump-date", "2025-06-17", "--package-version", "1.46.0"],
+    );
+    assert!(!result.status.success());
+}
+
+#[test]
+fn packages_regions_as_cargo_features() {
+    let dir = scratch("package-cargo");
+    let schema = fs::read_to_string(fixture()).unwrap();
+    for region in ["Japan", "Global"] {
+        let file = format!("{}.fbs", region);
+        fs::write(dir.join(&file), schema.replace("FlatData", region)).unwrap();
+
+        let result = bafb(
+            &dir,
+            &[
+                "generate",
+                "-f",
+                &file,
+                "-l",
+                "rust",
+                "-o",
+                "ba-flatdata",
+                "--package",
+                "cargo",
+                "--package-version",
+                "1.46.0",
+            ],
+        );
+        assert!(result.status.success(), "{}", printed(&result));
+    }
+
+    let manifest = fs::read_to_string(dir.join("ba-flatdata/Cargo.toml")).unwrap();
+    assert!(
+        manifest.starts_with(
+            "[package]\nname = \"ba-flatdata\"\nversion = \"1.46.0\"\nedition = \"2021\"\n"
+        ),
+        "{}",
+        manifest
+    );
+    assert!(
+        manifest.contains("\nflatbuffers = { version = \""),
+        "{}",
+        manifest
+    );
+    assert!(manifest.contains(" features = [\"serialize\"] }\nserde = \"1.0\"\n"));
+    assert!(
+        manifest
+            .ends_with("[features]\ndefault = [\"japan\", \"global\"]\njapan = []\nglobal = []\n")
+    );
+
+    // Both regions live in one lib.rs, each behind its feature
+    let lib = fs::read_to_string(dir.join("ba-flatdata/src/lib.rs")).unwrap();
+    assert!(lib.starts_with("#![allow("), "{}", lib);
+    assert!(lib.contains("\n#[cfg(feature = \"japan\")]\npub mod japan {\n"));
+    assert!(lib.contains("\n#[cfg(feature = \"global\")]\npub mod global {\n"));
+    assert!(!dir.join("ba-flatdata/src/mod.rs").exists());
+    assert!(
+        dir.join("ba-flatdata/src/japan/nested/group_excel_generated.rs")
+            .is_file()
+    );
+    assert!(
+        dir.join("ba-flatdata/src/global/character_excel_generated.rs")
+            .is_file()
+    );
+}
+
+#[test]
+fn packages_without_serde_unless_rust_serialize() {
+    let dir = scratch("package-cargo-plain");
+
+    let result = generate(
+        &dir,
+        &["--set", "flatc.rust_serialize=false"],
+        &["--package-version", "1.46.0"],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+
+    let manifest = fs::read_to_string(dir.join("ba-flatdata/Cargo.toml")).unwrap();
+    assert!(
+        manifest.contains("\n[dependencies]\nflatbuffers = \""),
+        "{}",
+        manifest
+    );
+    assert!(!manifest.contains("serde"));
+    // The FlatData namespace is not a region, so there are no features
+    assert!(!manifest.contains("[features]"));
+    assert!(dir.join("ba-flatdata/src/lib.rs").is_file());
+}
@@ -0,0 +1,95 @@
+//! Tests for the `[postprocess]` rewriting of generated Rust code and `bafb postprocess`.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+use std::fs;
+
+#[test]
+fn strips_banners_from_the_module_root_only() {
+    let dir = scratch("postprocess-strip");
+    fs::write(
+        dir.join("bafb.toml"),
+        "[postprocess]\nstrip_banners = true\nprelude = [\"use bacy::table_encryption::table_encryption_service;\"]\n",
+    )
+    .unwrap();
+
+    let fixture = fixture();
+    let result = bafb(
+        &dir,
+        &[
+            "generate",
+            "-f",
+            fixture.to_str().unwrap(),
+            "-l",
+            "rust",
+            "-o",
+            "generated",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+
+    let root = fs::read_to_string(dir.join("generated/mod.rs")).unwrap();
+    assert!(
+        root.starts_with(
+            "use bacy::table_encryption::table_encryption_service;\n\npub mod flat_data {\n"
+        ),
+        "{}",
+        root
+    );
+    assert!(!root.contains("// @generated"));
+
+    let source = fs::read_to_string(dir.join("generated/flat_data/rarity_generated.rs")).unwrap();
+    assert!(
+        source.starts_with(
+            "// automatically generated by the FlatBuffers compiler, do not modify\n// @generated\n"
+        ),
+        "{}",
+        source
+    );
+}
+
+#[test]
+fn merges_the_module_roots_of_several_regions() {
+    let dir = scratch("postprocess-merge");
+    fs::write(
+        dir.join("japan_mod.rs"),
+        "// Automatically generated by the Flatbuffers compiler. Do not modify.\n// @generated\nuse bacy::table_encryption::table_encryption_service;\npub mod japan {\n  use super::*;\n  mod excel_generated;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("global_mod.rs"),
+        "// @generated\nuse bacy::table_encryption::table_encryption_service;\npub mod global {\n  use super::*;\n  mod excel_generated;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.toml"),
+        "[postprocess]\nprelude = [\"use bacy::table_encryption::table_encryption_service;\"]\nmerge = [\"japan_mod.rs\", \"global_mod.rs\", \"korea_mod.rs\"]\n",
+    )
+    .unwrap();
+
+    // Roots that were not generated are skipped
+    let result = bafb(&dir, &["postprocess", "--output", "."]);
+    assert!(result.status.success(), "{}", printed(&result));
+    assert_eq!(
+        fs::read_to_string(dir.join("mod.rs")).unwrap(),
+        "use bacy::table_encryption::table_encryption_service;\n\
+         \n\
+         pub mod japan {\n  use super::*;\n  mod excel_generated;\n}\n\
+         \n\
+         pub mod global {\n  use super::*;\n  mod excel_generated;\n}\n"
+    );
+
+    let result = bafb(
+        &dir,
+        &[
+            "postprocess",
+            "--output",
+            ".",
+            "--set",
+            "postprocess.merge=[]",
+        ],
+    );
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("postprocess.merge lists no module roots to merge"));
+}
@@ -0,0 +1,447 @@
+//! Tests for the `bafb schema` subcommands.
+
+mod common;
+
+use common::{bafb, fixture, printed, read_tree, scratch};
+use std::fs;
+
+#[test]
+fn split_and_merge_round_trip() {
+    let dir = scratch("schema-split");
+    let fixture = fixture();
+
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "split",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--output",
+            "split",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "split failed:\n{}",
+        printed(&result)
+    );
+
+    let files = read_tree(&dir.join("split"));
+    let names: Vec<&str> = files.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        [
+            "FlatData/CharacterExcel.fbs",
+            "FlatData/CharacterExcelTable.fbs",
+            "FlatData/Nested/GroupExcel.fbs",
+            "FlatData/Reward.fbs",
+            "FlatData/RewardExcel.fbs",
+            "FlatData/ScenarioExcel.fbs",
+            "excel.fbs",
+            "shared.fbs",
+        ]
+    );
+    assert!(
+        files["FlatData/Nested/GroupExcel.fbs"]
+            .starts_with("include \"../CharacterExcel.fbs\";\ninclude \"../../shared.fbs\";\n")
+    );
+    assert!(files["excel.fbs"].contains("root_type FlatData.CharacterExcelTable;"));
+    assert!(!files["shared.fbs"].contains("include"));
+
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "merge",
+            "--fbs",
+            "split/excel.fbs",
+            "--output",
+            "merged.fbs",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "merge failed:\n{}",
+        printed(&result)
+    );
+
+    // The merged schema generates the same code as the original
+    let generated = |schema: &str, output: &str| {
+        let result = bafb(
+            &dir,
+            &[
+                "generate",
+                "--fbs",
+                schema,
+                "--language",
+                "rust",
+                "--output",
+                output,
+            ],
+        );
+        assert!(
+            result.status.success(),
+            "generate failed:\n{}",
+            printed(&result)
+        );
+        read_tree(&dir.join(output))
+    };
+    let original = generated(fixture.to_str().unwrap(), "original");
+    assert_eq!(generated("merged.fbs", "merged"), original);
+    assert_eq!(generated("split/excel.fbs", "from-split"), original);
+}
+
+#[test]
+fn split_rejects_clashing_index() {
+    let dir = scratch("schema-split-clash");
+    fs::write(dir.join("Item.fbs"), "table Item {\n    Id: long;\n}\n").unwrap();
+
+    let result = bafb(
+        &dir,
+        &["schema", "split", "--fbs", "Item.fbs", "--output", "split"],
+    );
+    assert!(!result.status.success());
+    assert!(printed(&result).contains("clashes"), "{}", printed(&result));
+}
+
+#[test]
+fn fmt_orders_declarations_and_checks() {
+    let dir = scratch("schema-fmt");
+    fs::copy(fixture(), dir.join("excel.fbs")).unwrap();
+    fs::copy(fixture(), dir.join("dependency.fbs")).unwrap();
+
+    let result = bafb(&dir, &["schema", "fmt", "--check", "excel.fbs"]);
+    assert!(!result.status.success());
+    assert!(
+        printed(&result).contains("Not formatted: excel.fbs"),
+        "{}",
+        printed(&result)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("excel.fbs")).unwrap(),
+        fs::read_to_string(fixture()).unwrap()
+    );
+
+    for args in [
+        &["schema", "fmt", "excel.fbs"][..],
+        &["schema", "fmt", "--order", "dependency", "dependency.fbs"],
+        &["schema", "fmt", "--check", "excel.fbs"],
+        &[
+            "schema",
+            "fmt",
+            "--check",
+            "--order",
+            "dependency",
+            "dependency.fbs",
+        ],
+    ] {
+        let result = bafb(&dir, args);
+        assert!(
+            result.status.success(),
+            "{:?} failed:\n{}",
+            args,
+            printed(&result)
+        );
+    }
+
+    let declarations = |name: &str| -> Vec<String> {
+        fs::read_to_string(dir.join(name))
+            .unwrap()
+            .lines()
+            .filter(|line| line.ends_with('{'))
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .map(str::to_string)
+            .collect()
+    };
+    assert_eq!(
+        declarations("excel.fbs"),
+        [
+            "Bounds",
+            "CharacterExcel",
+            "CharacterExcelTable",
+            "Position",
+            "ProductionStep",
+            "Rarity",
+            "Reward",
+            "RewardExcel",
+            "ScenarioExcel",
+            "TacticRole",
+            "GroupExcel",
+        ]
+    );
+    let dependency = declarations("dependency.fbs");
+    let position = |name: &str| dependency.iter().position(|other| other == name).unwrap();
+    assert!(position("Position") < position("Bounds"));
+    assert!(position("Rarity") < position("CharacterExcel"));
+    assert!(position("CharacterExcel") < position("CharacterExcelTable"));
+
+    // Reordering declarations only moves the module declarations in mod.rs
+    let generated = |schema: &str, output: &str| {
+        let result = bafb(
+            &dir,
+            &[
+                "generate",
+                "--fbs",
+                schema,
+                "--language",
+                "rust",
+                "--output",
+                output,
+            ],
+        );
+        assert!(
+            result.status.success(),
+            "generate failed:\n{}",
+            printed(&result)
+        );
+        let mut files = read_tree(&dir.join(output));
+        files.remove("mod.rs");
+        files
+    };
+    let original = generated(fixture().to_str().unwrap(), "original");
+    assert_eq!(generated("excel.fbs", "alphabetical"), original);
+    assert_eq!(generated("dependency.fbs", "dependency"), original);
+}
+
+#[test]
+fn lint_reports_rules() {
+    let dir = scratch("schema-lint");
+    fs::write(
+        dir.join("lint.fbs"),
+        r#"namespace FlatData;
+
+enum Grade : int { A = 0, B = 1, C = 1, D = 4 }
+
+enum Unused : byte { None }
+
+table Empty {
+}
+
+table ItemExcel {
+    Grade: Grade;
+    Reward: RewardPlaceholder;
+    type: int;
+    ItemId: long;
+    Item_Id: long;
+}
+
+namespace FlatData.Other;
+
+table ItemExcel {
+    Id: long;
+}
+"#,
+    )
+    .unwrap();
+
+    let result = bafb(
+        &dir,
+        &[
+            "schema", "lint", "--fbs", "lint.fbs", "--format", "json", "-l", "rust",
+        ],
+    );
+    assert!(!result.status.success());
+    let findings: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    let findings: Vec<String> = findings
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            format!(
+                "{} {} {}",
+                finding["severity"].as_str().unwrap(),
+                finding["rule"].as_str().unwrap(),
+                finding["location"].as_str().unwrap()
+            )
+        })
+        .collect();
+    assert_eq!(
+        findings,
+        [
+            "warning empty-table FlatData.Empty",
+            "warning enum-gap FlatData.Grade",
+            "error duplicate-enum-value FlatData.Grade.C",
+            "warning duplicate-name FlatData.ItemExcel",
+            "error snake-case-collision FlatData.ItemExcel.Item_Id",
+            "error unresolved-type FlatData.ItemExcel.Reward",
+            "warning reserved-keyword FlatData.ItemExcel.type",
+            "warning duplicate-name FlatData.Other.ItemExcel",
+            "warning unreferenced-type FlatData.Unused",
+        ]
+    );
+
+    // Allowed rules are skipped and the remaining warnings do not fail the command
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "lint",
+            "--fbs",
+            "lint.fbs",
+            "--allow",
+            "duplicate-enum-value,snake-case-collision,unresolved-type",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    assert!(
+        String::from_utf8_lossy(&result.stdout)
+            .contains("warning[enum-gap] FlatData.Grade: no value for 2 to 3"),
+        "{}",
+        printed(&result)
+    );
+}
+
+#[test]
+fn doc_renders_pages_with_annotations() {
+    let dir = scratch("schema-doc");
+    let schema = fs::read_to_string(fixture()).unwrap();
+    let global = schema.replace("    Optional: uint = null;\n", "");
+    fs::write(dir.join("global.fbs"), &global).unwrap();
+    fs::create_dir_all(dir.join("history")).unwrap();
+    fs::write(dir.join("history").join("1.10.0.fbs"), &schema).unwrap();
+    fs::write(
+        dir.join("history").join("1.9.2.fbs"),
+        global.replace("    Scale: float = 1.5;\n", ""),
+    )
+    .unwrap();
+
+    let fixture = fixture();
+    let japan = format!("japan={}", fixture.display());
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "doc",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--output",
+            "site",
+            "--region",
+            &japan,
+            "--region",
+            "global=global.fbs",
+            "--history",
+            "history",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    let site = read_tree(&dir.join("site"));
+
+    let character = &site["FlatData.CharacterExcel.html"];
+    assert!(character.contains("<h1>table CharacterExcel</h1>\n"));
+    // Field types link to their page
+    assert!(character.contains(
+        "<td class=\"type\"><a href=\"FlatData.ProductionStep.html\">ProductionStep</a></td><td><code>Release</code></td>"
+    ));
+    // Versions are ordered by their numbers, 1.9.2 comes before 1.10.0
+    assert!(character.contains(
+        "<td class=\"type\">float</td><td><code>1.5</code></td><td></td><td></td><td>japan, global</td><td>1.10.0</td></tr>"
+    ));
+    assert!(character.contains("<td></td><td>japan</td><td>1.10.0</td></tr>"));
+    assert!(character.contains("<tr id=\"OldTag\" class=\"deprecated\">"));
+    assert!(character.contains(
+        "<h2>Used by</h2>\n<ul>\n<li><a href=\"FlatData.CharacterExcelTable.html\">FlatData.CharacterExcelTable</a></li>\n<li><a href=\"FlatData.Nested.GroupExcel.html\">FlatData.Nested.GroupExcel</a></li>\n<li><a href=\"FlatData.Reward.html\">FlatData.Reward</a></li>\n</ul>\n"
+    ));
+
+    assert!(site["FlatData.TacticRole.html"].contains(
+        "<tr id=\"Supporter\"><td><a href=\"#Supporter\">Supporter</a></td><td class=\"type\">-1</td>"
+    ));
+    assert!(site["index.html"].contains("<h2>Enums (3)</h2>\n"));
+    assert!(site["used-by.html"].contains(
+        "<tr><td><a href=\"FlatData.Bounds.html\">FlatData.Bounds</a></td><td><a href=\"FlatData.ScenarioExcel.html\">FlatData.ScenarioExcel</a></td></tr>"
+    ));
+    assert!(site["search-index.js"].contains(
+        r#"{"name":"FlatData.CharacterExcel.Scale","kind":"field","page":"FlatData.CharacterExcel.html#Scale"}"#
+    ));
+
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "doc",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--output",
+            "wiki",
+            "--format",
+            "markdown",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+    let wiki = read_tree(&dir.join("wiki"));
+    assert!(wiki["FlatData.Nested.GroupExcel.md"].contains(
+        "| Members | \\[[FlatData.CharacterExcel](FlatData.CharacterExcel.md)\\] |  |  |  |\n"
+    ));
+    assert!(
+        wiki["README.md"].contains(
+            "- [FlatData.TacticRole](FlatData.TacticRole.md): Where a character shows up\n"
+        )
+    );
+    assert!(!wiki.contains_key("search.js"));
+}
+
+#[test]
+fn changelog_groups_changes_since_a_previous_dump() {
+    let dir = scratch("schema-changelog");
+    let schema = fs::read_to_string(fixture()).unwrap();
+    let reward = "table RewardExcel {\n    Reward: Reward;\n    Amount: short = -5;\n}\n";
+    assert!(schema.contains(reward));
+    let previous = schema
+        .replace(reward, "")
+        .replace("    Scale: float = 1.5;\n", "")
+        .replace("    Tanker = 2,\n", "")
+        .replace("FrameHash: ulong;", "FrameHash: long;")
+        .replace(
+            "root_type",
+            "table OldExcel {\n    Id: long;\n}\n\nroot_type",
+        );
+    fs::create_dir_all(dir.join("previous")).unwrap();
+    fs::write(dir.join("previous").join("BlueArchive.fbs"), previous).unwrap();
+
+    let fixture = fixture();
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "changelog",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--previous",
+            "previous",
+            "--output",
+            "CHANGELOG.md",
+            "--game-version",
+            "1.46.0",
+        ],
+    );
+    assert!(result.status.success(), "{}", printed(&result));
+
+    assert_eq!(
+        fs::read_to_string(dir.join("CHANGELOG.md")).unwrap(),
+        "# 1.46.0
+
+Schema changes since previous: 1 new table, 1 removed table, 1 new field, 1 enum addition, 1 type change.
+
+## New tables (1)
+
+- `FlatData.RewardExcel` (2 fields)
+
+## Removed tables (1)
+
+- `FlatData.Nested.OldExcel` (1 field)
+
+## New fields (1)
+
+- `FlatData.CharacterExcel.Scale`: `float = 1.5`
+
+## Enum additions (1)
+
+- `FlatData.TacticRole`: `Tanker = 2`
+
+## Type changes (1)
+
+- `FlatData.ScenarioExcel.FrameHash`: `long` → `ulong`
+"
+    );
+}
@@ -0,0 +1,218 @@
+//! Tests for `bafb schema export`.
+
+mod common;
+
+use common::{bafb, fixture, printed, scratch};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+
+fn export(dir: &Path, format: &str, output: &str) -> String {
+    let fixture = fixture();
+    let result = bafb(
+        dir,
+        &[
+            "schema",
+            "export",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--format",
+            format,
+            "--output",
+            output,
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "export failed:\n{}",
+        printed(&result)
+    );
+    fs::read_to_string(dir.join(output)).unwrap()
+}
+
+#[test]
+fn exports_json_schema() {
+    let dir = scratch("export-jsonschema");
+    let schema: Value = serde_json::from_str(&export(&dir, "jsonschema", "excel.json")).unwrap();
+
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(schema["$ref"], "#/$defs/FlatData.CharacterExcelTable");
+
+    let definitions = &schema["$defs"];
+    assert_eq!(
+        definitions["FlatData.TacticRole"],
+        json!({
+            "type": "string",
+            "enum": ["None", "DamageDealer", "Tanker", "Supporter"],
+            "description": "Where a character shows up",
+        })
+    );
+
+    let character = &definitions["FlatData.CharacterExcel"];
+    assert_eq!(
+        character["properties"]["Rarity"],
+        json!({ "$ref": "#/$defs/FlatData.Rarity" })
+    );
+    assert_eq!(
+        character["properties"]["ProductionStep"]["default"],
+        "Release"
+    );
+    assert_eq!(
+        character["properties"]["Optional"],
+        json!({ "type": "integer", "minimum": 0, "maximum": 4294967295u32 })
+    );
+    assert!(character["properties"].get("OldTag").is_none());
+    assert_eq!(
+        character["required"],
+        json!([
+            "Id",
+            "CostumeGroupId",
+            "IsPlayable",
+            "ProductionStep",
+            "Rarity",
+            "TacticRole",
+            "Scale"
+        ])
+    );
+
+    let position = &definitions["FlatData.Position"];
+    assert_eq!(
+        position["properties"]["layer"],
+        json!({ "type": "integer", "minimum": -128, "maximum": 127 })
+    );
+    assert_eq!(position["required"], json!(["x", "y", "layer"]));
+    assert_eq!(
+        definitions["FlatData.ScenarioExcel"]["properties"]["FrameHash"]["maximum"],
+        json!(u64::MAX)
+    );
+
+    let reward = &definitions["FlatData.RewardExcel"]["properties"];
+    assert_eq!(
+        reward["Reward_type"],
+        json!({ "$ref": "#/$defs/FlatData.Reward" })
+    );
+    assert_eq!(
+        reward["Reward"]["anyOf"],
+        json!([
+            { "$ref": "#/$defs/FlatData.CharacterExcel" },
+            { "$ref": "#/$defs/FlatData.ScenarioExcel" },
+        ])
+    );
+    assert_eq!(
+        definitions["FlatData.Reward"]["enum"],
+        json!(["NONE", "CharacterExcel", "ScenarioExcel"])
+    );
+}
+
+#[test]
+fn exports_proto() {
+    let dir = scratch("export-proto");
+    let proto = export(&dir, "proto", "excel.proto");
+
+    assert!(proto.contains("syntax = \"proto3\";\n\npackage FlatData;\n"));
+    assert!(proto.contains(
+        "message CharacterExcel {\n  reserved 8;\n  reserved \"OldTag\";\n  int64 Id = 1;\n"
+    ));
+    assert!(proto.contains("  optional uint32 Optional = 13;\n"));
+    assert!(proto.contains("  repeated string Tags = 9;\n"));
+    assert!(proto.contains("message Nested_GroupExcel {\n"));
+    assert!(proto.contains(
+        "message Reward {\n  oneof value {\n    CharacterExcel CharacterExcel = 1;\n    ScenarioExcel ScenarioExcel = 2;\n  }\n}\n"
+    ));
+    assert!(
+        proto.contains("message RewardExcel {\n  Reward Reward = 2;\n  int32 Amount = 3;\n}\n")
+    );
+    assert!(proto.contains("enum TacticRole {\n  TACTIC_ROLE_NONE = 0;\n"));
+
+    // Enums without a zero get one, combinable and 64 bit enums are written as numbers
+    let fbs = dir.join("odd.fbs");
+    fs::write(
+        &fbs,
+        "enum Grade : int { A = 1, B = 2, Best = 2 }\n\
+         enum Flags : uint (bit_flags) { One, Two }\n\
+         enum Big : long { Small = 1, Large = 5000000000 }\n\
+         table Item { grade: Grade = A; flags: Flags; size: Big = Small; }\n\
+         root_type Item;\n",
+    )
+    .unwrap();
+    let result = bafb(
+        &dir,
+        &["schema", "export", "--fbs", "odd.fbs", "--format", "proto"],
+    );
+    assert!(
+        result.status.success(),
+        "export failed:\n{}",
+        printed(&result)
+    );
+    let proto = fs::read_to_string(dir.join("odd.proto")).unwrap();
+    assert!(proto.contains(
+        "enum Grade {\n  option allow_alias = true;\n  GRADE_UNSPECIFIED = 0;\n  GRADE_A = 1;\n  GRADE_B = 2;\n  GRADE_BEST = 2;\n}\n"
+    ));
+    assert!(proto.contains(
+        "message Item {\n  Grade grade = 1;\n  uint32 flags = 2;\n  int64 size = 3;\n}\n"
+    ));
+    assert!(!proto.contains("enum Flags"));
+    assert!(!proto.contains("package"));
+}
+
+#[test]
+fn numbers_bit_flags_from_the_previous_bit() {
+    let dir = scratch("export-bit-flags");
+    fs::write(
+        dir.join("flags.fbs"),
+        "enum Flags : ubyte (bit_flags) { A, B = 3, C }\n\
+         enum Wide : ulong (bit_flags) { Low, High = 62, Top }\n\
+         table Item { flags: Flags = C; wide: Wide = High; }\n\
+         root_type Item;\n",
+    )
+    .unwrap();
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "export",
+            "--fbs",
+            "flags.fbs",
+            "--format",
+            "jsonschema",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "export failed:\n{}",
+        printed(&result)
+    );
+
+    // C follows B = 3, Top after High = 62 is bit 63 which a ulong still holds
+    let schema: Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("flags.schema.json")).unwrap()).unwrap();
+    let properties = &schema["$defs"]["Item"]["properties"];
+    assert_eq!(properties["flags"]["default"], json!(1 << 4));
+    assert_eq!(properties["wide"]["default"], json!(1u64 << 62));
+
+    fs::write(
+        dir.join("over.fbs"),
+        "enum Over : ulong (bit_flags) { A = 64 }\n",
+    )
+    .unwrap();
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "export",
+            "--fbs",
+            "over.fbs",
+            "--format",
+            "jsonschema",
+        ],
+    );
+    assert!(!result.status.success());
+    assert!(
+        printed(&result).contains("bit flag Over.A is out of range"),
+        "{}",
+        printed(&result)
+    );
+}
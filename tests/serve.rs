@@ -0,0 +1,261 @@
+//! Tests for `bafb serve` and the GraphQL export it answers queries with.
+
+mod common;
+
+use common::{bafb, command, fixture, printed, scratch};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Child, ChildStdout, Stdio};
+
+const SKILLS: &str = r#"
+namespace FlatData;
+
+table SkillExcel {
+    Id: long (key);
+    Name: string;
+}
+
+table CharacterExcel {
+    Id: long (key);
+    SkillId: long;
+    SkillIds: [long];
+    BuffId: long;
+}
+"#;
+
+/// A running `bafb serve`, killed when dropped
+struct Served {
+    child: Child,
+    // Kept open so the server can still write to it
+    _stdout: BufReader<ChildStdout>,
+    url: String,
+}
+
+impl Served {
+    fn start(dir: &Path, fbs: &Path) -> Self {
+        let mut child = command(
+            dir,
+            &[
+                "serve",
+                "--fbs",
+                fbs.to_str().unwrap(),
+                "--data",
+                "decoded",
+                "--port",
+                "0",
+            ],
+        )
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let url = loop {
+            let mut line = String::new();
+            if stdout.read_line(&mut line).unwrap() == 0 {
+                let _ = child.kill();
+                panic!("bafb serve exited before serving");
+            }
+            if let Some((_, url)) = line.trim().split_once(" on ") {
+                break url.to_string();
+            }
+        };
+
+        Self {
+            child,
+            _stdout: stdout,
+            url,
+        }
+    }
+
+    async fn get(&self, path: &str) -> (u16, Value) {
+        let response = reqwest::get(format!("{}{}", self.url, path)).await.unwrap();
+        let status = response.status().as_u16();
+        (
+            status,
+            serde_json::from_str(&response.text().await.unwrap()).unwrap(),
+        )
+    }
+
+    async fn query(&self, query: &str, variables: Value) -> Value {
+        let response = reqwest::Client::new()
+            .post(format!("{}/graphql", self.url))
+            .header("content-type", "application/json")
+            .body(json!({ "query": query, "variables": variables }).to_string())
+            .send()
+            .await
+            .unwrap();
+        serde_json::from_str(&response.text().await.unwrap()).unwrap()
+    }
+}
+
+impl Drop for Served {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn exports_graphql_schema() {
+    let dir = scratch("export-graphql");
+    let fixture = fixture();
+    let result = bafb(
+        &dir,
+        &[
+            "schema",
+            "export",
+            "--fbs",
+            fixture.to_str().unwrap(),
+            "--format",
+            "graphql",
+            "--output",
+            "excel.graphql",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "export failed:\n{}",
+        printed(&result)
+    );
+    let sdl = fs::read_to_string(dir.join("excel.graphql")).unwrap();
+
+    assert!(sdl.contains(
+        "type CharacterExcel {\n  Id: Long!\n  DevName: String\n  CostumeGroupId: Long!\n  IsPlayable: Boolean!\n  ProductionStep: ProductionStep!\n  Rarity: Rarity!\n  TacticRole: TacticRole!\n  Tags: [String!]\n  StatLevelUpType: [Int!]\n  Spawn: Position\n  Scale: Float!\n  Optional: Long\n}\n"
+    ));
+    assert!(sdl.contains("\"Where a character shows up\"\nenum TacticRole {\n"));
+    assert!(sdl.contains("union Reward = CharacterExcel | ScenarioExcel\n"));
+    assert!(sdl.contains("  Bounds: [Bounds!]!\n"));
+    assert!(sdl.contains("  characterExcel(Id: Long!): CharacterExcel\n"));
+    assert!(sdl.contains("  nested_GroupExcel(Id: Int!): Nested_GroupExcel\n"));
+    assert!(sdl.contains("  scenarioExcelList(offset: Int! = 0, limit: Int): [ScenarioExcel!]!\n"));
+    // Rows without a key can only be listed
+    assert!(!sdl.contains("scenarioExcel("));
+}
+
+#[tokio::test]
+async fn serves_decoded_tables() {
+    let dir = scratch("serve");
+    let data = dir.join("decoded");
+    fs::create_dir_all(&data).unwrap();
+    fs::write(
+        data.join("CharacterExcelTable.json"),
+        r#"{"DataList":[
+            {"Id":10010,"DevName":"Shiroko","CostumeGroupId":0,"IsPlayable":true,"ProductionStep":"Doing","Rarity":"N","TacticRole":"Supporter","Tags":["tag"],"Spawn":{"x":1.0,"y":2.0,"layer":3},"Scale":1.5},
+            {"Id":10011,"CostumeGroupId":0,"IsPlayable":false,"ProductionStep":"Release","Rarity":3,"TacticRole":"None","Scale":1.5}
+        ]}"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("ScenarioExcel.json"),
+        r#"[{"FrameHash":18446744073709551615,"Bounds":[],"Name":"s"}]"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("RewardExcel.json"),
+        r#"[{"Reward_type":"ScenarioExcel","Reward":{"FrameHash":1,"Bounds":[],"Name":"t"},"Amount":3},{"Reward_type":"NONE","Amount":-5}]"#,
+    )
+    .unwrap();
+
+    let served = Served::start(&dir, &fixture());
+
+    let (status, tables) = served.get("/").await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        tables["tables"][0],
+        json!({ "name": "CharacterExcel", "key": "Id", "rows": 2 })
+    );
+
+    let (status, row) = served.get("/tables/characterexcel/10010").await;
+    assert_eq!(status, 200);
+    assert_eq!(row["DevName"], "Shiroko");
+    let (status, error) = served.get("/tables/CharacterExcel/1").await;
+    assert_eq!(status, 404);
+    assert_eq!(error["error"], "CharacterExcel has no row 1");
+    let (_, rows) = served.get("/tables/RewardExcel?offset=1").await;
+    assert_eq!(rows, json!([{ "Reward_type": "NONE", "Amount": -5 }]));
+
+    let response = served
+        .query(
+            "query($id: Long!) { characterExcel(Id: $id) { DevName Rarity TacticRole Spawn { layer } } }",
+            json!({ "id": 10011 }),
+        )
+        .await;
+    // Numbers of enum values are answered with their names
+    assert_eq!(
+        response,
+        json!({ "data": { "characterExcel": {
+            "DevName": null, "Rarity": "SR", "TacticRole": "None", "Spawn": null
+        } } })
+    );
+
+    let response = served
+        .query(
+            "{ rewardExcelList { Amount Reward { __typename ... on ScenarioExcel { Name } } } \
+             scenarioExcelList(limit: 1) { FrameHash } }",
+            json!({}),
+        )
+        .await;
+    assert_eq!(
+        response,
+        json!({ "data": {
+            "rewardExcelList": [
+                { "Amount": 3, "Reward": { "__typename": "ScenarioExcel", "Name": "t" } },
+                { "Amount": -5, "Reward": null },
+            ],
+            "scenarioExcelList": [{ "FrameHash": 18446744073709551615u64 }],
+        } })
+    );
+
+    let (status, _) = served.get("/graphql?query=%7Btables%7D").await;
+    assert_eq!(status, 200);
+}
+
+#[tokio::test]
+async fn serves_relationships_between_tables() {
+    let dir = scratch("serve-relationships");
+    fs::write(dir.join("skills.fbs"), SKILLS).unwrap();
+    let data = dir.join("decoded");
+    fs::create_dir_all(&data).unwrap();
+    fs::write(
+        data.join("SkillExcel.json"),
+        r#"[{"Id":1,"Name":"Strike"},{"Id":2,"Name":"Guard"}]"#,
+    )
+    .unwrap();
+    fs::write(
+        data.join("CharacterExcel.json"),
+        r#"[{"Id":7,"SkillId":2,"SkillIds":[1,3,2],"BuffId":1}]"#,
+    )
+    .unwrap();
+
+    let served = Served::start(&dir, &dir.join("skills.fbs"));
+
+    let sdl = reqwest::get(format!("{}/schema.graphql", served.url))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(sdl.contains("  Skill: SkillExcel\n"));
+    assert!(sdl.contains("  Skills: [SkillExcel!]\n"));
+    // There is no BuffExcel to point to
+    assert!(!sdl.contains("Buff:"));
+
+    let response = served
+        .query(
+            "{ characterExcel(Id: 7) { Skill { Name } Skills { Id } } }",
+            json!({}),
+        )
+        .await;
+    // Ids without a row are left out
+    assert_eq!(
+        response,
+        json!({ "data": { "characterExcel": {
+            "Skill": { "Name": "Guard" },
+            "Skills": [{ "Id": 1 }, { "Id": 2 }],
+        } } })
+    );
+}
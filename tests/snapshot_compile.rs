@@ -0,0 +1,85 @@
+//! Builds the `tests/snapshots/excel` bindings against the `flatbuffers` crate.
+//!
+//! Keep the dev-dependency on the version in `FLATBUFFERS_VERSION`, the generated code targets it.
+
+#[allow(
+    unused_imports,
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals,
+    mismatched_lifetime_syntaxes,
+    clippy::all
+)]
+#[rustfmt::skip]
+#[path = "snapshots/excel/mod.rs"]
+mod excel;
+
+use excel::flat_data::nested::GroupExcelT;
+use excel::flat_data::*;
+
+fn character(id: i64, name: &str) -> CharacterExcelT {
+    CharacterExcelT {
+        Id: id,
+        DevName: Some(name.to_string()),
+        Rarity: Rarity::SR,
+        TacticRole: TacticRole::Supporter,
+        Tags: Some(vec!["front".to_string(), "support".to_string()]),
+        Spawn: Some(PositionT {
+            x: 1.0,
+            y: -2.5,
+            layer: 3,
+        }),
+        Optional: Some(7),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn round_trips_tables_through_the_object_api() {
+    let table = CharacterExcelTableT {
+        DataList: Some(vec![character(10, "Shiroko"), character(20, "Hoshino")]),
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let root = table.pack(&mut builder);
+    finish_character_excel_table_buffer(&mut builder, root);
+
+    let decoded = root_as_character_excel_table(builder.finished_data()).unwrap();
+    let rows = decoded.DataList().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows.get(1).DevName(), Some("Hoshino"));
+    assert_eq!(rows.get(0).TacticRole(), TacticRole::Supporter);
+    // Defaults from the schema apply to fields that were not set
+    assert!(rows.get(0).IsPlayable());
+    assert_eq!(rows.get(0).ProductionStep(), ProductionStep::Release);
+    assert_eq!(rows.get(0).Scale(), 1.5);
+
+    let unpacked = decoded.unpack();
+    let first = &unpacked.DataList.as_ref().unwrap()[0];
+    assert_eq!(first.Id, 10);
+    assert_eq!(first.Spawn.as_ref().unwrap().y, -2.5);
+    assert_eq!(first.Optional, Some(7));
+}
+
+#[test]
+fn serializes_tables_with_serde() {
+    let group = GroupExcelT {
+        Id: 3,
+        Members: Some(vec![character(10, "Shiroko")]),
+        Name: Some("Abydos".to_string()),
+        ..Default::default()
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let root = group.pack(&mut builder);
+    builder.finish(root, None);
+
+    let decoded = flatbuffers::root::<nested::GroupExcel>(builder.finished_data()).unwrap();
+    let json = serde_json::to_value(decoded).unwrap();
+    assert_eq!(json["Id"], 3);
+    assert_eq!(json["Step"], "Doing");
+    assert_eq!(json["Name"], "Abydos");
+    assert_eq!(json["Members"][0]["Rarity"], "SR");
+    assert_eq!(json["Members"][0]["Tags"][1], "support");
+}
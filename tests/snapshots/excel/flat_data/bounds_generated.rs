@@ -0,0 +1,192 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+// struct Bounds, aligned to 8
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Bounds(pub [u8; 32]);
+impl Default for Bounds {
+  fn default() -> Self {
+    Self([0; 32])
+  }
+}
+impl ::core::fmt::Debug for Bounds {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    f.debug_struct("Bounds")
+      .field("origin", &self.origin())
+      .field("size", &self.size())
+      .field("visible", &self.visible())
+      .finish()
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Bounds {}
+impl<'a> flatbuffers::Follow<'a> for Bounds {
+  type Inner = &'a Bounds;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    unsafe { <&'a Bounds>::follow(buf, loc) }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a Bounds {
+  type Inner = &'a Bounds;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    unsafe { flatbuffers::follow_cast_ref::<Bounds>(buf, loc) }
+  }
+}
+impl<'b> flatbuffers::Push for Bounds {
+    type Output = Bounds;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = unsafe { ::core::slice::from_raw_parts(self as *const Bounds as *const u8, <Self as flatbuffers::Push>::size()) };
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn alignment() -> flatbuffers::PushAlignment {
+        flatbuffers::PushAlignment::new(8)
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Bounds {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+impl Serialize for Bounds {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("Bounds", 3)?;
+      s.serialize_field("origin", &self.origin())?;
+      s.serialize_field("size", &self.size())?;
+      s.serialize_field("visible", &self.visible())?;
+    s.end()
+  }
+}
+
+impl<'a> Bounds {
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    origin: &Position,
+    size: f64,
+    visible: bool,
+  ) -> Self {
+    let mut s = Self([0; 32]);
+    s.set_origin(origin);
+    s.set_size(size);
+    s.set_visible(visible);
+    s
+  }
+
+  pub fn origin(&self) -> &Position {
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid struct in this slot
+    unsafe { &*(self.0[0..].as_ptr() as *const Position) }
+  }
+
+  #[allow(clippy::identity_op)]
+  pub fn set_origin(&mut self, x: &Position) {
+    self.0[0..0 + 12].copy_from_slice(&x.0)
+  }
+
+  pub fn size(&self) -> f64 {
+    let mut mem = core::mem::MaybeUninit::<<f64 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[16..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f64 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_size(&mut self, x: f64) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[16..].as_mut_ptr(),
+        core::mem::size_of::<<f64 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn visible(&self) -> bool {
+    let mut mem = core::mem::MaybeUninit::<<bool as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[24..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<bool as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_visible(&mut self, x: bool) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[24..].as_mut_ptr(),
+        core::mem::size_of::<<bool as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn unpack(&self) -> BoundsT {
+    BoundsT {
+      origin: self.origin().unpack(),
+      size: self.size(),
+      visible: self.visible(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BoundsT {
+  pub origin: PositionT,
+  pub size: f64,
+  pub visible: bool,
+}
+impl BoundsT {
+  pub fn pack(&self) -> Bounds {
+    Bounds::new(
+      &self.origin.pack(),
+      self.size,
+      self.visible,
+    )
+  }
+}
+
@@ -0,0 +1,459 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+pub enum CharacterExcelOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CharacterExcel<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CharacterExcel<'a> {
+  type Inner = CharacterExcel<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> CharacterExcel<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_DEVNAME: flatbuffers::VOffsetT = 6;
+  pub const VT_COSTUMEGROUPID: flatbuffers::VOffsetT = 8;
+  pub const VT_ISPLAYABLE: flatbuffers::VOffsetT = 10;
+  pub const VT_PRODUCTIONSTEP: flatbuffers::VOffsetT = 12;
+  pub const VT_RARITY: flatbuffers::VOffsetT = 14;
+  pub const VT_TACTICROLE: flatbuffers::VOffsetT = 16;
+  pub const VT_TAGS: flatbuffers::VOffsetT = 20;
+  pub const VT_STATLEVELUPTYPE: flatbuffers::VOffsetT = 22;
+  pub const VT_SPAWN: flatbuffers::VOffsetT = 24;
+  pub const VT_SCALE: flatbuffers::VOffsetT = 26;
+  pub const VT_OPTIONAL: flatbuffers::VOffsetT = 28;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CharacterExcel { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CharacterExcelArgs<'args>
+  ) -> flatbuffers::WIPOffset<CharacterExcel<'bldr>> {
+    let mut builder = CharacterExcelBuilder::new(_fbb);
+    builder.add_TacticRole(args.TacticRole);
+    builder.add_CostumeGroupId(args.CostumeGroupId);
+    builder.add_Id(args.Id);
+    if let Some(x) = args.Optional { builder.add_Optional(x); }
+    builder.add_Scale(args.Scale);
+    if let Some(x) = args.Spawn { builder.add_Spawn(x); }
+    if let Some(x) = args.StatLevelUpType { builder.add_StatLevelUpType(x); }
+    if let Some(x) = args.Tags { builder.add_Tags(x); }
+    builder.add_ProductionStep(args.ProductionStep);
+    if let Some(x) = args.DevName { builder.add_DevName(x); }
+    builder.add_Rarity(args.Rarity);
+    builder.add_IsPlayable(args.IsPlayable);
+    builder.finish()
+  }
+
+  pub fn unpack(&self) -> CharacterExcelT {
+    let Id = self.Id();
+    let DevName = self.DevName().map(|x| {
+      x.to_string()
+    });
+    let CostumeGroupId = self.CostumeGroupId();
+    let IsPlayable = self.IsPlayable();
+    let ProductionStep_ = self.ProductionStep();
+    let Rarity_ = self.Rarity();
+    let TacticRole_ = self.TacticRole();
+    let Tags = self.Tags().map(|x| {
+      x.iter().map(|s| s.to_string()).collect()
+    });
+    let StatLevelUpType = self.StatLevelUpType().map(|x| {
+      x.into_iter().collect()
+    });
+    let Spawn = self.Spawn().map(|x| {
+      x.unpack()
+    });
+    let Scale = self.Scale();
+    let Optional = self.Optional();
+    CharacterExcelT {
+      Id,
+      DevName,
+      CostumeGroupId,
+      IsPlayable,
+      ProductionStep: ProductionStep_,
+      Rarity: Rarity_,
+      TacticRole: TacticRole_,
+      Tags,
+      StatLevelUpType,
+      Spawn,
+      Scale,
+      Optional,
+    }
+  }
+
+  #[inline]
+  pub fn key_compare_less_than(&self, o: &CharacterExcel) -> bool {
+    self.Id() < o.Id()
+  }
+
+  #[inline]
+  pub fn key_compare_with_value(&self, val: i64) -> ::core::cmp::Ordering {
+    let key = self.Id();
+    key.cmp(&val)
+  }
+  #[inline]
+  pub fn Id(&self) -> i64 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(CharacterExcel::VT_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn DevName(&self) -> Option<&'a str> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CharacterExcel::VT_DEVNAME, None)}
+  }
+  #[inline]
+  pub fn CostumeGroupId(&self) -> i64 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(CharacterExcel::VT_COSTUMEGROUPID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn IsPlayable(&self) -> bool {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(CharacterExcel::VT_ISPLAYABLE, Some(true)).unwrap()}
+  }
+  #[inline]
+  pub fn ProductionStep(&self) -> ProductionStep {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ProductionStep>(CharacterExcel::VT_PRODUCTIONSTEP, Some(ProductionStep::Release)).unwrap()}
+  }
+  #[inline]
+  pub fn Rarity(&self) -> Rarity {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Rarity>(CharacterExcel::VT_RARITY, Some(Rarity::N)).unwrap()}
+  }
+  #[inline]
+  pub fn TacticRole(&self) -> TacticRole {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<TacticRole>(CharacterExcel::VT_TACTICROLE, Some(TacticRole::None)).unwrap()}
+  }
+  #[inline]
+  pub fn Tags(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(CharacterExcel::VT_TAGS, None)}
+  }
+  #[inline]
+  pub fn StatLevelUpType(&self) -> Option<flatbuffers::Vector<'a, i32>> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, i32>>>(CharacterExcel::VT_STATLEVELUPTYPE, None)}
+  }
+  #[inline]
+  pub fn Spawn(&self) -> Option<&'a Position> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Position>(CharacterExcel::VT_SPAWN, None)}
+  }
+  #[inline]
+  pub fn Scale(&self) -> f32 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(CharacterExcel::VT_SCALE, Some(1.5)).unwrap()}
+  }
+  #[inline]
+  pub fn Optional(&self) -> Option<u32> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(CharacterExcel::VT_OPTIONAL, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CharacterExcel<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<i64>("Id", Self::VT_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("DevName", Self::VT_DEVNAME, false)?
+     .visit_field::<i64>("CostumeGroupId", Self::VT_COSTUMEGROUPID, false)?
+     .visit_field::<bool>("IsPlayable", Self::VT_ISPLAYABLE, false)?
+     .visit_field::<ProductionStep>("ProductionStep", Self::VT_PRODUCTIONSTEP, false)?
+     .visit_field::<Rarity>("Rarity", Self::VT_RARITY, false)?
+     .visit_field::<TacticRole>("TacticRole", Self::VT_TACTICROLE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("Tags", Self::VT_TAGS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, i32>>>("StatLevelUpType", Self::VT_STATLEVELUPTYPE, false)?
+     .visit_field::<Position>("Spawn", Self::VT_SPAWN, false)?
+     .visit_field::<f32>("Scale", Self::VT_SCALE, false)?
+     .visit_field::<u32>("Optional", Self::VT_OPTIONAL, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CharacterExcelArgs<'a> {
+    pub Id: i64,
+    pub DevName: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub CostumeGroupId: i64,
+    pub IsPlayable: bool,
+    pub ProductionStep: ProductionStep,
+    pub Rarity: Rarity,
+    pub TacticRole: TacticRole,
+    pub Tags: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub StatLevelUpType: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, i32>>>,
+    pub Spawn: Option<&'a Position>,
+    pub Scale: f32,
+    pub Optional: Option<u32>,
+}
+impl<'a> Default for CharacterExcelArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CharacterExcelArgs {
+      Id: 0,
+      DevName: None,
+      CostumeGroupId: 0,
+      IsPlayable: true,
+      ProductionStep: ProductionStep::Release,
+      Rarity: Rarity::N,
+      TacticRole: TacticRole::None,
+      Tags: None,
+      StatLevelUpType: None,
+      Spawn: None,
+      Scale: 1.5,
+      Optional: None,
+    }
+  }
+}
+
+impl Serialize for CharacterExcel<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("CharacterExcel", 12)?;
+      s.serialize_field("Id", &self.Id())?;
+      if let Some(f) = self.DevName() {
+        s.serialize_field("DevName", &f)?;
+      } else {
+        s.skip_field("DevName")?;
+      }
+      s.serialize_field("CostumeGroupId", &self.CostumeGroupId())?;
+      s.serialize_field("IsPlayable", &self.IsPlayable())?;
+      s.serialize_field("ProductionStep", &self.ProductionStep())?;
+      s.serialize_field("Rarity", &self.Rarity())?;
+      s.serialize_field("TacticRole", &self.TacticRole())?;
+      if let Some(f) = self.Tags() {
+        s.serialize_field("Tags", &f)?;
+      } else {
+        s.skip_field("Tags")?;
+      }
+      if let Some(f) = self.StatLevelUpType() {
+        s.serialize_field("StatLevelUpType", &f)?;
+      } else {
+        s.skip_field("StatLevelUpType")?;
+      }
+      if let Some(f) = self.Spawn() {
+        s.serialize_field("Spawn", &f)?;
+      } else {
+        s.skip_field("Spawn")?;
+      }
+      s.serialize_field("Scale", &self.Scale())?;
+      if let Some(f) = self.Optional() {
+        s.serialize_field("Optional", &f)?;
+      } else {
+        s.skip_field("Optional")?;
+      }
+    s.end()
+  }
+}
+
+pub struct CharacterExcelBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CharacterExcelBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_Id(&mut self, Id: i64) {
+    self.fbb_.push_slot::<i64>(CharacterExcel::VT_ID, Id, 0);
+  }
+  #[inline]
+  pub fn add_DevName(&mut self, DevName: flatbuffers::WIPOffset<&'b str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CharacterExcel::VT_DEVNAME, DevName);
+  }
+  #[inline]
+  pub fn add_CostumeGroupId(&mut self, CostumeGroupId: i64) {
+    self.fbb_.push_slot::<i64>(CharacterExcel::VT_COSTUMEGROUPID, CostumeGroupId, 0);
+  }
+  #[inline]
+  pub fn add_IsPlayable(&mut self, IsPlayable: bool) {
+    self.fbb_.push_slot::<bool>(CharacterExcel::VT_ISPLAYABLE, IsPlayable, true);
+  }
+  #[inline]
+  pub fn add_ProductionStep(&mut self, ProductionStep_: ProductionStep) {
+    self.fbb_.push_slot::<ProductionStep>(CharacterExcel::VT_PRODUCTIONSTEP, ProductionStep_, ProductionStep::Release);
+  }
+  #[inline]
+  pub fn add_Rarity(&mut self, Rarity_: Rarity) {
+    self.fbb_.push_slot::<Rarity>(CharacterExcel::VT_RARITY, Rarity_, Rarity::N);
+  }
+  #[inline]
+  pub fn add_TacticRole(&mut self, TacticRole_: TacticRole) {
+    self.fbb_.push_slot::<TacticRole>(CharacterExcel::VT_TACTICROLE, TacticRole_, TacticRole::None);
+  }
+  #[inline]
+  pub fn add_Tags(&mut self, Tags: flatbuffers::WIPOffset<flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CharacterExcel::VT_TAGS, Tags);
+  }
+  #[inline]
+  pub fn add_StatLevelUpType(&mut self, StatLevelUpType: flatbuffers::WIPOffset<flatbuffers::Vector<'b, i32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CharacterExcel::VT_STATLEVELUPTYPE, StatLevelUpType);
+  }
+  #[inline]
+  pub fn add_Spawn(&mut self, Spawn: &Position) {
+    self.fbb_.push_slot_always::<&Position>(CharacterExcel::VT_SPAWN, Spawn);
+  }
+  #[inline]
+  pub fn add_Scale(&mut self, Scale: f32) {
+    self.fbb_.push_slot::<f32>(CharacterExcel::VT_SCALE, Scale, 1.5);
+  }
+  #[inline]
+  pub fn add_Optional(&mut self, Optional: u32) {
+    self.fbb_.push_slot_always::<u32>(CharacterExcel::VT_OPTIONAL, Optional);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CharacterExcelBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CharacterExcelBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CharacterExcel<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl ::core::fmt::Debug for CharacterExcel<'_> {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let mut ds = f.debug_struct("CharacterExcel");
+      ds.field("Id", &self.Id());
+      ds.field("DevName", &self.DevName());
+      ds.field("CostumeGroupId", &self.CostumeGroupId());
+      ds.field("IsPlayable", &self.IsPlayable());
+      ds.field("ProductionStep", &self.ProductionStep());
+      ds.field("Rarity", &self.Rarity());
+      ds.field("TacticRole", &self.TacticRole());
+      ds.field("Tags", &self.Tags());
+      ds.field("StatLevelUpType", &self.StatLevelUpType());
+      ds.field("Spawn", &self.Spawn());
+      ds.field("Scale", &self.Scale());
+      ds.field("Optional", &self.Optional());
+      ds.finish()
+  }
+}
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterExcelT {
+  pub Id: i64,
+  pub DevName: Option<String>,
+  pub CostumeGroupId: i64,
+  pub IsPlayable: bool,
+  pub ProductionStep: ProductionStep,
+  pub Rarity: Rarity,
+  pub TacticRole: TacticRole,
+  pub Tags: Option<Vec<String>>,
+  pub StatLevelUpType: Option<Vec<i32>>,
+  pub Spawn: Option<PositionT>,
+  pub Scale: f32,
+  pub Optional: Option<u32>,
+}
+impl Default for CharacterExcelT {
+  fn default() -> Self {
+    Self {
+      Id: 0,
+      DevName: None,
+      CostumeGroupId: 0,
+      IsPlayable: true,
+      ProductionStep: ProductionStep::Release,
+      Rarity: Rarity::N,
+      TacticRole: TacticRole::None,
+      Tags: None,
+      StatLevelUpType: None,
+      Spawn: None,
+      Scale: 1.5,
+      Optional: None,
+    }
+  }
+}
+impl CharacterExcelT {
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(
+    &self,
+    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>
+  ) -> flatbuffers::WIPOffset<CharacterExcel<'b>> {
+    let Id = self.Id;
+    let DevName = self.DevName.as_ref().map(|x|{
+      _fbb.create_string(x)
+    });
+    let CostumeGroupId = self.CostumeGroupId;
+    let IsPlayable = self.IsPlayable;
+    let ProductionStep_ = self.ProductionStep;
+    let Rarity_ = self.Rarity;
+    let TacticRole_ = self.TacticRole;
+    let Tags = self.Tags.as_ref().map(|x|{
+      let w: Vec<_> = x.iter().map(|s| _fbb.create_string(s)).collect();_fbb.create_vector(&w)
+    });
+    let StatLevelUpType = self.StatLevelUpType.as_ref().map(|x|{
+      _fbb.create_vector(x)
+    });
+    let Spawn_tmp = self.Spawn.as_ref().map(|x| x.pack());
+    let Spawn = Spawn_tmp.as_ref();
+    let Scale = self.Scale;
+    let Optional = self.Optional;
+    CharacterExcel::create(_fbb, &CharacterExcelArgs{
+      Id,
+      DevName,
+      CostumeGroupId,
+      IsPlayable,
+      ProductionStep: ProductionStep_,
+      Rarity: Rarity_,
+      TacticRole: TacticRole_,
+      Tags,
+      StatLevelUpType,
+      Spawn,
+      Scale,
+      Optional,
+    })
+  }
+}
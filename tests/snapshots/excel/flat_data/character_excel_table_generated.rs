@@ -0,0 +1,229 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+pub enum CharacterExcelTableOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CharacterExcelTable<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CharacterExcelTable<'a> {
+  type Inner = CharacterExcelTable<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> CharacterExcelTable<'a> {
+  pub const VT_DATALIST: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CharacterExcelTable { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CharacterExcelTableArgs<'args>
+  ) -> flatbuffers::WIPOffset<CharacterExcelTable<'bldr>> {
+    let mut builder = CharacterExcelTableBuilder::new(_fbb);
+    if let Some(x) = args.DataList { builder.add_DataList(x); }
+    builder.finish()
+  }
+
+  pub fn unpack(&self) -> CharacterExcelTableT {
+    let DataList = self.DataList().map(|x| {
+      x.iter().map(|t| t.unpack()).collect()
+    });
+    CharacterExcelTableT {
+      DataList,
+    }
+  }
+
+  #[inline]
+  pub fn DataList(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CharacterExcel<'a>>>> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CharacterExcel<'a>>>>>(CharacterExcelTable::VT_DATALIST, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CharacterExcelTable<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CharacterExcel>>>>("DataList", Self::VT_DATALIST, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CharacterExcelTableArgs<'a> {
+    pub DataList: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CharacterExcel<'a>>>>>,
+}
+impl<'a> Default for CharacterExcelTableArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CharacterExcelTableArgs {
+      DataList: None,
+    }
+  }
+}
+
+impl Serialize for CharacterExcelTable<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("CharacterExcelTable", 1)?;
+      if let Some(f) = self.DataList() {
+        s.serialize_field("DataList", &f)?;
+      } else {
+        s.skip_field("DataList")?;
+      }
+    s.end()
+  }
+}
+
+pub struct CharacterExcelTableBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CharacterExcelTableBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_DataList(&mut self, DataList: flatbuffers::WIPOffset<flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<CharacterExcel<'b>>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CharacterExcelTable::VT_DATALIST, DataList);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CharacterExcelTableBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CharacterExcelTableBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CharacterExcelTable<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl ::core::fmt::Debug for CharacterExcelTable<'_> {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let mut ds = f.debug_struct("CharacterExcelTable");
+      ds.field("DataList", &self.DataList());
+      ds.finish()
+  }
+}
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterExcelTableT {
+  pub DataList: Option<Vec<CharacterExcelT>>,
+}
+impl Default for CharacterExcelTableT {
+  fn default() -> Self {
+    Self {
+      DataList: None,
+    }
+  }
+}
+impl CharacterExcelTableT {
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(
+    &self,
+    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>
+  ) -> flatbuffers::WIPOffset<CharacterExcelTable<'b>> {
+    let DataList = self.DataList.as_ref().map(|x|{
+      let w: Vec<_> = x.iter().map(|t| t.pack(_fbb)).collect();_fbb.create_vector(&w)
+    });
+    CharacterExcelTable::create(_fbb, &CharacterExcelTableArgs{
+      DataList,
+    })
+  }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `CharacterExcelTable`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_character_excel_table_unchecked`.
+pub fn root_as_character_excel_table(buf: &[u8]) -> Result<CharacterExcelTable<'_>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<CharacterExcelTable>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `CharacterExcelTable` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_character_excel_table_unchecked`.
+pub fn size_prefixed_root_as_character_excel_table(buf: &[u8]) -> Result<CharacterExcelTable<'_>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<CharacterExcelTable>(buf)
+}
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `CharacterExcelTable` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_character_excel_table_unchecked`.
+pub fn root_as_character_excel_table_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<CharacterExcelTable<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<CharacterExcelTable<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `CharacterExcelTable` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_character_excel_table_unchecked`.
+pub fn size_prefixed_root_as_character_excel_table_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<CharacterExcelTable<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<CharacterExcelTable<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a CharacterExcelTable and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `CharacterExcelTable`.
+pub unsafe fn root_as_character_excel_table_unchecked(buf: &[u8]) -> CharacterExcelTable<'_> {
+  unsafe { flatbuffers::root_unchecked::<CharacterExcelTable>(buf) }
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed CharacterExcelTable and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `CharacterExcelTable`.
+pub unsafe fn size_prefixed_root_as_character_excel_table_unchecked(buf: &[u8]) -> CharacterExcelTable<'_> {
+  unsafe { flatbuffers::size_prefixed_root_unchecked::<CharacterExcelTable>(buf) }
+}
+#[inline]
+pub fn finish_character_excel_table_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+    root: flatbuffers::WIPOffset<CharacterExcelTable<'a>>) {
+  fbb.finish(root, None);
+}
+
+#[inline]
+pub fn finish_size_prefixed_character_excel_table_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>, root: flatbuffers::WIPOffset<CharacterExcelTable<'a>>) {
+  fbb.finish_size_prefixed(root, None);
+}
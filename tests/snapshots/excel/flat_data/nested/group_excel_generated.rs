@@ -0,0 +1,248 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+pub enum GroupExcelOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct GroupExcel<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for GroupExcel<'a> {
+  type Inner = GroupExcel<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> GroupExcel<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_MEMBERS: flatbuffers::VOffsetT = 6;
+  pub const VT_STEP: flatbuffers::VOffsetT = 8;
+  pub const VT_NAME: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    GroupExcel { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args GroupExcelArgs<'args>
+  ) -> flatbuffers::WIPOffset<GroupExcel<'bldr>> {
+    let mut builder = GroupExcelBuilder::new(_fbb);
+    if let Some(x) = args.Name { builder.add_Name(x); }
+    builder.add_Step(args.Step);
+    if let Some(x) = args.Members { builder.add_Members(x); }
+    builder.add_Id(args.Id);
+    builder.finish()
+  }
+
+  pub fn unpack(&self) -> GroupExcelT {
+    let Id = self.Id();
+    let Members = self.Members().map(|x| {
+      x.iter().map(|t| t.unpack()).collect()
+    });
+    let Step = self.Step();
+    let Name = self.Name().map(|x| {
+      x.to_string()
+    });
+    GroupExcelT {
+      Id,
+      Members,
+      Step,
+      Name,
+    }
+  }
+
+  #[inline]
+  pub fn key_compare_less_than(&self, o: &GroupExcel) -> bool {
+    self.Id() < o.Id()
+  }
+
+  #[inline]
+  pub fn key_compare_with_value(&self, val: i32) -> ::core::cmp::Ordering {
+    let key = self.Id();
+    key.cmp(&val)
+  }
+  #[inline]
+  pub fn Id(&self) -> i32 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i32>(GroupExcel::VT_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn Members(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<super::super::super::flat_data::CharacterExcel<'a>>>> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<super::super::super::flat_data::CharacterExcel<'a>>>>>(GroupExcel::VT_MEMBERS, None)}
+  }
+  #[inline]
+  pub fn Step(&self) -> super::super::super::flat_data::ProductionStep {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<super::super::super::flat_data::ProductionStep>(GroupExcel::VT_STEP, Some(super::super::super::flat_data::ProductionStep::Doing)).unwrap()}
+  }
+  #[inline]
+  pub fn Name(&self) -> Option<&'a str> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(GroupExcel::VT_NAME, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for GroupExcel<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<i32>("Id", Self::VT_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<super::super::super::flat_data::CharacterExcel>>>>("Members", Self::VT_MEMBERS, false)?
+     .visit_field::<super::super::super::flat_data::ProductionStep>("Step", Self::VT_STEP, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("Name", Self::VT_NAME, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct GroupExcelArgs<'a> {
+    pub Id: i32,
+    pub Members: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<super::super::super::flat_data::CharacterExcel<'a>>>>>,
+    pub Step: super::super::super::flat_data::ProductionStep,
+    pub Name: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for GroupExcelArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    GroupExcelArgs {
+      Id: 0,
+      Members: None,
+      Step: super::super::super::flat_data::ProductionStep::Doing,
+      Name: None,
+    }
+  }
+}
+
+impl Serialize for GroupExcel<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("GroupExcel", 4)?;
+      s.serialize_field("Id", &self.Id())?;
+      if let Some(f) = self.Members() {
+        s.serialize_field("Members", &f)?;
+      } else {
+        s.skip_field("Members")?;
+      }
+      s.serialize_field("Step", &self.Step())?;
+      if let Some(f) = self.Name() {
+        s.serialize_field("Name", &f)?;
+      } else {
+        s.skip_field("Name")?;
+      }
+    s.end()
+  }
+}
+
+pub struct GroupExcelBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> GroupExcelBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_Id(&mut self, Id: i32) {
+    self.fbb_.push_slot::<i32>(GroupExcel::VT_ID, Id, 0);
+  }
+  #[inline]
+  pub fn add_Members(&mut self, Members: flatbuffers::WIPOffset<flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<super::super::super::flat_data::CharacterExcel<'b>>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(GroupExcel::VT_MEMBERS, Members);
+  }
+  #[inline]
+  pub fn add_Step(&mut self, Step: super::super::super::flat_data::ProductionStep) {
+    self.fbb_.push_slot::<super::super::super::flat_data::ProductionStep>(GroupExcel::VT_STEP, Step, super::super::super::flat_data::ProductionStep::Doing);
+  }
+  #[inline]
+  pub fn add_Name(&mut self, Name: flatbuffers::WIPOffset<&'b str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(GroupExcel::VT_NAME, Name);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> GroupExcelBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    GroupExcelBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<GroupExcel<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl ::core::fmt::Debug for GroupExcel<'_> {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let mut ds = f.debug_struct("GroupExcel");
+      ds.field("Id", &self.Id());
+      ds.field("Members", &self.Members());
+      ds.field("Step", &self.Step());
+      ds.field("Name", &self.Name());
+      ds.finish()
+  }
+}
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupExcelT {
+  pub Id: i32,
+  pub Members: Option<Vec<super::super::super::flat_data::CharacterExcelT>>,
+  pub Step: super::super::super::flat_data::ProductionStep,
+  pub Name: Option<String>,
+}
+impl Default for GroupExcelT {
+  fn default() -> Self {
+    Self {
+      Id: 0,
+      Members: None,
+      Step: super::super::super::flat_data::ProductionStep::Doing,
+      Name: None,
+    }
+  }
+}
+impl GroupExcelT {
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(
+    &self,
+    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>
+  ) -> flatbuffers::WIPOffset<GroupExcel<'b>> {
+    let Id = self.Id;
+    let Members = self.Members.as_ref().map(|x|{
+      let w: Vec<_> = x.iter().map(|t| t.pack(_fbb)).collect();_fbb.create_vector(&w)
+    });
+    let Step = self.Step;
+    let Name = self.Name.as_ref().map(|x|{
+      _fbb.create_string(x)
+    });
+    GroupExcel::create(_fbb, &GroupExcelArgs{
+      Id,
+      Members,
+      Step,
+      Name,
+    })
+  }
+}
@@ -0,0 +1,274 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+pub enum RewardExcelOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RewardExcel<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RewardExcel<'a> {
+  type Inner = RewardExcel<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> RewardExcel<'a> {
+  pub const VT_REWARD_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_REWARD: flatbuffers::VOffsetT = 6;
+  pub const VT_AMOUNT: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RewardExcel { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RewardExcelArgs
+  ) -> flatbuffers::WIPOffset<RewardExcel<'bldr>> {
+    let mut builder = RewardExcelBuilder::new(_fbb);
+    if let Some(x) = args.Reward { builder.add_Reward(x); }
+    builder.add_Amount(args.Amount);
+    builder.add_Reward_type(args.Reward_type);
+    builder.finish()
+  }
+
+  pub fn unpack(&self) -> RewardExcelT {
+    let Reward_ = match self.Reward_type() {
+      Reward::NONE => RewardT::NONE,
+      Reward::CharacterExcel => RewardT::CharacterExcel(Box::new(
+        self.Reward_as_character_excel()
+            .expect("Invalid union table, expected `Reward::CharacterExcel`.")
+            .unpack()
+      )),
+      Reward::ScenarioExcel => RewardT::ScenarioExcel(Box::new(
+        self.Reward_as_scenario_excel()
+            .expect("Invalid union table, expected `Reward::ScenarioExcel`.")
+            .unpack()
+      )),
+      _ => RewardT::NONE,
+    };
+    let Amount = self.Amount();
+    RewardExcelT {
+      Reward: Reward_,
+      Amount,
+    }
+  }
+
+  #[inline]
+  pub fn Reward_type(&self) -> Reward {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Reward>(RewardExcel::VT_REWARD_TYPE, Some(Reward::NONE)).unwrap()}
+  }
+  #[inline]
+  pub fn Reward(&self) -> Option<flatbuffers::Table<'a>> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(RewardExcel::VT_REWARD, None)}
+  }
+  #[inline]
+  pub fn Amount(&self) -> i16 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i16>(RewardExcel::VT_AMOUNT, Some(-5)).unwrap()}
+  }
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn Reward_as_character_excel(&self) -> Option<CharacterExcel<'a>> {
+    if self.Reward_type() == Reward::CharacterExcel {
+      self.Reward().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { CharacterExcel::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn Reward_as_scenario_excel(&self) -> Option<ScenarioExcel<'a>> {
+    if self.Reward_type() == Reward::ScenarioExcel {
+      self.Reward().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { ScenarioExcel::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+}
+
+impl flatbuffers::Verifiable for RewardExcel<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_union::<Reward, _>("Reward_type", Self::VT_REWARD_TYPE, "Reward", Self::VT_REWARD, false, |key, v, pos| {
+        match key {
+          Reward::CharacterExcel => v.verify_union_variant::<flatbuffers::ForwardsUOffset<CharacterExcel>>("Reward::CharacterExcel", pos),
+          Reward::ScenarioExcel => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ScenarioExcel>>("Reward::ScenarioExcel", pos),
+          _ => Ok(()),
+        }
+     })?
+     .visit_field::<i16>("Amount", Self::VT_AMOUNT, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RewardExcelArgs {
+    pub Reward_type: Reward,
+    pub Reward: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+    pub Amount: i16,
+}
+impl Default for RewardExcelArgs {
+  #[inline]
+  fn default() -> Self {
+    RewardExcelArgs {
+      Reward_type: Reward::NONE,
+      Reward: None,
+      Amount: -5,
+    }
+  }
+}
+
+impl Serialize for RewardExcel<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("RewardExcel", 3)?;
+      s.serialize_field("Reward_type", &self.Reward_type())?;
+      match self.Reward_type() {
+        Reward::NONE => (),
+        Reward::CharacterExcel => {
+          let f = self.Reward_as_character_excel()
+            .expect("Invalid union table, expected `Reward::CharacterExcel`.");
+          s.serialize_field("Reward", &f)?;
+        }
+        Reward::ScenarioExcel => {
+          let f = self.Reward_as_scenario_excel()
+            .expect("Invalid union table, expected `Reward::ScenarioExcel`.");
+          s.serialize_field("Reward", &f)?;
+        }
+        _ => unimplemented!(),
+      }
+      s.serialize_field("Amount", &self.Amount())?;
+    s.end()
+  }
+}
+
+pub struct RewardExcelBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RewardExcelBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_Reward_type(&mut self, Reward_type: Reward) {
+    self.fbb_.push_slot::<Reward>(RewardExcel::VT_REWARD_TYPE, Reward_type, Reward::NONE);
+  }
+  #[inline]
+  pub fn add_Reward(&mut self, Reward_: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(RewardExcel::VT_REWARD, Reward_);
+  }
+  #[inline]
+  pub fn add_Amount(&mut self, Amount: i16) {
+    self.fbb_.push_slot::<i16>(RewardExcel::VT_AMOUNT, Amount, -5);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RewardExcelBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RewardExcelBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RewardExcel<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl ::core::fmt::Debug for RewardExcel<'_> {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let mut ds = f.debug_struct("RewardExcel");
+      ds.field("Reward_type", &self.Reward_type());
+      match self.Reward_type() {
+        Reward::CharacterExcel => {
+          if let Some(x) = self.Reward_as_character_excel() {
+            ds.field("Reward", &x)
+          } else {
+            ds.field("Reward", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        Reward::ScenarioExcel => {
+          if let Some(x) = self.Reward_as_scenario_excel() {
+            ds.field("Reward", &x)
+          } else {
+            ds.field("Reward", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        _ => {
+          let x: Option<()> = None;
+          ds.field("Reward", &x)
+        },
+      };
+      ds.field("Amount", &self.Amount());
+      ds.finish()
+  }
+}
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardExcelT {
+  pub Reward: RewardT,
+  pub Amount: i16,
+}
+impl Default for RewardExcelT {
+  fn default() -> Self {
+    Self {
+      Reward: RewardT::NONE,
+      Amount: -5,
+    }
+  }
+}
+impl RewardExcelT {
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(
+    &self,
+    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>
+  ) -> flatbuffers::WIPOffset<RewardExcel<'b>> {
+    let Reward_type = self.Reward.reward_type();
+    let Reward_ = self.Reward.pack(_fbb);
+    let Amount = self.Amount;
+    RewardExcel::create(_fbb, &RewardExcelArgs{
+      Reward_type,
+      Reward: Reward_,
+      Amount,
+    })
+  }
+}
@@ -0,0 +1,183 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_REWARD: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_REWARD: u8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_REWARD: [Reward; 3] = [
+  Reward::NONE,
+  Reward::CharacterExcel,
+  Reward::ScenarioExcel,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Reward(pub u8);
+#[allow(non_upper_case_globals)]
+impl Reward {
+  pub const NONE: Self = Self(0);
+  pub const CharacterExcel: Self = Self(1);
+  pub const ScenarioExcel: Self = Self(2);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::NONE,
+    Self::CharacterExcel,
+    Self::ScenarioExcel,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::NONE => Some("NONE"),
+      Self::CharacterExcel => Some("CharacterExcel"),
+      Self::ScenarioExcel => Some("ScenarioExcel"),
+      _ => None,
+    }
+  }
+}
+impl ::core::fmt::Debug for Reward {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl Serialize for Reward {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_unit_variant("Reward", self.0 as u32, self.variant_name().unwrap())
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for Reward {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = unsafe { flatbuffers::read_scalar_at::<u8>(buf, loc) };
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for Reward {
+    type Output = Reward;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        unsafe { flatbuffers::emplace_scalar::<u8>(dst, self.0) };
+    }
+}
+
+impl flatbuffers::EndianScalar for Reward {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for Reward {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Reward {}
+pub struct RewardUnionTableOffset {}
+
+#[allow(clippy::upper_case_acronyms)]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RewardT {
+  NONE,
+  CharacterExcel(Box<CharacterExcelT>),
+  ScenarioExcel(Box<ScenarioExcelT>),
+}
+impl Default for RewardT {
+  fn default() -> Self {
+    Self::NONE
+  }
+}
+impl RewardT {
+  pub fn reward_type(&self) -> Reward {
+    match self {
+      Self::NONE => Reward::NONE,
+      Self::CharacterExcel(_) => Reward::CharacterExcel,
+      Self::ScenarioExcel(_) => Reward::ScenarioExcel,
+    }
+  }
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(&self, fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>) -> Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>> {
+    match self {
+      Self::NONE => None,
+      Self::CharacterExcel(v) => Some(v.pack(fbb).as_union_value()),
+      Self::ScenarioExcel(v) => Some(v.pack(fbb).as_union_value()),
+    }
+  }
+  /// If the union variant matches, return the owned CharacterExcelT, setting the union to NONE.
+  pub fn take_character_excel(&mut self) -> Option<Box<CharacterExcelT>> {
+    if let Self::CharacterExcel(_) = self {
+      let v = core::mem::replace(self, Self::NONE);
+      if let Self::CharacterExcel(w) = v {
+        Some(w)
+      } else {
+        unreachable!()
+      }
+    } else {
+      None
+    }
+  }
+  /// If the union variant matches, return a reference to the CharacterExcelT.
+  pub fn as_character_excel(&self) -> Option<&CharacterExcelT> {
+    if let Self::CharacterExcel(v) = self { Some(v.as_ref()) } else { None }
+  }
+  /// If the union variant matches, return a mutable reference to the CharacterExcelT.
+  pub fn as_character_excel_mut(&mut self) -> Option<&mut CharacterExcelT> {
+    if let Self::CharacterExcel(v) = self { Some(v.as_mut()) } else { None }
+  }
+  /// If the union variant matches, return the owned ScenarioExcelT, setting the union to NONE.
+  pub fn take_scenario_excel(&mut self) -> Option<Box<ScenarioExcelT>> {
+    if let Self::ScenarioExcel(_) = self {
+      let v = core::mem::replace(self, Self::NONE);
+      if let Self::ScenarioExcel(w) = v {
+        Some(w)
+      } else {
+        unreachable!()
+      }
+    } else {
+      None
+    }
+  }
+  /// If the union variant matches, return a reference to the ScenarioExcelT.
+  pub fn as_scenario_excel(&self) -> Option<&ScenarioExcelT> {
+    if let Self::ScenarioExcel(v) = self { Some(v.as_ref()) } else { None }
+  }
+  /// If the union variant matches, return a mutable reference to the ScenarioExcelT.
+  pub fn as_scenario_excel_mut(&mut self) -> Option<&mut ScenarioExcelT> {
+    if let Self::ScenarioExcel(v) = self { Some(v.as_mut()) } else { None }
+  }
+}
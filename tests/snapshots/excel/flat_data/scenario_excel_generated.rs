@@ -0,0 +1,212 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+// @generated
+extern crate alloc;
+extern crate flatbuffers;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::cmp::Ordering;
+extern crate serde;
+use self::serde::ser::{Serialize, Serializer, SerializeStruct};
+use self::flatbuffers::{EndianScalar, Follow};
+use super::*;
+pub enum ScenarioExcelOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ScenarioExcel<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ScenarioExcel<'a> {
+  type Inner = ScenarioExcel<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: unsafe { flatbuffers::Table::new(buf, loc) } }
+  }
+}
+
+impl<'a> ScenarioExcel<'a> {
+  pub const VT_FRAMEHASH: flatbuffers::VOffsetT = 4;
+  pub const VT_BOUNDS: flatbuffers::VOffsetT = 6;
+  pub const VT_NAME: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ScenarioExcel { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ScenarioExcelArgs<'args>
+  ) -> flatbuffers::WIPOffset<ScenarioExcel<'bldr>> {
+    let mut builder = ScenarioExcelBuilder::new(_fbb);
+    builder.add_FrameHash(args.FrameHash);
+    if let Some(x) = args.Name { builder.add_Name(x); }
+    if let Some(x) = args.Bounds { builder.add_Bounds(x); }
+    builder.finish()
+  }
+
+  pub fn unpack(&self) -> ScenarioExcelT {
+    let FrameHash = self.FrameHash();
+    let Bounds_ = {
+      let x = self.Bounds();
+      x.iter().map(|t| t.unpack()).collect()
+    };
+    let Name = {
+      let x = self.Name();
+      x.to_string()
+    };
+    ScenarioExcelT {
+      FrameHash,
+      Bounds: Bounds_,
+      Name,
+    }
+  }
+
+  #[inline]
+  pub fn FrameHash(&self) -> u64 {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ScenarioExcel::VT_FRAMEHASH, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn Bounds(&self) -> flatbuffers::Vector<'a, Bounds> {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, Bounds>>>(ScenarioExcel::VT_BOUNDS, None).unwrap()}
+  }
+  #[inline]
+  pub fn Name(&self) -> &'a str {
+// Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ScenarioExcel::VT_NAME, None).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ScenarioExcel<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("FrameHash", Self::VT_FRAMEHASH, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, Bounds>>>("Bounds", Self::VT_BOUNDS, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("Name", Self::VT_NAME, true)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ScenarioExcelArgs<'a> {
+    pub FrameHash: u64,
+    pub Bounds: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, Bounds>>>,
+    pub Name: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ScenarioExcelArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ScenarioExcelArgs {
+      FrameHash: 0,
+      Bounds: None, // required field,
+      Name: None, // required field,
+    }
+  }
+}
+
+impl Serialize for ScenarioExcel<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut s = serializer.serialize_struct("ScenarioExcel", 3)?;
+      s.serialize_field("FrameHash", &self.FrameHash())?;
+      s.serialize_field("Bounds", &self.Bounds())?;
+      s.serialize_field("Name", &self.Name())?;
+    s.end()
+  }
+}
+
+pub struct ScenarioExcelBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ScenarioExcelBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_FrameHash(&mut self, FrameHash: u64) {
+    self.fbb_.push_slot::<u64>(ScenarioExcel::VT_FRAMEHASH, FrameHash, 0);
+  }
+  #[inline]
+  pub fn add_Bounds(&mut self, Bounds_: flatbuffers::WIPOffset<flatbuffers::Vector<'b, Bounds>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ScenarioExcel::VT_BOUNDS, Bounds_);
+  }
+  #[inline]
+  pub fn add_Name(&mut self, Name: flatbuffers::WIPOffset<&'b str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ScenarioExcel::VT_NAME, Name);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ScenarioExcelBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ScenarioExcelBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ScenarioExcel<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, ScenarioExcel::VT_BOUNDS,"Bounds");
+    self.fbb_.required(o, ScenarioExcel::VT_NAME,"Name");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl ::core::fmt::Debug for ScenarioExcel<'_> {
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let mut ds = f.debug_struct("ScenarioExcel");
+      ds.field("FrameHash", &self.FrameHash());
+      ds.field("Bounds", &self.Bounds());
+      ds.field("Name", &self.Name());
+      ds.finish()
+  }
+}
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioExcelT {
+  pub FrameHash: u64,
+  pub Bounds: Vec<BoundsT>,
+  pub Name: String,
+}
+impl Default for ScenarioExcelT {
+  fn default() -> Self {
+    Self {
+      FrameHash: 0,
+      Bounds: Default::default(),
+      Name: "".to_string(),
+    }
+  }
+}
+impl ScenarioExcelT {
+  pub fn pack<'b, A: flatbuffers::Allocator + 'b>(
+    &self,
+    _fbb: &mut flatbuffers::FlatBufferBuilder<'b, A>
+  ) -> flatbuffers::WIPOffset<ScenarioExcel<'b>> {
+    let FrameHash = self.FrameHash;
+    let Bounds_ = Some({
+      let x = &self.Bounds;
+      let w: Vec<_> = x.iter().map(|t| t.pack()).collect();_fbb.create_vector(&w)
+    });
+    let Name = Some({
+      let x = &self.Name;
+      _fbb.create_string(x)
+    });
+    ScenarioExcel::create(_fbb, &ScenarioExcelArgs{
+      FrameHash,
+      Bounds: Bounds_,
+      Name,
+    })
+  }
+}
@@ -0,0 +1,30 @@
+// Automatically generated by the Flatbuffers compiler. Do not modify.
+// @generated
+pub mod flat_data {
+  use super::*;
+  pub mod nested {
+    use super::*;
+    mod group_excel_generated;
+    pub use self::group_excel_generated::*;
+  } // nested
+  mod production_step_generated;
+  pub use self::production_step_generated::*;
+  mod rarity_generated;
+  pub use self::rarity_generated::*;
+  mod tactic_role_generated;
+  pub use self::tactic_role_generated::*;
+  mod reward_generated;
+  pub use self::reward_generated::*;
+  mod position_generated;
+  pub use self::position_generated::*;
+  mod bounds_generated;
+  pub use self::bounds_generated::*;
+  mod character_excel_generated;
+  pub use self::character_excel_generated::*;
+  mod character_excel_table_generated;
+  pub use self::character_excel_table_generated::*;
+  mod scenario_excel_generated;
+  pub use self::scenario_excel_generated::*;
+  mod reward_excel_generated;
+  pub use self::reward_excel_generated::*;
+} // flat_data